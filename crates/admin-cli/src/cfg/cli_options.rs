@@ -109,6 +109,12 @@ pub struct CliOptions {
     #[clap(subcommand)]
     pub commands: Option<CliCommand>,
 
+    #[clap(long, global = true, value_name = "SECONDS", default_value_t = 120)]
+    #[clap(
+        help = "Abort any single API request that takes longer than this many seconds. 0 disables the timeout."
+    )]
+    pub timeout: u64,
+
     #[clap(short = 'p', long, default_value_t = 100)]
     #[clap(help = "For commands that internally retrieve data with paging, use this page size.")]
     pub internal_page_size: usize,
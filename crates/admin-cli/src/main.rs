@@ -18,6 +18,7 @@
 // CLI enums variants can be rather large, we are ok with that.
 #![allow(clippy::large_enum_variant)]
 use std::pin::Pin;
+use std::time::Duration;
 
 use ::rpc::admin_cli::CarbideCliError;
 use ::rpc::forge_api_client::ForgeApiClient;
@@ -68,6 +69,8 @@ mod machine_validation;
 mod managed_host;
 mod measurement;
 mod metadata;
+#[cfg(test)]
+mod mock_api;
 mod mlx;
 mod network_devices;
 mod network_security_group;
@@ -183,6 +186,8 @@ async fn main() -> color_eyre::Result<()> {
 
     let mut client_config = ForgeClientConfig::new(forge_root_ca_path, forge_client_cert);
     client_config.socks_proxy(proxy);
+    client_config
+        .request_timeout((config.timeout > 0).then(|| Duration::from_secs(config.timeout)));
 
     let ctx = RuntimeContext {
         api_client: ApiClient(ForgeApiClient::new(&ApiConfig::new(&url, &client_config))),
@@ -0,0 +1,153 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A mocked Forge API for testing commands end to end.
+//!
+//! `MockApi` serves canned responses over gRPC on a local port, and hands out an `ApiClient`
+//! connected to it, so tests can run a command and check what it prints.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use ::rpc::forge_api_client::ForgeApiClient;
+use ::rpc::forge_tls_client::ForgeClientT;
+use ::rpc::protos::forge::forge_client::ForgeClient;
+use bytes::{BufMut, Bytes, BytesMut};
+use http_body_util::{BodyExt, StreamBody};
+use hyper::HeaderMap;
+use hyper::body::{Frame, Incoming};
+use hyper::service::service_fn;
+use hyper_util::client::legacy;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use tokio::net::TcpListener;
+use tower::ServiceExt;
+
+use crate::rpc::ApiClient;
+
+type ResponseBody =
+    StreamBody<futures::stream::Iter<std::vec::IntoIter<Result<Frame<Bytes>, Infallible>>>>;
+
+/// Canned responses of the mocked API, by RPC name (e.g. `GetRackFirmware`).
+/// RPCs without a response fail with `Unimplemented`.
+#[derive(Debug, Default)]
+pub struct MockApi {
+    responses: HashMap<&'static str, Result<Bytes, tonic::Code>>,
+}
+
+impl MockApi {
+    /// Answers `rpc` with `response`
+    pub fn respond(mut self, rpc: &'static str, response: impl prost::Message) -> Self {
+        self.responses
+            .insert(rpc, Ok(response.encode_to_vec().into()));
+        self
+    }
+
+    /// Fails `rpc` with `code`
+    pub fn fail(mut self, rpc: &'static str, code: tonic::Code) -> Self {
+        self.responses.insert(rpc, Err(code));
+        self
+    }
+
+    /// Starts serving the responses, and returns a client connected to them
+    pub async fn client(self) -> ApiClient {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let responses = Arc::new(self.responses);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let responses = responses.clone();
+                let service = service_fn(move |request: hyper::Request<Incoming>| {
+                    let responses = responses.clone();
+                    async move {
+                        let rpc = request
+                            .uri()
+                            .path()
+                            .rsplit('/')
+                            .next()
+                            .unwrap_or_default()
+                            .to_string();
+                        request.into_body().collect().await.ok();
+                        Ok::<_, Infallible>(respond(responses.get(rpc.as_str())))
+                    }
+                });
+                tokio::spawn(
+                    hyper::server::conn::http2::Builder::new(TokioExecutor::new())
+                        .serve_connection(TokioIo::new(stream), service),
+                );
+            }
+        });
+
+        ApiClient(ForgeApiClient::build(MockConnectionProvider { url }))
+    }
+}
+
+/// Encodes a gRPC response: the message, if any, followed by the status in the trailers
+fn respond(response: Option<&Result<Bytes, tonic::Code>>) -> hyper::Response<ResponseBody> {
+    let mut frames = Vec::new();
+    let code = match response {
+        Some(Ok(message)) => {
+            let mut data = BytesMut::with_capacity(5 + message.len());
+            data.put_u8(0); // Not compressed
+            data.put_u32(message.len() as u32);
+            data.put_slice(message);
+            frames.push(Ok(Frame::data(data.freeze())));
+            tonic::Code::Ok
+        }
+        Some(Err(code)) => *code,
+        None => tonic::Code::Unimplemented,
+    };
+    let mut trailers = HeaderMap::new();
+    trailers.insert("grpc-status", (code as i32).into());
+    frames.push(Ok(Frame::trailers(trailers)));
+
+    hyper::Response::builder()
+        .header("content-type", "application/grpc")
+        .body(StreamBody::new(futures::stream::iter(frames)))
+        .unwrap()
+}
+
+/// Connects to the mocked API over plaintext HTTP/2
+#[derive(Debug)]
+struct MockConnectionProvider {
+    url: String,
+}
+
+#[async_trait::async_trait]
+impl tonic_client_wrapper::ConnectionProvider<ForgeClientT> for MockConnectionProvider {
+    async fn provide_connection(&self) -> Result<ForgeClientT, tonic::Status> {
+        let uri: hyper::Uri = self
+            .url
+            .parse()
+            .map_err(|e| tonic::Status::internal(format!("Invalid url {}: {e}", self.url)))?;
+        let client = legacy::Client::builder(TokioExecutor::new())
+            .http2_only(true)
+            .build_http::<tonic::body::Body>()
+            .boxed_clone();
+        Ok(ForgeClient::with_origin(client, uri))
+    }
+
+    async fn connection_is_stale(&self, _last_connected: SystemTime) -> bool {
+        false
+    }
+
+    fn connection_url(&self) -> &str {
+        &self.url
+    }
+}
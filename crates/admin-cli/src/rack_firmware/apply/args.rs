@@ -28,4 +28,10 @@ pub struct Args {
 
     #[clap(help = "Firmware type: dev or prod", value_parser = ["dev", "prod"])]
     pub firmware_type: String,
+
+    #[clap(
+        long = "node-id",
+        help = "Only update this node of the rack instead of all of its devices. Can be repeated"
+    )]
+    pub node_ids: Vec<String>,
 }
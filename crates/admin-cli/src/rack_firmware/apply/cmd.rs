@@ -19,6 +19,7 @@ use ::rpc::admin_cli::{CarbideCliError, OutputFormat};
 use prettytable::{Cell, Row, Table};
 
 use super::args::Args;
+use crate::rack_firmware::with_schema_version;
 use crate::rpc::ApiClient;
 
 pub async fn apply(
@@ -35,6 +36,7 @@ pub async fn apply(
         rack_id: Some(opts.rack_id),
         firmware_id: opts.firmware_id,
         firmware_type: opts.firmware_type,
+        node_ids: opts.node_ids,
     };
 
     let response = api_client
@@ -44,23 +46,7 @@ pub async fn apply(
         .map_err(CarbideCliError::from)?;
 
     if format == OutputFormat::Json {
-        let result = serde_json::json!({
-            "total_updates": response.total_updates,
-            "successful_updates": response.successful_updates,
-            "failed_updates": response.failed_updates,
-            "device_results": response.device_results.iter().map(|r| serde_json::json!({
-                "device_id": r.device_id,
-                "device_type": r.device_type,
-                "success": r.success,
-                "message": r.message,
-                "job_id": r.job_id,
-                "node_jobs": r.node_jobs.iter().map(|j| serde_json::json!({
-                    "node_id": j.node_id,
-                    "job_id": j.job_id,
-                })).collect::<Vec<_>>(),
-            })).collect::<Vec<_>>(),
-        });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        println!("{}", serde_json::to_string_pretty(&apply_json(&response))?);
     } else {
         let mut table = Table::new();
         table.set_titles(Row::new(vec![
@@ -136,3 +122,23 @@ pub async fn apply(
 
     Ok(())
 }
+
+/// Builds the JSON printed by `rack-firmware apply --format json`.
+pub(crate) fn apply_json(response: &rpc::forge::RackFirmwareApplyResponse) -> serde_json::Value {
+    with_schema_version(serde_json::json!({
+        "total_updates": response.total_updates,
+        "successful_updates": response.successful_updates,
+        "failed_updates": response.failed_updates,
+        "device_results": response.device_results.iter().map(|r| serde_json::json!({
+            "device_id": r.device_id,
+            "device_type": r.device_type,
+            "success": r.success,
+            "message": r.message,
+            "job_id": r.job_id,
+            "node_jobs": r.node_jobs.iter().map(|j| serde_json::json!({
+                "node_id": j.node_id,
+                "job_id": j.job_id,
+            })).collect::<Vec<_>>(),
+        })).collect::<Vec<_>>(),
+    }))
+}
@@ -21,8 +21,19 @@ use clap::Parser;
 
 #[derive(Parser, Debug)]
 pub struct Args {
-    #[clap(help = "Path to JSON configuration file")]
+    #[clap(long = "config", help = "Path to JSON configuration file")]
     pub json_file: PathBuf,
-    #[clap(help = "Artifactory token for downloading firmware files")]
+    #[clap(
+        long = "token",
+        help = "Artifactory token for downloading firmware files"
+    )]
     pub artifactory_token: String,
+
+    #[clap(
+        long = "label",
+        value_name = "KEY=VALUE",
+        value_parser = crate::rack_firmware::parse_label,
+        help = "Label to attach to the configuration. Can be repeated"
+    )]
+    pub labels: Vec<(String, String)>,
 }
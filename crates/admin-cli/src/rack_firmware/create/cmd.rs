@@ -16,45 +16,72 @@
  */
 
 use std::fs;
+use std::io::Write;
+use std::path::Path;
 
 use ::rpc::admin_cli::{CarbideCliError, OutputFormat};
 
 use super::args::Args;
+use crate::rack_firmware::{board_sku_count, with_schema_version};
 use crate::rpc::ApiClient;
 
 pub async fn create(
     opts: Args,
     format: OutputFormat,
     api_client: &ApiClient,
+    output: &mut impl Write,
 ) -> Result<(), CarbideCliError> {
-    // Read JSON file
-    let config_json = fs::read_to_string(&opts.json_file).map_err(|e| {
-        CarbideCliError::GenericError(format!(
-            "Failed to read file {}: {}",
-            opts.json_file.display(),
-            e
-        ))
-    })?;
-
-    // Check that the JSON is valid
-    serde_json::from_str::<serde_json::Value>(&config_json)
-        .map_err(|e| CarbideCliError::GenericError(format!("Invalid JSON in file: {}", e)))?;
+    let (config_json, board_sku_count) = read_config(&opts.json_file)?;
 
     let request = rpc::forge::RackFirmwareCreateRequest {
         config_json,
         artifactory_token: opts.artifactory_token,
+        labels: opts.labels.into_iter().collect(),
     };
 
     let result = api_client.0.create_rack_firmware(request).await?;
 
     if format == OutputFormat::Json {
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        let mut json = serde_json::to_value(&result)?;
+        if let Some(object) = json.as_object_mut() {
+            object.insert("board_sku_count".to_string(), board_sku_count.into());
+        }
+        writeln!(
+            output,
+            "{}",
+            serde_json::to_string_pretty(&with_schema_version(json))?
+        )?;
     } else {
-        println!("Created Rack firmware configuration:");
-        println!("  ID: {}", result.id);
-        println!("  Available: {}", result.available);
-        println!("  Created: {}", result.created);
+        writeln!(output, "Created Rack firmware configuration:")?;
+        writeln!(output, "  ID: {}", result.id)?;
+        writeln!(output, "  Available: {}", result.available)?;
+        writeln!(output, "  Created: {}", result.created)?;
+        writeln!(output, "  Board SKUs: {}", board_sku_count)?;
     }
 
     Ok(())
 }
+
+/// Reads a rack firmware configuration file, checking that it exists and
+/// contains valid JSON before anything is sent to the API.
+///
+/// Returns the file contents along with the number of entries in its
+/// `BoardSKUs` array.
+pub(crate) fn read_config(path: &Path) -> Result<(String, usize), CarbideCliError> {
+    if !path.is_file() {
+        return Err(CarbideCliError::GenericError(format!(
+            "Configuration file not found: {}",
+            path.display()
+        )));
+    }
+
+    let config_json = fs::read_to_string(path).map_err(|e| {
+        CarbideCliError::GenericError(format!("Failed to read file {}: {}", path.display(), e))
+    })?;
+
+    // Check that the JSON is valid
+    let config = serde_json::from_str::<serde_json::Value>(&config_json)
+        .map_err(|e| CarbideCliError::GenericError(format!("Invalid JSON in file: {}", e)))?;
+
+    Ok((config_json, board_sku_count(&config)))
+}
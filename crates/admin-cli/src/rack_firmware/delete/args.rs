@@ -19,6 +19,9 @@ use clap::Parser;
 
 #[derive(Parser, Debug)]
 pub struct Args {
-    #[clap(help = "ID of the configuration to delete")]
+    #[clap(long, help = "ID of the configuration to delete")]
     pub id: String,
+
+    #[clap(long, short = 'y', help = "Delete without asking for confirmation")]
+    pub yes: bool,
 }
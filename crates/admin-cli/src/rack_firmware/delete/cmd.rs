@@ -15,18 +15,82 @@
  * limitations under the License.
  */
 
-use ::rpc::admin_cli::CarbideCliError;
+use std::io::{BufRead, Write};
+
+use ::rpc::admin_cli::{CarbideCliError, OutputFormat};
 
 use super::args::Args;
+use crate::rack_firmware::{board_sku_count, with_schema_version};
 use crate::rpc::ApiClient;
 
-pub async fn delete(opts: Args, api_client: &ApiClient) -> Result<(), CarbideCliError> {
+pub async fn delete(
+    opts: Args,
+    format: OutputFormat,
+    api_client: &ApiClient,
+    output: &mut impl Write,
+) -> Result<(), CarbideCliError> {
     let id = opts.id;
+
+    // Fetch the configuration first, so the confirmation and the final report
+    // can say what is about to be removed.
+    let config = match api_client
+        .0
+        .get_rack_firmware(rpc::forge::RackFirmwareGetRequest { id: id.clone() })
+        .await
+    {
+        Ok(config) => config,
+        Err(status) if status.code() == tonic::Code::NotFound => {
+            return Err(CarbideCliError::GenericError(format!(
+                "Rack firmware configuration not found: {}",
+                id
+            )));
+        }
+        Err(err) => return Err(CarbideCliError::from(err)),
+    };
+
+    // Keep the prompt out of JSON output, so it stays parseable
+    let confirmed = if format == OutputFormat::Json {
+        confirm_delete(
+            &config,
+            opts.yes,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stderr(),
+        )?
+    } else {
+        confirm_delete(&config, opts.yes, &mut std::io::stdin().lock(), output)?
+    };
+    if !confirmed {
+        if format == OutputFormat::Json {
+            writeln!(
+                output,
+                "{}",
+                serde_json::to_string_pretty(&delete_json(&config, false))?
+            )?;
+        } else {
+            writeln!(
+                output,
+                "Aborted, Rack firmware configuration {} was not deleted",
+                id
+            )?;
+        }
+        return Ok(());
+    }
+
     let request = rpc::forge::RackFirmwareDeleteRequest { id: id.clone() };
 
     match api_client.0.delete_rack_firmware(request).await {
+        Ok(_) if format == OutputFormat::Json => {
+            writeln!(
+                output,
+                "{}",
+                serde_json::to_string_pretty(&delete_json(&config, true))?
+            )?;
+        }
         Ok(_) => {
-            println!("Deleted Rack firmware configuration: {}", id);
+            writeln!(output, "Deleted Rack firmware configuration: {}", id)?;
+            for line in describe_config(&config) {
+                writeln!(output, "  {}", line)?;
+            }
         }
         Err(status) if status.code() == tonic::Code::NotFound => {
             return Err(CarbideCliError::GenericError(format!(
@@ -39,3 +103,68 @@ pub async fn delete(opts: Args, api_client: &ApiClient) -> Result<(), CarbideCli
 
     Ok(())
 }
+
+/// Builds the JSON printed for `rack-firmware delete --format json`.
+pub(crate) fn delete_json(config: &rpc::forge::RackFirmware, deleted: bool) -> serde_json::Value {
+    let board_sku_count = serde_json::from_str::<serde_json::Value>(&config.config_json)
+        .map(|v| board_sku_count(&v))
+        .unwrap_or(0);
+
+    with_schema_version(serde_json::json!({
+        "id": config.id,
+        "deleted": deleted,
+        "board_sku_count": board_sku_count,
+        "available": config.available,
+        "available_types": config.available_types,
+        "created": config.created,
+    }))
+}
+
+/// Asks whether `config` should really be deleted. Returns true right away
+/// when `yes` is set; otherwise prompts on `output` and reads the answer from
+/// `input`. Anything other than "y" or "yes" declines.
+pub(crate) fn confirm_delete(
+    config: &rpc::forge::RackFirmware,
+    yes: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> std::io::Result<bool> {
+    if yes {
+        return Ok(true);
+    }
+
+    writeln!(
+        output,
+        "WARNING: You are about to delete Rack firmware configuration {}:",
+        config.id
+    )?;
+    for line in describe_config(config) {
+        writeln!(output, "  {}", line)?;
+    }
+    write!(output, "Continue? (y/N): ")?;
+    output.flush()?;
+
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Summarizes what deleting `config` removes.
+fn describe_config(config: &rpc::forge::RackFirmware) -> Vec<String> {
+    let board_skus = serde_json::from_str::<serde_json::Value>(&config.config_json)
+        .map(|v| board_sku_count(&v))
+        .unwrap_or(0);
+    let downloaded = if config.available {
+        "all firmware types".to_string()
+    } else if !config.available_types.is_empty() {
+        config.available_types.join(", ")
+    } else {
+        "none".to_string()
+    };
+
+    vec![
+        format!("Board SKUs: {}", board_skus),
+        format!("Downloaded firmware: {}", downloaded),
+        format!("Created: {}", config.created),
+    ]
+}
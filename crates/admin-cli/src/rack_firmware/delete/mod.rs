@@ -26,7 +26,13 @@ use crate::cfg::runtime::RuntimeContext;
 
 impl Run for Args {
     async fn run(self, ctx: &mut RuntimeContext) -> CarbideCliResult<()> {
-        cmd::delete(self, &ctx.api_client).await?;
+        cmd::delete(
+            self,
+            ctx.config.format,
+            &ctx.api_client,
+            &mut std::io::stdout(),
+        )
+        .await?;
         Ok(())
     }
 }
@@ -19,6 +19,7 @@ use ::rpc::admin_cli::{CarbideCliError, OutputFormat};
 use prettytable::{Cell, Row, Table};
 
 use super::args::Args;
+use crate::rack_firmware::with_schema_version;
 use crate::rpc::ApiClient;
 
 pub async fn get(
@@ -41,7 +42,10 @@ pub async fn get(
     };
 
     if format == OutputFormat::Json {
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&with_schema_version(serde_json::to_value(&result)?))?
+        );
     } else {
         println!("Rack Firmware Configuration:");
         println!("  ID: {}", result.id);
@@ -21,4 +21,19 @@ use clap::Parser;
 pub struct Args {
     #[clap(long, help = "Show only available configurations")]
     pub only_available: bool,
+
+    #[clap(
+        long,
+        requires = "only_available",
+        help = "With --only-available, also show configurations available for this firmware type (prod or dev)"
+    )]
+    pub firmware_type: Option<String>,
+
+    #[clap(
+        long = "label",
+        value_name = "KEY=VALUE",
+        value_parser = crate::rack_firmware::parse_label,
+        help = "Show only configurations with this label. Can be repeated"
+    )]
+    pub labels: Vec<(String, String)>,
 }
@@ -15,47 +15,92 @@
  * limitations under the License.
  */
 
+use std::io::Write;
+
 use ::rpc::admin_cli::{CarbideCliError, OutputFormat};
 use prettytable::{Cell, Row, Table};
 
 use super::args::Args;
+use crate::rack_firmware::{board_sku_count, with_schema_version};
 use crate::rpc::ApiClient;
 
 pub async fn list(
     opts: Args,
     format: OutputFormat,
     api_client: &ApiClient,
+    output: &mut impl Write,
 ) -> Result<(), CarbideCliError> {
     let request = rpc::forge::RackFirmwareListRequest {
         only_available: opts.only_available,
+        firmware_type: opts.firmware_type,
+        label_selector: opts.labels.into_iter().collect(),
     };
 
     let result = api_client.0.list_rack_firmware(request).await?;
 
     if format == OutputFormat::Json {
-        println!("{}", serde_json::to_string_pretty(&result.configs)?);
+        let configs = result
+            .configs
+            .iter()
+            .map(|config| {
+                let mut json = serde_json::to_value(config)?;
+                if let Some(object) = json.as_object_mut() {
+                    object.insert(
+                        "board_sku_count".to_string(),
+                        config_board_sku_count(config).into(),
+                    );
+                }
+                Ok(json)
+            })
+            .collect::<Result<Vec<_>, serde_json::Error>>()?;
+        let json = with_schema_version(serde_json::json!({ "configs": configs }));
+        writeln!(output, "{}", serde_json::to_string_pretty(&json)?)?;
     } else if result.configs.is_empty() {
-        println!("No Rack firmware configurations found.");
+        writeln!(output, "No Rack firmware configurations found.")?;
     } else {
-        let mut table = Table::new();
-        table.set_titles(Row::new(vec![
-            Cell::new("ID"),
-            Cell::new("Available"),
-            Cell::new("Created"),
-            Cell::new("Updated"),
-        ]));
+        build_table(&result.configs).print(output)?;
+    }
 
-        for config in result.configs {
-            table.add_row(Row::new(vec![
-                Cell::new(&config.id),
-                Cell::new(&config.available.to_string()),
-                Cell::new(&config.created),
-                Cell::new(&config.updated),
-            ]));
-        }
+    Ok(())
+}
 
-        table.printstd();
+/// Returns the number of board SKUs in `config`, or `None` if its JSON is invalid.
+fn config_board_sku_count(config: &rpc::forge::RackFirmware) -> Option<usize> {
+    serde_json::from_str::<serde_json::Value>(&config.config_json)
+        .map(|v| board_sku_count(&v))
+        .ok()
+}
+
+/// Builds the table shown for `rack-firmware list`.
+pub(crate) fn build_table(configs: &[rpc::forge::RackFirmware]) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("ID"),
+        Cell::new("Board SKUs"),
+        Cell::new("Available"),
+        Cell::new("Created"),
+        Cell::new("Updated"),
+    ]));
+
+    for config in configs {
+        let board_skus = config_board_sku_count(config)
+            .map(|count| count.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        // Partially available configs list the firmware types that can be applied
+        let available = if config.available || config.available_types.is_empty() {
+            config.available.to_string()
+        } else {
+            config.available_types.join(", ")
+        };
+
+        table.add_row(Row::new(vec![
+            Cell::new(&config.id),
+            Cell::new(&board_skus),
+            Cell::new(&available),
+            Cell::new(&config.created),
+            Cell::new(&config.updated),
+        ]));
     }
 
-    Ok(())
+    table
 }
@@ -20,7 +20,9 @@ mod create;
 mod delete;
 mod get;
 mod list;
+mod show;
 mod status;
+mod validate;
 
 #[cfg(test)]
 mod tests;
@@ -34,12 +36,18 @@ pub enum Cmd {
     #[clap(about = "Create a new Rack firmware configuration from JSON file")]
     Create(create::Args),
 
+    #[clap(about = "Validate a Rack firmware configuration JSON file without uploading it")]
+    Validate(validate::Args),
+
     #[clap(about = "Get a Rack firmware configuration by ID")]
     Get(get::Args),
 
     #[clap(about = "List all Rack firmware configurations")]
     List(list::Args),
 
+    #[clap(about = "Show the firmware lookup table built for a configuration")]
+    Show(show::Args),
+
     #[clap(about = "Delete a Rack firmware configuration")]
     Delete(delete::Args),
 
@@ -49,3 +57,37 @@ pub enum Cmd {
     #[clap(about = "Check the status of an async firmware update job")]
     Status(status::Args),
 }
+
+/// Version of the JSON printed by `rack-firmware` commands with `--format json`.
+/// Bump this whenever the shape of that output changes, so scripts consuming
+/// it can detect the change.
+const JSON_SCHEMA_VERSION: u32 = 1;
+
+/// Adds the top-level `schema_version` field to a JSON object printed by a
+/// `rack-firmware` command.
+fn with_schema_version(mut value: serde_json::Value) -> serde_json::Value {
+    if let Some(object) = value.as_object_mut() {
+        object.insert("schema_version".to_string(), JSON_SCHEMA_VERSION.into());
+    }
+    value
+}
+
+/// Returns the number of entries in a rack firmware configuration's
+/// `BoardSKUs` array, or 0 if the array is missing.
+fn board_sku_count(config: &serde_json::Value) -> usize {
+    config
+        .get("BoardSKUs")
+        .and_then(|v| v.as_array())
+        .map(|skus| skus.len())
+        .unwrap_or(0)
+}
+
+/// Parses a `KEY=VALUE` label given on the command line.
+fn parse_label(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("invalid label \"{s}\": expected KEY=VALUE")),
+    }
+}
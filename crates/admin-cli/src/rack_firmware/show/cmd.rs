@@ -0,0 +1,127 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ::rpc::admin_cli::{CarbideCliError, OutputFormat};
+use prettytable::{Cell, Row, Table};
+
+use super::args::Args;
+use crate::rack_firmware::with_schema_version;
+use crate::rpc::ApiClient;
+
+pub async fn show(
+    opts: Args,
+    format: OutputFormat,
+    api_client: &ApiClient,
+) -> Result<(), CarbideCliError> {
+    let id = opts.id;
+    let request = rpc::forge::RackFirmwareGetRequest { id: id.clone() };
+
+    let result = match api_client.0.get_rack_firmware(request).await {
+        Ok(response) => response,
+        Err(status) if status.code() == tonic::Code::NotFound => {
+            return Err(CarbideCliError::GenericError(format!(
+                "Rack firmware configuration not found: {}",
+                id
+            )));
+        }
+        Err(err) => return Err(CarbideCliError::from(err)),
+    };
+
+    let parsed = serde_json::from_str::<serde_json::Value>(&result.parsed_components)
+        .unwrap_or(serde_json::Value::Null);
+
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&with_schema_version(serde_json::to_value(&parsed)?))?
+        );
+        return Ok(());
+    }
+
+    println!("Firmware lookup table for {}:", result.id);
+
+    match build_lookup_tables(&parsed) {
+        Some(tables) => {
+            for (device_type, table) in tables {
+                println!("\n[{}]", device_type);
+                table.printstd();
+            }
+        }
+        None => {
+            // Before the firmware is downloaded, parsed_components holds the
+            // raw BoardSKU components rather than the lookup table.
+            let board_skus = parsed
+                .get("board_skus")
+                .and_then(|v| v.as_array())
+                .map(|skus| skus.len())
+                .unwrap_or(0);
+            println!(
+                "  Lookup table not built yet (available: {}, {} board SKUs parsed)",
+                result.available, board_skus
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Renders a firmware lookup table as one table per device type, sorted by
+/// device type and component key.
+///
+/// Returns `None` if `parsed` isn't a lookup table.
+pub(crate) fn build_lookup_tables(parsed: &serde_json::Value) -> Option<Vec<(String, Table)>> {
+    let devices = parsed.get("devices")?.as_object()?;
+
+    let mut device_types: Vec<_> = devices.iter().collect();
+    device_types.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut tables = Vec::with_capacity(device_types.len());
+    for (device_type, components) in device_types {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![
+            Cell::new("Component"),
+            Cell::new("Target"),
+            Cell::new("Filename"),
+            Cell::new("Version"),
+        ]));
+
+        let mut entries: Vec<_> = components
+            .as_object()
+            .map(|c| c.iter().collect())
+            .unwrap_or_default();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        for (_key, entry) in entries {
+            let field = |name: &str| {
+                entry
+                    .get(name)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("-")
+                    .to_string()
+            };
+            table.add_row(Row::new(vec![
+                Cell::new(&field("component")),
+                Cell::new(&field("target")),
+                Cell::new(&field("filename")),
+                Cell::new(&field("version")),
+            ]));
+        }
+
+        tables.push((device_type.clone(), table));
+    }
+
+    Some(tables)
+}
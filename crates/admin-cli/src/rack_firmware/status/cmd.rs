@@ -16,8 +16,10 @@
  */
 
 use ::rpc::admin_cli::{CarbideCliError, OutputFormat};
+use prettytable::{Cell, Row, Table};
 
 use super::args::Args;
+use crate::rack_firmware::with_schema_version;
 use crate::rpc::ApiClient;
 
 pub async fn get_job_status(
@@ -36,16 +38,7 @@ pub async fn get_job_status(
         .map_err(CarbideCliError::from)?;
 
     if format == OutputFormat::Json {
-        let result = serde_json::json!({
-            "job_id": response.job_id,
-            "state": response.state,
-            "state_description": response.state_description,
-            "rack_id": response.rack_id,
-            "node_id": response.node_id,
-            "error_message": response.error_message,
-            "result_json": response.result_json,
-        });
-        println!("{}", serde_json::to_string_pretty(&result)?);
+        println!("{}", serde_json::to_string_pretty(&status_json(&response))?);
     } else {
         println!("Firmware Job Status");
         println!("  Job ID:      {}", response.job_id);
@@ -58,10 +51,88 @@ pub async fn get_job_status(
             println!("  Error:       {}", response.error_message);
         }
 
-        if !response.result_json.is_empty() {
+        if !response.node_results.is_empty() {
+            build_node_results_table(&response.node_results).printstd();
+        } else if !response.result_json.is_empty() {
             println!("  Result:      {}", response.result_json);
         }
     }
 
     Ok(())
 }
+
+/// Builds the JSON printed by `rack-firmware status --format json`.
+pub(crate) fn status_json(
+    response: &rpc::forge::RackFirmwareJobStatusResponse,
+) -> serde_json::Value {
+    let node_results: Vec<serde_json::Value> = response
+        .node_results
+        .iter()
+        .map(|node| {
+            let component_results: Vec<serde_json::Value> = node
+                .component_results
+                .iter()
+                .map(|c| {
+                    serde_json::json!({
+                        "target": c.target,
+                        "status": c.status,
+                        "message": c.message,
+                        "version": c.version,
+                    })
+                })
+                .collect();
+            serde_json::json!({
+                "node_id": node.node_id,
+                "status": node.status,
+                "message": node.message,
+                "component_results": component_results,
+            })
+        })
+        .collect();
+
+    with_schema_version(serde_json::json!({
+        "job_id": response.job_id,
+        "state": response.state,
+        "state_description": response.state_description,
+        "rack_id": response.rack_id,
+        "node_id": response.node_id,
+        "error_message": response.error_message,
+        "result_json": response.result_json,
+        "node_results": node_results,
+    }))
+}
+
+/// Builds the per-node, per-component result table shown for `rack-firmware status`.
+pub(crate) fn build_node_results_table(
+    node_results: &[rpc::forge::FirmwareJobNodeResult],
+) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new(vec![
+        Cell::new("Node"),
+        Cell::new("Target"),
+        Cell::new("Status"),
+        Cell::new("Version"),
+        Cell::new("Message"),
+    ]));
+
+    for node in node_results {
+        table.add_row(Row::new(vec![
+            Cell::new(&node.node_id),
+            Cell::new("-"),
+            Cell::new(&node.status),
+            Cell::new("-"),
+            Cell::new(&node.message),
+        ]));
+        for component in &node.component_results {
+            table.add_row(Row::new(vec![
+                Cell::new(&node.node_id),
+                Cell::new(&component.target),
+                Cell::new(&component.status),
+                Cell::new(&component.version),
+                Cell::new(&component.message),
+            ]));
+        }
+    }
+
+    table
+}
@@ -23,9 +23,12 @@
 // Command Structure - Baseline debug_assert() of the entire command.
 // Argument Parsing  - Ensure required/optional arg combinations parse correctly.
 
+use ::rpc::admin_cli::OutputFormat;
 use clap::{CommandFactory, Parser};
+use temp_dir::TempDir;
 
 use super::*;
+use crate::mock_api::MockApi;
 
 // verify_cmd_structure runs a baseline clap debug_assert()
 // to do basic command configuration checking and validation,
@@ -71,6 +74,64 @@ fn parse_list_only_available() {
     }
 }
 
+// parse_list_firmware_type ensures list accepts --firmware-type together
+// with --only-available, and rejects it on its own.
+#[test]
+fn parse_list_firmware_type() {
+    let cmd = Cmd::try_parse_from([
+        "rack-firmware",
+        "list",
+        "--only-available",
+        "--firmware-type",
+        "prod",
+    ])
+    .expect("should parse list with firmware-type");
+
+    match cmd {
+        Cmd::List(args) => {
+            assert!(args.only_available);
+            assert_eq!(args.firmware_type.as_deref(), Some("prod"));
+        }
+        _ => panic!("expected List variant"),
+    }
+
+    let result = Cmd::try_parse_from(["rack-firmware", "list", "--firmware-type", "prod"]);
+    assert!(result.is_err(), "should fail without --only-available");
+}
+
+// parse_list_labels ensures list accepts repeated KEY=VALUE labels and
+// rejects labels without a key.
+#[test]
+fn parse_list_labels() {
+    let cmd = Cmd::try_parse_from([
+        "rack-firmware",
+        "list",
+        "--label",
+        "channel=canary",
+        "--label",
+        "approved-by=ops",
+    ])
+    .expect("should parse list with labels");
+
+    match cmd {
+        Cmd::List(args) => {
+            assert_eq!(
+                args.labels,
+                vec![
+                    ("channel".to_string(), "canary".to_string()),
+                    ("approved-by".to_string(), "ops".to_string()),
+                ]
+            );
+        }
+        _ => panic!("expected List variant"),
+    }
+
+    for label in ["channel", "=canary"] {
+        let result = Cmd::try_parse_from(["rack-firmware", "list", "--label", label]);
+        assert!(result.is_err(), "label {label:?} should be rejected");
+    }
+}
+
 // parse_create_missing_args_fails ensures create fails without required args.
 #[test]
 fn parse_create_missing_args_fails() {
@@ -78,6 +139,145 @@ fn parse_create_missing_args_fails() {
     assert!(result.is_err(), "should fail without json_file and token");
 }
 
+// parse_create ensures create parses with --config and --token.
+#[test]
+fn parse_create() {
+    let cmd = Cmd::try_parse_from([
+        "rack-firmware",
+        "create",
+        "--config",
+        "firmware.json",
+        "--token",
+        "secret",
+    ])
+    .expect("should parse create");
+
+    match cmd {
+        Cmd::Create(args) => {
+            assert_eq!(args.json_file, std::path::PathBuf::from("firmware.json"));
+            assert_eq!(args.artifactory_token, "secret");
+        }
+        _ => panic!("expected Create variant"),
+    }
+}
+
+// parse_create_positional_fails ensures create no longer accepts
+// the config file and token as positional arguments.
+#[test]
+fn parse_create_positional_fails() {
+    let result = Cmd::try_parse_from(["rack-firmware", "create", "firmware.json", "secret"]);
+    assert!(result.is_err(), "should fail with positional args");
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Input Validation
+//
+// This section contains tests for validating the configuration
+// file before it is sent to the API.
+
+fn write_temp_config(contents: &str) -> (TempDir, std::path::PathBuf) {
+    let dir = TempDir::new().expect("should create temp dir");
+    let path = dir.path().join("rack-firmware.json");
+    std::fs::write(&path, contents).expect("should write temp config");
+    (dir, path)
+}
+
+// read_config_counts_board_skus ensures a valid config is read and its
+// board SKUs are counted.
+#[test]
+fn read_config_counts_board_skus() {
+    let (_dir, path) =
+        write_temp_config(r#"{"BoardSKUs": [{"SKUID": "a"}, {"SKUID": "b"}, {"SKUID": "c"}]}"#);
+    let (config_json, board_sku_count) =
+        create::cmd::read_config(&path).expect("should read config");
+
+    assert!(config_json.contains("BoardSKUs"));
+    assert_eq!(board_sku_count, 3);
+}
+
+// read_config_missing_file_fails ensures a nonexistent file is rejected.
+#[test]
+fn read_config_missing_file_fails() {
+    let dir = TempDir::new().expect("should create temp dir");
+    let path = dir.path().join("does-not-exist.json");
+    assert!(create::cmd::read_config(&path).is_err());
+}
+
+// read_config_invalid_json_fails ensures a file that isn't JSON is rejected.
+#[test]
+fn read_config_invalid_json_fails() {
+    let (_dir, path) = write_temp_config("BoardSKUs: []");
+    let result = create::cmd::read_config(&path);
+
+    assert!(result.is_err(), "should fail on invalid JSON");
+}
+
+// parse_validate ensures validate parses with a config file.
+#[test]
+fn parse_validate() {
+    let cmd = Cmd::try_parse_from(["rack-firmware", "validate", "--config", "fw.json"])
+        .expect("should parse validate");
+
+    match cmd {
+        Cmd::Validate(args) => {
+            assert_eq!(args.json_file, std::path::PathBuf::from("fw.json"));
+        }
+        _ => panic!("expected Validate variant"),
+    }
+}
+
+// validate_file_accepts_valid_config ensures a well-formed config has no
+// errors.
+#[test]
+fn validate_file_accepts_valid_config() {
+    let (_dir, path) = write_temp_config(
+        r#"{
+            "Id": "fw-1",
+            "BoardSKUs": [{
+                "SKUID": "sku-001",
+                "Components": {"Firmware": [{
+                    "Component": "BMC",
+                    "Locations": [{"Location": "example.com/bmc.fwpkg", "Type": "Firmware", "Size": 1024}]
+                }]}
+            }]
+        }"#,
+    );
+    let findings = validate::cmd::validate_file(&path).expect("should read config");
+
+    assert!(findings.is_empty(), "unexpected findings: {findings:?}");
+}
+
+// validate_file_reports_invalid_config ensures problems in a config are
+// reported as errors.
+#[test]
+fn validate_file_reports_invalid_config() {
+    let (_dir, path) = write_temp_config(
+        r#"{
+            "BoardSKUs": [{
+                "SKUID": "sku-001",
+                "Components": {"Firmware": [{
+                    "Component": "BMC",
+                    "Locations": [{"Location": "example.com/bmc.fwpkg", "Type": "Firmware", "Size": -1}]
+                }]}
+            }]
+        }"#,
+    );
+    let findings = validate::cmd::validate_file(&path).expect("should read config");
+
+    let errors: Vec<&str> = findings
+        .iter()
+        .filter(|f| f.is_error())
+        .filter_map(|f| f.subject.as_deref())
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            "Id",
+            "BoardSKUs[0].Components.Firmware[0].Locations[0].Size"
+        ]
+    );
+}
+
 // parse_get_missing_id_fails ensures get fails without ID.
 #[test]
 fn parse_get_missing_id_fails() {
@@ -85,9 +285,384 @@ fn parse_get_missing_id_fails() {
     assert!(result.is_err(), "should fail without id");
 }
 
+// parse_show ensures show parses with --id.
+#[test]
+fn parse_show() {
+    let cmd =
+        Cmd::try_parse_from(["rack-firmware", "show", "--id", "fw-1"]).expect("should parse show");
+
+    match cmd {
+        Cmd::Show(args) => {
+            assert_eq!(args.id, "fw-1");
+        }
+        _ => panic!("expected Show variant"),
+    }
+}
+
+// parse_show_missing_id_fails ensures show fails without --id.
+#[test]
+fn parse_show_missing_id_fails() {
+    let result = Cmd::try_parse_from(["rack-firmware", "show"]);
+    assert!(result.is_err(), "should fail without id");
+}
+
 // parse_delete_missing_id_fails ensures delete fails without ID.
 #[test]
 fn parse_delete_missing_id_fails() {
     let result = Cmd::try_parse_from(["rack-firmware", "delete"]);
     assert!(result.is_err(), "should fail without id");
 }
+
+// parse_delete_with_yes ensures delete parses --id and --yes.
+#[test]
+fn parse_delete_with_yes() {
+    let cmd = Cmd::try_parse_from(["rack-firmware", "delete", "--id", "fw-1", "--yes"])
+        .expect("should parse delete with --yes");
+
+    match cmd {
+        Cmd::Delete(args) => {
+            assert_eq!(args.id, "fw-1");
+            assert!(args.yes);
+        }
+        _ => panic!("expected Delete variant"),
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Output Rendering
+//
+// This section contains tests for how API responses are rendered.
+
+// build_list_table_shows_availability ensures the list table has one
+// row per config, with its board SKU count and availability.
+#[test]
+fn build_list_table_shows_availability() {
+    let configs = vec![
+        rpc::forge::RackFirmware {
+            id: "fw-ready".to_string(),
+            config_json: r#"{"BoardSKUs": [{"SKUID": "a"}, {"SKUID": "b"}]}"#.to_string(),
+            available: true,
+            ..Default::default()
+        },
+        rpc::forge::RackFirmware {
+            id: "fw-downloading".to_string(),
+            config_json: r#"{"BoardSKUs": [{"SKUID": "a"}]}"#.to_string(),
+            available: false,
+            ..Default::default()
+        },
+        rpc::forge::RackFirmware {
+            id: "fw-prod-only".to_string(),
+            config_json: r#"{"BoardSKUs": [{"SKUID": "a"}]}"#.to_string(),
+            available: false,
+            available_types: vec!["prod".to_string()],
+            ..Default::default()
+        },
+    ];
+
+    let table = list::cmd::build_table(&configs);
+    assert_eq!(table.len(), 3);
+
+    let cells = |row: usize| -> Vec<String> {
+        table
+            .get_row(row)
+            .unwrap()
+            .iter()
+            .map(|c| c.get_content())
+            .collect()
+    };
+    assert_eq!(cells(0)[..3], ["fw-ready", "2", "true"]);
+    assert_eq!(cells(1)[..3], ["fw-downloading", "1", "false"]);
+    assert_eq!(cells(2)[..3], ["fw-prod-only", "1", "prod"]);
+}
+
+// confirm_delete_without_yes_aborts ensures delete prompts and declines
+// unless the user answers yes.
+#[test]
+fn confirm_delete_without_yes_aborts() {
+    let config = rpc::forge::RackFirmware {
+        id: "fw-1".to_string(),
+        config_json: r#"{"BoardSKUs": [{"SKUID": "a"}]}"#.to_string(),
+        available: true,
+        ..Default::default()
+    };
+
+    for answer in ["", "\n", "n\n", "no\n", "maybe\n"] {
+        let mut output = Vec::new();
+        let confirmed =
+            delete::cmd::confirm_delete(&config, false, &mut answer.as_bytes(), &mut output)
+                .unwrap();
+        assert!(!confirmed, "answer {answer:?} should abort");
+
+        let prompt = String::from_utf8(output).unwrap();
+        assert!(prompt.contains("delete Rack firmware configuration fw-1"));
+        assert!(prompt.contains("Board SKUs: 1"));
+        assert!(prompt.contains("Continue? (y/N)"));
+    }
+
+    for answer in ["y\n", "YES\n"] {
+        let confirmed =
+            delete::cmd::confirm_delete(&config, false, &mut answer.as_bytes(), &mut Vec::new())
+                .unwrap();
+        assert!(confirmed, "answer {answer:?} should proceed");
+    }
+}
+
+// confirm_delete_with_yes_proceeds ensures --yes skips the prompt.
+#[test]
+fn confirm_delete_with_yes_proceeds() {
+    let config = rpc::forge::RackFirmware {
+        id: "fw-1".to_string(),
+        ..Default::default()
+    };
+
+    let mut output = Vec::new();
+    let confirmed =
+        delete::cmd::confirm_delete(&config, true, &mut "".as_bytes(), &mut output).unwrap();
+    assert!(confirmed);
+    assert!(output.is_empty(), "--yes should not prompt");
+}
+
+// build_lookup_tables_renders_devices ensures a lookup table is rendered
+// as one table per device type, with rows sorted by component key.
+#[test]
+fn build_lookup_tables_renders_devices() {
+    let parsed = serde_json::json!({
+        "devices": {
+            "Switch": {
+                "BMC_prod": {
+                    "component": "BMC",
+                    "target": "bmc",
+                    "filename": "switch_bmc.fwpkg",
+                    "bundle": "P4978",
+                    "firmware_type": "prod",
+                    "version": "1.2.3",
+                    "subcomponents": []
+                }
+            },
+            "GB200ComputeTray": {
+                "HMC_prod": {
+                    "component": "HMC",
+                    "target": "hmc",
+                    "filename": "hmc.fwpkg",
+                    "bundle": "P4975",
+                    "firmware_type": "prod",
+                    "version": null,
+                    "subcomponents": []
+                },
+                "BMC_prod": {
+                    "component": "BMC",
+                    "target": "bmc",
+                    "filename": "bmc.fwpkg",
+                    "bundle": "P4975",
+                    "firmware_type": "prod",
+                    "version": "2.0",
+                    "subcomponents": []
+                }
+            }
+        }
+    });
+
+    let tables = show::cmd::build_lookup_tables(&parsed).expect("should be a lookup table");
+    let device_types: Vec<_> = tables.iter().map(|(d, _)| d.as_str()).collect();
+    assert_eq!(device_types, ["GB200ComputeTray", "Switch"]);
+
+    let cells = |table: &prettytable::Table, row: usize| -> Vec<String> {
+        table
+            .get_row(row)
+            .unwrap()
+            .iter()
+            .map(|c| c.get_content())
+            .collect()
+    };
+    let tray = &tables[0].1;
+    assert_eq!(tray.len(), 2);
+    assert_eq!(cells(tray, 0), ["BMC", "bmc", "bmc.fwpkg", "2.0"]);
+    assert_eq!(cells(tray, 1), ["HMC", "hmc", "hmc.fwpkg", "-"]);
+}
+
+// build_lookup_tables_before_download ensures raw parsed components
+// (before the lookup table is built) aren't treated as a lookup table.
+#[test]
+fn build_lookup_tables_before_download() {
+    let parsed = serde_json::json!({ "board_skus": [] });
+    assert!(show::cmd::build_lookup_tables(&parsed).is_none());
+}
+
+// apply_json_has_schema_version ensures apply JSON output carries the
+// current schema version.
+#[test]
+fn apply_json_has_schema_version() {
+    let response = rpc::forge::RackFirmwareApplyResponse {
+        total_updates: 1,
+        successful_updates: 1,
+        ..Default::default()
+    };
+
+    let json = apply::cmd::apply_json(&response);
+    assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+    assert_eq!(json["total_updates"], 1);
+}
+
+// status_json_has_schema_version ensures status JSON output carries the
+// current schema version.
+#[test]
+fn status_json_has_schema_version() {
+    let response = rpc::forge::RackFirmwareJobStatusResponse {
+        job_id: "job-1".to_string(),
+        state: "RUNNING".to_string(),
+        ..Default::default()
+    };
+
+    let json = status::cmd::status_json(&response);
+    assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+    assert_eq!(json["job_id"], "job-1");
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Command Output
+//
+// This section contains tests running commands against a mocked API,
+// and checking what they print.
+
+// create_prints_board_sku_count ensures create reports the board SKU
+// count of the uploaded config in both table and JSON output.
+#[tokio::test]
+async fn create_prints_board_sku_count() {
+    let (_dir, path) = write_temp_config(r#"{"BoardSKUs": [{"SKUID": "a"}, {"SKUID": "b"}]}"#);
+    let api_client = MockApi::default()
+        .respond(
+            "CreateRackFirmware",
+            rpc::forge::RackFirmware {
+                id: "fw-1".to_string(),
+                ..Default::default()
+            },
+        )
+        .client()
+        .await;
+    let args = || create::Args {
+        json_file: path.clone(),
+        artifactory_token: "secret".to_string(),
+        labels: vec![],
+    };
+
+    let mut output = Vec::new();
+    create::cmd::create(args(), OutputFormat::AsciiTable, &api_client, &mut output)
+        .await
+        .expect("should create");
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("ID: fw-1"), "{output}");
+    assert!(output.contains("Board SKUs: 2"), "{output}");
+
+    let mut output = Vec::new();
+    create::cmd::create(args(), OutputFormat::Json, &api_client, &mut output)
+        .await
+        .expect("should create");
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+    assert_eq!(json["id"], "fw-1");
+    assert_eq!(json["board_sku_count"], 2);
+}
+
+// list_json_has_board_sku_count ensures list JSON output carries the
+// board SKU count shown in the table.
+#[tokio::test]
+async fn list_json_has_board_sku_count() {
+    let api_client = MockApi::default()
+        .respond(
+            "ListRackFirmware",
+            rpc::forge::RackFirmwareList {
+                configs: vec![
+                    rpc::forge::RackFirmware {
+                        id: "fw-1".to_string(),
+                        config_json: r#"{"BoardSKUs": [{"SKUID": "a"}, {"SKUID": "b"}]}"#
+                            .to_string(),
+                        ..Default::default()
+                    },
+                    rpc::forge::RackFirmware {
+                        id: "fw-invalid".to_string(),
+                        config_json: "BoardSKUs: []".to_string(),
+                        ..Default::default()
+                    },
+                ],
+            },
+        )
+        .client()
+        .await;
+    let Cmd::List(args) = Cmd::try_parse_from(["rack-firmware", "list"]).unwrap() else {
+        panic!("expected List variant");
+    };
+
+    let mut output = Vec::new();
+    list::cmd::list(args, OutputFormat::Json, &api_client, &mut output)
+        .await
+        .expect("should list");
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let configs = &json["configs"];
+    assert_eq!(configs[0]["id"], "fw-1");
+    assert_eq!(configs[0]["board_sku_count"], 2);
+    assert_eq!(configs[1]["id"], "fw-invalid");
+    assert_eq!(configs[1]["board_sku_count"], serde_json::Value::Null);
+}
+
+// list_json_has_schema_version ensures list JSON output wraps the configs
+// in an object carrying the current schema version.
+#[tokio::test]
+async fn list_json_has_schema_version() {
+    let api_client = MockApi::default()
+        .respond(
+            "ListRackFirmware",
+            rpc::forge::RackFirmwareList {
+                configs: vec![rpc::forge::RackFirmware {
+                    id: "fw-1".to_string(),
+                    ..Default::default()
+                }],
+            },
+        )
+        .client()
+        .await;
+    let Cmd::List(args) = Cmd::try_parse_from(["rack-firmware", "list"]).unwrap() else {
+        panic!("expected List variant");
+    };
+
+    let mut output = Vec::new();
+    list::cmd::list(args, OutputFormat::Json, &api_client, &mut output)
+        .await
+        .expect("should list");
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+    assert_eq!(json["configs"].as_array().map(Vec::len), Some(1));
+}
+
+// delete_json_has_schema_version ensures delete JSON output reports the
+// deleted config along with the current schema version.
+#[tokio::test]
+async fn delete_json_has_schema_version() {
+    let api_client = MockApi::default()
+        .respond(
+            "GetRackFirmware",
+            rpc::forge::RackFirmware {
+                id: "fw-1".to_string(),
+                config_json: r#"{"BoardSKUs": [{"SKUID": "a"}]}"#.to_string(),
+                available: true,
+                ..Default::default()
+            },
+        )
+        .respond("DeleteRackFirmware", ())
+        .client()
+        .await;
+    let Cmd::Delete(args) =
+        Cmd::try_parse_from(["rack-firmware", "delete", "--id", "fw-1", "--yes"]).unwrap()
+    else {
+        panic!("expected Delete variant");
+    };
+
+    let mut output = Vec::new();
+    delete::cmd::delete(args, OutputFormat::Json, &api_client, &mut output)
+        .await
+        .expect("should delete");
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(json["schema_version"], JSON_SCHEMA_VERSION);
+    assert_eq!(json["id"], "fw-1");
+    assert_eq!(json["deleted"], true);
+    assert_eq!(json["board_sku_count"], 1);
+}
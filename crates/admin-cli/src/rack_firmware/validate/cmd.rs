@@ -0,0 +1,71 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::Path;
+
+use ::rpc::admin_cli::{CarbideCliError, OutputFormat};
+use ::rpc::rack_firmware::validate_config;
+use ::rpc::validation::ValidationFinding;
+
+use super::args::Args;
+use crate::rack_firmware::create::cmd::read_config;
+use crate::rack_firmware::with_schema_version;
+
+pub fn validate(opts: Args, format: OutputFormat) -> Result<(), CarbideCliError> {
+    let findings = validate_file(&opts.json_file)?;
+    let error_count = findings.iter().filter(|f| f.is_error()).count();
+
+    if format == OutputFormat::Json {
+        let output = serde_json::json!({
+            "valid": error_count == 0,
+            "findings": findings,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&with_schema_version(output))?
+        );
+    } else {
+        for finding in &findings {
+            println!("{}", finding);
+        }
+        if error_count == 0 {
+            println!(
+                "{}: valid ({} warnings)",
+                opts.json_file.display(),
+                findings.len()
+            );
+        }
+    }
+
+    if error_count > 0 {
+        return Err(CarbideCliError::GenericError(format!(
+            "{}: {} errors found",
+            opts.json_file.display(),
+            error_count
+        )));
+    }
+
+    Ok(())
+}
+
+/// Reads a rack firmware configuration file and validates it the same way
+/// carbide-api does when the configuration is created.
+pub(crate) fn validate_file(path: &Path) -> Result<Vec<ValidationFinding>, CarbideCliError> {
+    let (config_json, _) = read_config(path)?;
+    let config = serde_json::from_str(&config_json)?;
+    Ok(validate_config(&config))
+}
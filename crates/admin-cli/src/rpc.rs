@@ -1274,6 +1274,7 @@ impl ApiClient {
                 labels,
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         };
 
         tracing::trace!("{}", serde_json::to_string(&instance_request).unwrap());
@@ -0,0 +1,12 @@
+-- Remembers the most recent firmware apply per rack, firmware config and
+-- firmware type, so that a retried apply returns the jobs it already created
+-- instead of flashing the rack again. The row is inserted before the jobs are
+-- started, with a NULL response until they are.
+CREATE TABLE rack_firmware_apply(
+    rack_id VARCHAR(64) NOT NULL,
+    firmware_id VARCHAR(256) NOT NULL,
+    firmware_type VARCHAR(32) NOT NULL,
+    response JSONB,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (rack_id, firmware_id, firmware_type)
+);
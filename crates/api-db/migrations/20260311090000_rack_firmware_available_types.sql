@@ -0,0 +1,3 @@
+-- Firmware types (e.g. "prod", "dev") whose files have all been downloaded.
+-- A config can be usable for one type while downloads for another lag behind.
+ALTER TABLE rack_firmware ADD COLUMN available_types TEXT[] NOT NULL DEFAULT '{}';
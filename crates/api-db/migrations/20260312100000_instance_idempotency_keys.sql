@@ -0,0 +1,10 @@
+-- Maps the idempotency key a tenant sent with an AllocateInstance request to
+-- the instance it created, so that a retried request returns that instance
+-- instead of allocating another one.
+CREATE TABLE instance_idempotency_keys(
+    tenant_organization_id VARCHAR(256) NOT NULL,
+    idempotency_key VARCHAR(256) NOT NULL,
+    instance_id UUID NOT NULL,
+    created TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tenant_organization_id, idempotency_key)
+);
@@ -0,0 +1,2 @@
+-- Arbitrary key/value labels on a firmware configuration, e.g. channel=canary.
+ALTER TABLE rack_firmware ADD COLUMN labels JSONB NOT NULL DEFAULT '{}';
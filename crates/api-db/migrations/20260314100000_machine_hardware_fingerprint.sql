@@ -0,0 +1 @@
+ALTER TABLE machines ADD COLUMN IF NOT EXISTS hardware_fingerprint TEXT;
@@ -0,0 +1,2 @@
+-- Expired idempotency keys are pruned by their creation time
+CREATE INDEX IF NOT EXISTS idx_instance_idempotency_keys_on_created ON instance_idempotency_keys (created);
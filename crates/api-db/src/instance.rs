@@ -57,6 +57,17 @@ impl ColumnInfo<'_> for IdColumn {
 #[derive(Debug, Clone, Copy)]
 pub struct InstanceTable {}
 
+/// An instance that has not reached the Ready tenant state yet
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StuckInstance {
+    pub id: InstanceId,
+    pub machine_id: MachineId,
+    /// When the instance was created
+    pub requested: DateTime<Utc>,
+    /// The current state of the Machine the instance is allocated on, as JSON
+    pub state: String,
+}
+
 pub async fn find_ids(
     txn: impl DbReader<'_>,
     filter: rpc::InstanceSearchFilter,
@@ -158,6 +169,110 @@ pub async fn find_by_id(
         .map_err(|e| DatabaseError::query(query, e))
 }
 
+/// Returns the ID of the instance created for `idempotency_key` by the given
+/// tenant, if the key was recorded after `since` and the instance was not
+/// deleted since.
+pub async fn find_id_by_idempotency_key(
+    txn: impl DbReader<'_>,
+    tenant_organization_id: &str,
+    idempotency_key: &str,
+    since: DateTime<Utc>,
+) -> Result<Option<InstanceId>, DatabaseError> {
+    let query = "SELECT k.instance_id FROM instance_idempotency_keys k
+        INNER JOIN instances i ON i.id = k.instance_id
+        WHERE k.tenant_organization_id = $1 AND k.idempotency_key = $2
+            AND k.created > $3 AND i.deleted IS NULL";
+    sqlx::query_as(query)
+        .bind(tenant_organization_id)
+        .bind(idempotency_key)
+        .bind(since)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
+/// Records that `idempotency_key` created `instance_id`. A key recorded at or
+/// before `since` is replaced; a more recent one fails with
+/// `AlreadyFoundError`, which happens when two requests with the same key
+/// race each other.
+pub async fn record_idempotency_key(
+    txn: &mut PgConnection,
+    tenant_organization_id: &str,
+    idempotency_key: &str,
+    instance_id: InstanceId,
+    since: DateTime<Utc>,
+) -> DatabaseResult<()> {
+    let query = "INSERT INTO instance_idempotency_keys
+            (tenant_organization_id, idempotency_key, instance_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (tenant_organization_id, idempotency_key) DO UPDATE
+            SET instance_id = EXCLUDED.instance_id, created = NOW()
+            WHERE instance_idempotency_keys.created <= $4
+        RETURNING instance_id";
+    let recorded: Option<InstanceId> = sqlx::query_as(query)
+        .bind(tenant_organization_id)
+        .bind(idempotency_key)
+        .bind(instance_id)
+        .bind(since)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    match recorded {
+        Some(_) => Ok(()),
+        None => Err(DatabaseError::AlreadyFoundError {
+            kind: "Instance idempotency key",
+            id: idempotency_key.to_string(),
+        }),
+    }
+}
+
+/// Deletes the idempotency keys recorded at or before `since`, which can no
+/// longer be used to find the instance they created.
+pub async fn delete_expired_idempotency_keys(
+    txn: &mut PgConnection,
+    since: DateTime<Utc>,
+) -> DatabaseResult<()> {
+    let query = "DELETE FROM instance_idempotency_keys WHERE created <= $1";
+    sqlx::query(query)
+        .bind(since)
+        .execute(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(())
+}
+
+/// Returns instances created before `created_before` which never reached the
+/// Ready tenant state, oldest first.
+///
+/// An instance counts as Ready once its host entered `Assigned/Ready`, either
+/// currently or at any point recorded in the machine state history since the
+/// instance was created.
+pub async fn find_stuck(
+    txn: impl DbReader<'_>,
+    created_before: DateTime<Utc>,
+) -> Result<Vec<StuckInstance>, DatabaseError> {
+    let query = "SELECT i.id, i.machine_id, i.requested, m.controller_state::TEXT AS state
+        FROM instances i
+        INNER JOIN machines m ON m.id = i.machine_id
+        WHERE i.deleted IS NULL AND i.requested < $1
+            AND NOT (m.controller_state->>'state' = 'assigned'
+                AND m.controller_state->'instance_state'->>'state' = 'ready')
+            AND NOT EXISTS (
+                SELECT 1 FROM machine_state_history h
+                WHERE h.machine_id = i.machine_id AND h.timestamp >= i.requested
+                    AND h.state->>'state' = 'assigned'
+                    AND h.state->'instance_state'->>'state' = 'ready'
+            )
+        ORDER BY i.requested ASC";
+    sqlx::query_as(query)
+        .bind(created_before)
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))
+}
+
 pub async fn find_id_by_machine_id(
     txn: &mut PgConnection,
     machine_id: &MachineId,
@@ -637,6 +637,28 @@ pub async fn update_discovery_time(
     Ok(())
 }
 
+/// Stores the hardware fingerprint of a machine and returns the previously stored one
+///
+/// Returns `None` if the machine had no fingerprint recorded yet, or doesn't exist.
+pub async fn update_hardware_fingerprint(
+    machine_id: &MachineId,
+    fingerprint: &str,
+    txn: &mut PgConnection,
+) -> Result<Option<String>, DatabaseError> {
+    let query = r#"UPDATE machines m SET hardware_fingerprint=$2
+        FROM (SELECT id, hardware_fingerprint FROM machines WHERE id=$1 FOR UPDATE) previous
+        WHERE m.id=previous.id
+        RETURNING previous.hardware_fingerprint"#;
+    let previous: Option<Option<String>> = sqlx::query_scalar(query)
+        .bind(machine_id)
+        .bind(fingerprint)
+        .fetch_optional(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query, e))?;
+
+    Ok(previous.flatten())
+}
+
 pub async fn update_scout_contact_time(
     machine_id: &MachineId,
     txn: &mut PgConnection,
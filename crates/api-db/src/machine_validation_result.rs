@@ -16,7 +16,8 @@
  */
 use carbide_uuid::machine::MachineId;
 use model::machine::machine_search_config::MachineSearchConfig;
-use model::machine_validation::MachineValidationResult;
+use model::machine_validation::{MachineValidationResult, validation_findings};
+use rpc::validation::ValidationFinding;
 use sqlx::PgConnection;
 
 use crate::{DatabaseError, DatabaseResult, ObjectFilter, machine_validation_suites};
@@ -172,7 +173,7 @@ pub async fn create(value: MachineValidationResult, txn: &mut PgConnection) -> D
 pub async fn validate_current_context(
     txn: &mut PgConnection,
     id: &rpc::Uuid,
-) -> DatabaseResult<Option<String>> {
+) -> DatabaseResult<Vec<ValidationFinding>> {
     let db_results = find_by(
         txn,
         ObjectFilter::List(&[id.to_string()]),
@@ -180,12 +181,7 @@ pub async fn validate_current_context(
     )
     .await?;
 
-    for result in db_results {
-        if result.exit_code != 0 {
-            return Ok(Some(format!("{} is failed", result.name)));
-        }
-    }
-    Ok(None)
+    Ok(validation_findings(&db_results))
 }
 
 pub async fn find_by_validation_id(
@@ -15,6 +15,9 @@
  * limitations under the License.
  */
 
+use std::collections::HashMap;
+
+use carbide_uuid::rack::RackId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::Error::RowNotFound;
@@ -30,7 +33,11 @@ pub struct RackFirmware {
     pub id: String,
     pub config: Json<serde_json::Value>,
     pub available: bool,
+    /// Firmware types (lowercase, e.g. "prod") whose files have all been downloaded
+    pub available_types: Vec<String>,
     pub parsed_components: Option<Json<serde_json::Value>>,
+    /// Arbitrary key/value labels, e.g. `channel=canary`
+    pub labels: HashMap<String, String>,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
 }
@@ -41,7 +48,9 @@ impl<'r> FromRow<'r, PgRow> for RackFirmware {
             id: row.try_get("id")?,
             config: row.try_get("config")?,
             available: row.try_get("available")?,
+            available_types: row.try_get("available_types")?,
             parsed_components: row.try_get("parsed_components")?,
+            labels: row.try_get::<Json<HashMap<String, String>>, _>("labels")?.0,
             created: row.try_get("created")?,
             updated: row.try_get("updated")?,
         })
@@ -59,6 +68,8 @@ impl From<&RackFirmware> for rpc::forge::RackFirmware {
             id: db.id.clone(),
             config_json: db.config.0.to_string(),
             available: db.available,
+            available_types: db.available_types.clone(),
+            labels: db.labels.clone(),
             created: db.created.format("%Y-%m-%d %H:%M:%S").to_string(),
             updated: db.updated.format("%Y-%m-%d %H:%M:%S").to_string(),
             parsed_components,
@@ -67,19 +78,30 @@ impl From<&RackFirmware> for rpc::forge::RackFirmware {
 }
 
 impl RackFirmware {
+    /// Whether firmware of `firmware_type` (e.g. "Prod") can be applied from this configuration
+    pub fn is_available_for(&self, firmware_type: &str) -> bool {
+        self.available
+            || self
+                .available_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(firmware_type))
+    }
+
     /// Create a new Rack firmware configuration
     pub async fn create(
         txn: &mut PgConnection,
         id: &str,
         config: serde_json::Value,
         parsed_components: Option<serde_json::Value>,
+        labels: &HashMap<String, String>,
     ) -> DatabaseResult<Self> {
-        let query = "INSERT INTO rack_firmware (id, config, parsed_components) VALUES ($1, $2::jsonb, $3::jsonb) RETURNING *";
+        let query = "INSERT INTO rack_firmware (id, config, parsed_components, labels) VALUES ($1, $2::jsonb, $3::jsonb, $4::jsonb) RETURNING *";
 
         sqlx::query_as(query)
             .bind(id)
             .bind(Json(config))
             .bind(parsed_components.map(Json))
+            .bind(Json(labels))
             .fetch_one(txn)
             .await
             .map_err(|e| DatabaseError::new(query, e))
@@ -99,17 +121,33 @@ impl RackFirmware {
     }
 
     /// List all Rack firmware configurations
+    ///
+    /// With `only_available`, only configurations that are fully available are listed, or, if
+    /// `firmware_type` is given, those available for that firmware type.
+    /// Only configurations carrying all labels of `label_selector` are listed.
     pub async fn list_all(
         txn: &mut PgConnection,
         only_available: bool,
+        firmware_type: Option<&str>,
+        label_selector: &HashMap<String, String>,
     ) -> DatabaseResult<Vec<Self>> {
-        let query = if only_available {
-            "SELECT * FROM rack_firmware WHERE available = true ORDER BY created DESC"
-        } else {
-            "SELECT * FROM rack_firmware ORDER BY created DESC"
+        let query = match (only_available, firmware_type) {
+            (false, _) => {
+                "SELECT * FROM rack_firmware WHERE labels @> $1::jsonb ORDER BY created DESC"
+            }
+            (true, None) => {
+                "SELECT * FROM rack_firmware WHERE available = true AND labels @> $1::jsonb ORDER BY created DESC"
+            }
+            (true, Some(_)) => {
+                "SELECT * FROM rack_firmware WHERE (available = true OR LOWER($2) = ANY(available_types)) AND labels @> $1::jsonb ORDER BY created DESC"
+            }
         };
 
-        sqlx::query_as(query)
+        let mut query_as = sqlx::query_as::<_, Self>(query).bind(Json(label_selector));
+        if only_available && let Some(firmware_type) = firmware_type {
+            query_as = query_as.bind(firmware_type);
+        }
+        query_as
             .fetch_all(txn)
             .await
             .map_err(|e| DatabaseError::query(query, e))
@@ -131,6 +169,50 @@ impl RackFirmware {
             .map_err(|e| DatabaseError::new(query, e))
     }
 
+    /// Replace the parsed components, e.g. with a rebuilt firmware lookup table
+    pub async fn set_parsed_components(
+        txn: &mut PgConnection,
+        id: &str,
+        parsed_components: serde_json::Value,
+    ) -> DatabaseResult<Self> {
+        let query = "UPDATE rack_firmware SET parsed_components = $2::jsonb, updated = NOW() WHERE id = $1 RETURNING *";
+
+        sqlx::query_as(query)
+            .bind(id)
+            .bind(Json(parsed_components))
+            .fetch_one(txn)
+            .await
+            .map_err(|e| match e {
+                RowNotFound => DatabaseError::NotFoundError {
+                    kind: "rack firmware",
+                    id: format!("{id:?}"),
+                },
+                _ => DatabaseError::query(query, e),
+            })
+    }
+
+    /// Replace the labels
+    pub async fn set_labels(
+        txn: &mut PgConnection,
+        id: &str,
+        labels: &HashMap<String, String>,
+    ) -> DatabaseResult<Self> {
+        let query = "UPDATE rack_firmware SET labels = $2::jsonb, updated = NOW() WHERE id = $1 RETURNING *";
+
+        sqlx::query_as(query)
+            .bind(id)
+            .bind(Json(labels))
+            .fetch_one(txn)
+            .await
+            .map_err(|e| match e {
+                RowNotFound => DatabaseError::NotFoundError {
+                    kind: "rack firmware",
+                    id: format!("{id:?}"),
+                },
+                _ => DatabaseError::query(query, e),
+            })
+    }
+
     /// Update the available flag
     pub async fn set_available(
         txn: &mut PgConnection,
@@ -148,6 +230,22 @@ impl RackFirmware {
             .map_err(|e| DatabaseError::new(query, e))
     }
 
+    /// Update the firmware types whose files have all been downloaded
+    pub async fn set_available_types(
+        txn: &mut PgConnection,
+        id: &str,
+        available_types: &[String],
+    ) -> DatabaseResult<Self> {
+        let query = "UPDATE rack_firmware SET available_types = $2, updated = NOW() WHERE id = $1 RETURNING *";
+
+        sqlx::query_as(query)
+            .bind(id)
+            .bind(available_types)
+            .fetch_one(txn)
+            .await
+            .map_err(|e| DatabaseError::new(query, e))
+    }
+
     /// Delete a Rack firmware configuration
     pub async fn delete(txn: &mut PgConnection, id: &str) -> DatabaseResult<()> {
         let query = "DELETE FROM rack_firmware WHERE id = $1 RETURNING id";
@@ -161,3 +259,115 @@ impl RackFirmware {
         Ok(())
     }
 }
+
+/// A firmware apply to a rack. Used to answer a retried apply with the jobs it
+/// already created instead of flashing the rack again.
+#[derive(Debug, Clone, FromRow)]
+pub struct RackFirmwareApplyAttempt {
+    pub rack_id: RackId,
+    pub firmware_id: String,
+    pub firmware_type: String,
+    /// `None` while the apply is still starting jobs on RMS
+    pub response: Option<Json<rpc::forge::RackFirmwareApplyResponse>>,
+    pub created: DateTime<Utc>,
+}
+
+impl RackFirmwareApplyAttempt {
+    /// Claim the apply of `firmware_id` to `rack_id` with `firmware_type`,
+    /// unless it was already claimed after `since`.
+    ///
+    /// A claim which never recorded a response, e.g. because the process
+    /// applying it died, can be claimed again once it is older than
+    /// `unfinished_since`.
+    ///
+    /// Returns `None` if the apply was claimed, and the existing attempt
+    /// otherwise. The claim has to be committed before the apply starts jobs,
+    /// so that a concurrent apply sees it.
+    pub async fn claim(
+        txn: &mut PgConnection,
+        rack_id: RackId,
+        firmware_id: &str,
+        firmware_type: &str,
+        since: DateTime<Utc>,
+        unfinished_since: DateTime<Utc>,
+    ) -> DatabaseResult<Option<Self>> {
+        // A concurrent claim of the same apply blocks on the primary key until it
+        // commits, and then sees the recent attempt
+        let query = "INSERT INTO rack_firmware_apply (rack_id, firmware_id, firmware_type)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (rack_id, firmware_id, firmware_type) DO UPDATE
+            SET response = NULL, created = NOW()
+            WHERE rack_firmware_apply.created <= $4
+                OR (rack_firmware_apply.response IS NULL AND rack_firmware_apply.created <= $5)
+            RETURNING rack_id";
+
+        let claimed = sqlx::query(query)
+            .bind(rack_id)
+            .bind(firmware_id)
+            .bind(firmware_type)
+            .bind(since)
+            .bind(unfinished_since)
+            .fetch_optional(&mut *txn)
+            .await
+            .map_err(|e| DatabaseError::new(query, e))?;
+        if claimed.is_some() {
+            return Ok(None);
+        }
+
+        let query = "SELECT * FROM rack_firmware_apply
+            WHERE rack_id = $1 AND firmware_id = $2 AND firmware_type = $3";
+        sqlx::query_as(query)
+            .bind(rack_id)
+            .bind(firmware_id)
+            .bind(firmware_type)
+            .fetch_one(txn)
+            .await
+            .map(Some)
+            .map_err(|e| DatabaseError::query(query, e))
+    }
+
+    /// Record the jobs started by a claimed apply
+    pub async fn record(
+        txn: &mut PgConnection,
+        rack_id: RackId,
+        firmware_id: &str,
+        firmware_type: &str,
+        response: &rpc::forge::RackFirmwareApplyResponse,
+    ) -> DatabaseResult<()> {
+        let query = "UPDATE rack_firmware_apply SET response = $4::jsonb
+            WHERE rack_id = $1 AND firmware_id = $2 AND firmware_type = $3";
+
+        sqlx::query(query)
+            .bind(rack_id)
+            .bind(firmware_id)
+            .bind(firmware_type)
+            .bind(Json(response))
+            .execute(txn)
+            .await
+            .map_err(|e| DatabaseError::new(query, e))?;
+
+        Ok(())
+    }
+
+    /// Release a claimed apply which did not start any jobs, so that it can
+    /// be retried right away
+    pub async fn release(
+        txn: &mut PgConnection,
+        rack_id: RackId,
+        firmware_id: &str,
+        firmware_type: &str,
+    ) -> DatabaseResult<()> {
+        let query = "DELETE FROM rack_firmware_apply
+            WHERE rack_id = $1 AND firmware_id = $2 AND firmware_type = $3 AND response IS NULL";
+
+        sqlx::query(query)
+            .bind(rack_id)
+            .bind(firmware_id)
+            .bind(firmware_type)
+            .execute(txn)
+            .await
+            .map_err(|e| DatabaseError::new(query, e))?;
+
+        Ok(())
+    }
+}
@@ -210,6 +210,27 @@ pub async fn find_by_segment(
         .map_err(|e| DatabaseError::query(query.sql(), e))
 }
 
+/// Finds the VPCs that any of the segments `segment_ids` belong to
+pub async fn find_by_segments(
+    txn: impl DbReader<'_>,
+    segment_ids: &[NetworkSegmentId],
+) -> Result<Vec<Vpc>, DatabaseError> {
+    let mut query = FilterableQueryBuilder::new(
+        "SELECT v.* from vpcs v WHERE v.id IN (SELECT s.vpc_id FROM network_segments s",
+    )
+    .filter_relation(
+        &ObjectColumnFilter::List(network_segment::IdColumn, segment_ids),
+        Some("s"),
+    );
+    query.push(") AND v.deleted IS NULL");
+
+    query
+        .build_query_as()
+        .fetch_all(txn)
+        .await
+        .map_err(|e| DatabaseError::query(query.sql(), e))
+}
+
 /// Tries to deletes a VPC
 ///
 /// If the VPC existed at the point of deletion this returns the last known information about the VPC
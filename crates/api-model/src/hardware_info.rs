@@ -17,6 +17,7 @@
 
 //! Describes hardware that is discovered by Forge
 
+use std::collections::BTreeSet;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
@@ -28,6 +29,7 @@ use carbide_uuid::nvlink::NvLinkDomainId;
 use forge_network::{MELLANOX_SF_VF_MAC_ADDRESS_IN, MELLANOX_SF_VF_MAC_ADDRESS_OUT};
 use mac_address::{MacAddress, MacParseError};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use utils::models::arch::CpuArchitecture;
 
 use crate::machine::machine_id::MissingHardwareInfo;
@@ -909,6 +911,44 @@ pub enum HardwareInfoError {
     MissingHardwareInfo(#[from] MissingHardwareInfo),
 }
 
+/// Computes a stable fingerprint over the serial numbers of the components of a machine
+///
+/// The fingerprint covers the DMI product, board and chassis serials as well as the
+/// serials of disks and GPUs. Serials are sorted and deduplicated before they are hashed,
+/// so neither the order in which components are reported nor the partitioning of disks
+/// affects the result. A fingerprint which changes between two discoveries of the same
+/// machine indicates that components were swapped.
+pub fn hardware_fingerprint(info: &HardwareInfo) -> String {
+    let mut serials = BTreeSet::new();
+    let mut add_serial = |kind: &str, serial: &str| {
+        if !serial.is_empty() {
+            serials.insert(format!("{kind}:{serial}"));
+        }
+    };
+
+    if let Some(dmi_data) = &info.dmi_data {
+        add_serial("product", &dmi_data.product_serial);
+        add_serial("board", &dmi_data.board_serial);
+        add_serial("chassis", &dmi_data.chassis_serial);
+    }
+    for device in &info.block_devices {
+        add_serial("block", &device.serial);
+    }
+    for device in &info.nvme_devices {
+        add_serial("nvme", &device.serial);
+    }
+    for gpu in &info.gpus {
+        add_serial("gpu", &gpu.serial);
+    }
+
+    let mut hasher = Sha256::new();
+    for serial in serials {
+        hasher.update(serial.as_bytes());
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
 impl HardwareInfo {
     /// Returns whether the machine is deemed to be a DPU based on some properties
     pub fn is_dpu(&self) -> bool {
@@ -1111,6 +1151,37 @@ mod tests {
     const X86_V1_CPU_INFO_JSON: &[u8] =
         include_bytes!("hardware_info/test_data/x86_v1_cpu_info.json");
 
+    #[test]
+    fn test_hardware_fingerprint() {
+        let info: HardwareInfo = serde_json::from_slice(X86_INFO_JSON).unwrap();
+        let fingerprint = hardware_fingerprint(&info);
+        assert_eq!(fingerprint.len(), 64);
+
+        // The order in which components are reported doesn't matter
+        let mut reordered = info.clone();
+        reordered.block_devices.reverse();
+        reordered.nvme_devices.reverse();
+        assert_eq!(hardware_fingerprint(&reordered), fingerprint);
+
+        // Neither do additional partitions, or devices without a serial
+        let mut repartitioned = info.clone();
+        let mut partition = repartitioned.block_devices[0].clone();
+        partition.device_type = "partition".to_string();
+        repartitioned.block_devices.push(partition.clone());
+        partition.serial = String::new();
+        repartitioned.block_devices.push(partition);
+        assert_eq!(hardware_fingerprint(&repartitioned), fingerprint);
+
+        // A changed serial changes the fingerprint
+        let mut swapped = info.clone();
+        swapped.dmi_data.as_mut().unwrap().chassis_serial = "OtherChassis123".to_string();
+        assert_ne!(hardware_fingerprint(&swapped), fingerprint);
+
+        let mut swapped = info.clone();
+        swapped.nvme_devices[0].serial = "61M0A0D2XXXX".to_string();
+        assert_ne!(hardware_fingerprint(&swapped), fingerprint);
+    }
+
     #[test]
     fn test_machine_inventory_json_representation() {
         let inventory = MachineInventory {
@@ -21,6 +21,7 @@ use carbide_uuid::machine::MachineId;
 use chrono::{DateTime, Utc};
 use config_version::ConfigVersion;
 use rpc::errors::RpcDataConversionError;
+use rpc::validation::ValidationFinding;
 use serde::{Deserialize, Serialize};
 use sqlx::postgres::PgRow;
 use sqlx::{FromRow, Row};
@@ -361,6 +362,27 @@ pub struct MachineValidationResult {
     pub test_id: Option<String>,
 }
 
+impl MachineValidationResult {
+    /// Returns a finding if this test failed
+    pub fn finding(&self) -> Option<ValidationFinding> {
+        (self.exit_code != 0).then(|| {
+            ValidationFinding::error(
+                "FailedValidationTest",
+                format!("{} is failed", self.name),
+                Some(self.name.clone()),
+            )
+        })
+    }
+}
+
+/// Returns the findings for all failed tests in `results`
+pub fn validation_findings(results: &[MachineValidationResult]) -> Vec<ValidationFinding> {
+    results
+        .iter()
+        .filter_map(MachineValidationResult::finding)
+        .collect()
+}
+
 impl<'r> FromRow<'r, PgRow> for MachineValidationResult {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
         Ok(MachineValidationResult {
@@ -413,3 +435,52 @@ impl TryFrom<rpc::forge::MachineValidationResult> for MachineValidationResult {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rpc::validation::ValidationSeverity;
+
+    use super::*;
+
+    fn result(name: &str, exit_code: i32) -> MachineValidationResult {
+        MachineValidationResult {
+            validation_id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: String::new(),
+            stdout: String::new(),
+            stderr: String::new(),
+            command: "echo".to_string(),
+            args: String::new(),
+            context: "Discovery".to_string(),
+            exit_code,
+            start_time: Utc::now(),
+            end_time: Utc::now(),
+            test_id: Some(name.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_validation_findings() {
+        let results = [result("cpu", 0), result("gpu", 1), result("memory", -1)];
+
+        let findings = validation_findings(&results);
+        assert_eq!(
+            findings,
+            vec![
+                ValidationFinding {
+                    severity: ValidationSeverity::Error,
+                    code: "FailedValidationTest".to_string(),
+                    message: "gpu is failed".to_string(),
+                    subject: Some("gpu".to_string()),
+                },
+                ValidationFinding {
+                    severity: ValidationSeverity::Error,
+                    code: "FailedValidationTest".to_string(),
+                    message: "memory is failed".to_string(),
+                    subject: Some("memory".to_string()),
+                },
+            ]
+        );
+        assert!(validation_findings(&[result("cpu", 0)]).is_empty());
+    }
+}
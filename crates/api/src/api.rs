@@ -32,6 +32,7 @@ use ::rpc::protos::dns::{
 };
 use ::rpc::protos::{measured_boot as measured_boot_pb, mlx_device as mlx_device_pb};
 use carbide_dpf::KubeImpl;
+use carbide_uuid::instance::InstanceId;
 use carbide_uuid::machine::{MachineId, MachineInterfaceId};
 use db::db_read::PgPoolReader;
 use db::work_lock_manager::WorkLockManagerHandle;
@@ -51,6 +52,8 @@ use self::rpc::forge_server::Forge;
 use crate::cfg::file::CarbideConfig;
 use crate::dynamic_settings::DynamicSettings;
 use crate::ethernet_virtualization::EthVirtData;
+use crate::handlers::pxe::PxeRateLimiter;
+use crate::handlers::rack_firmware::{RackFirmwareDownloadTracker, RackFirmwareRolloutTracker};
 use crate::ib::IBFabricManager;
 use crate::logging::log_limiter::LogLimiter;
 use crate::nvlink::NmxmClientPool;
@@ -71,6 +74,9 @@ pub struct Api {
     pub(crate) ib_fabric_manager: Arc<dyn IBFabricManager>,
     pub(crate) runtime_config: Arc<CarbideConfig>,
     pub(crate) dpu_health_log_limiter: LogLimiter<MachineId>,
+    pub(crate) pxe_rate_limiter: PxeRateLimiter,
+    pub(crate) rack_firmware_downloads: RackFirmwareDownloadTracker,
+    pub(crate) rack_firmware_rollouts: RackFirmwareRolloutTracker,
     pub dynamic_settings: DynamicSettings,
     pub(crate) endpoint_explorer: Arc<dyn EndpointExplorer>,
     pub(crate) scout_stream_registry: ConnectionRegistry,
@@ -86,9 +92,13 @@ pub struct Api {
 pub(crate) type ScoutStreamType =
     Pin<Box<dyn Stream<Item = Result<rpc::ScoutStreamScoutBoundMessage, Status>> + Send>>;
 
+pub(crate) type WatchRackFirmwareDownloadType =
+    Pin<Box<dyn Stream<Item = Result<rpc::RackFirmwareDownloadEvent, Status>> + Send>>;
+
 #[tonic::async_trait]
 impl Forge for Api {
     type ScoutStreamStream = ScoutStreamType;
+    type WatchRackFirmwareDownloadStream = WatchRackFirmwareDownloadType;
 
     async fn version(
         &self,
@@ -392,6 +402,13 @@ impl Forge for Api {
         crate::handlers::instance::find_by_ids(self, request).await
     }
 
+    async fn find_stuck_instances(
+        &self,
+        request: Request<rpc::FindStuckInstancesRequest>,
+    ) -> Result<Response<rpc::StuckInstanceList>, Status> {
+        crate::handlers::instance::find_stuck(self, request).await
+    }
+
     async fn find_instance_by_machine_id(
         &self,
         request: Request<MachineId>,
@@ -399,6 +416,13 @@ impl Forge for Api {
         crate::handlers::instance::find_by_machine_id(self, request).await
     }
 
+    async fn get_instance_detail(
+        &self,
+        request: Request<InstanceId>,
+    ) -> Result<Response<rpc::InstanceDetail>, Status> {
+        crate::handlers::instance::get_detail(self, request).await
+    }
+
     async fn release_instance(
         &self,
         request: Request<rpc::InstanceReleaseRequest>,
@@ -1411,6 +1435,48 @@ impl Forge for Api {
         crate::handlers::rack_firmware::delete(self, request).await
     }
 
+    async fn update_rack_firmware_labels(
+        &self,
+        request: tonic::Request<rpc::RackFirmwareUpdateLabelsRequest>,
+    ) -> Result<Response<rpc::RackFirmware>, tonic::Status> {
+        crate::handlers::rack_firmware::update_labels(self, request).await
+    }
+
+    async fn rebuild_rack_firmware_lookup_table(
+        &self,
+        request: tonic::Request<rpc::RackFirmwareRebuildLookupTableRequest>,
+    ) -> Result<Response<rpc::RackFirmware>, tonic::Status> {
+        crate::handlers::rack_firmware::rebuild_lookup_table(self, request).await
+    }
+
+    async fn compare_rack_firmware(
+        &self,
+        request: tonic::Request<rpc::RackFirmwareCompareRequest>,
+    ) -> Result<Response<rpc::RackFirmwareDiff>, tonic::Status> {
+        crate::handlers::rack_firmware::compare(self, request).await
+    }
+
+    async fn purge_orphaned_rack_firmware_cache(
+        &self,
+        request: tonic::Request<rpc::RackFirmwareCachePurgeRequest>,
+    ) -> Result<Response<rpc::RackFirmwareCachePurgeResponse>, tonic::Status> {
+        crate::handlers::rack_firmware::purge_orphaned_cache(self, request).await
+    }
+
+    async fn preview_rack_firmware_downloads(
+        &self,
+        request: tonic::Request<rpc::RackFirmwareDownloadPreviewRequest>,
+    ) -> Result<Response<rpc::RackFirmwareDownloadPreview>, tonic::Status> {
+        crate::handlers::rack_firmware::preview_downloads(self, request).await
+    }
+
+    async fn watch_rack_firmware_download(
+        &self,
+        request: tonic::Request<rpc::RackFirmwareDownloadWatchRequest>,
+    ) -> Result<Response<Self::WatchRackFirmwareDownloadStream>, tonic::Status> {
+        crate::handlers::rack_firmware::watch_download(self, request).await
+    }
+
     async fn apply_rack_firmware(
         &self,
         request: tonic::Request<rpc::RackFirmwareApplyRequest>,
@@ -1418,6 +1484,20 @@ impl Forge for Api {
         crate::handlers::rack_firmware::apply(self, request).await
     }
 
+    async fn bulk_apply_rack_firmware(
+        &self,
+        request: tonic::Request<rpc::RackFirmwareBulkApplyRequest>,
+    ) -> Result<Response<rpc::RackFirmwareBulkApplyResponse>, tonic::Status> {
+        crate::handlers::rack_firmware::bulk_apply(self, request).await
+    }
+
+    async fn get_rack_firmware_bulk_apply_status(
+        &self,
+        request: tonic::Request<rpc::RackFirmwareBulkApplyStatusRequest>,
+    ) -> Result<Response<rpc::RackFirmwareBulkApplyResponse>, tonic::Status> {
+        crate::handlers::rack_firmware::get_bulk_apply_status(self, request)
+    }
+
     async fn get_rack_firmware_job_status(
         &self,
         request: tonic::Request<rpc::RackFirmwareJobStatusRequest>,
@@ -116,6 +116,7 @@ impl InternalRBACRules {
         x.perm("UpdateInstanceOperatingSystem", vec![SiteAgent]);
         x.perm("UpdateInstanceConfig", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("FindInstanceIds", vec![ForgeAdminCLI, SiteAgent]);
+        x.perm("FindStuckInstances", vec![ForgeAdminCLI]);
         x.perm(
             "FindInstancesByIds",
             vec![ForgeAdminCLI, SiteAgent, Ssh, SshRs],
@@ -124,6 +125,7 @@ impl InternalRBACRules {
             "FindInstanceByMachineID",
             vec![ForgeAdminCLI, Agent, SiteAgent],
         );
+        x.perm("GetInstanceDetail", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("RecordObservedInstanceNetworkStatus", vec![]);
         x.perm(
             "GetManagedHostNetworkConfig",
@@ -366,10 +368,18 @@ impl InternalRBACRules {
         x.perm("UpdateOsImage", vec![ForgeAdminCLI, SiteAgent]);
         x.perm("CreateRackFirmware", vec![ForgeAdminCLI]);
         x.perm("DeleteRackFirmware", vec![ForgeAdminCLI]);
+        x.perm("UpdateRackFirmwareLabels", vec![ForgeAdminCLI]);
+        x.perm("RebuildRackFirmwareLookupTable", vec![ForgeAdminCLI]);
+        x.perm("CompareRackFirmware", vec![ForgeAdminCLI]);
+        x.perm("PurgeOrphanedRackFirmwareCache", vec![ForgeAdminCLI]);
+        x.perm("PreviewRackFirmwareDownloads", vec![ForgeAdminCLI]);
+        x.perm("WatchRackFirmwareDownload", vec![ForgeAdminCLI]);
         x.perm("FindRackStateHistories", vec![ForgeAdminCLI, Machineatron]);
         x.perm("ListRackFirmware", vec![ForgeAdminCLI]);
         x.perm("GetRackFirmware", vec![ForgeAdminCLI]);
         x.perm("ApplyRackFirmware", vec![ForgeAdminCLI]);
+        x.perm("BulkApplyRackFirmware", vec![ForgeAdminCLI]);
+        x.perm("GetRackFirmwareBulkApplyStatus", vec![ForgeAdminCLI]);
         x.perm("GetRackFirmwareJobStatus", vec![ForgeAdminCLI]);
         x.perm("RebootCompleted", vec![Machineatron, Scout]);
         x.perm("PersistValidationResult", vec![Scout]);
@@ -409,6 +409,32 @@ pub struct CarbideConfig {
     // rms_api_url is the URL to the Rack Manager Service API.
     pub rms_api_url: Option<String>,
 
+    /// Directory the files of rack firmware configurations are downloaded to,
+    /// in one subdirectory per configuration
+    #[serde(default = "default_rack_firmware_directory")]
+    pub rack_firmware_directory: PathBuf,
+
+    /// Check that all files of a rack firmware configuration can be downloaded, with HEAD
+    /// requests, before downloading any of them
+    #[serde(default)]
+    pub rack_firmware_preflight_downloads: bool,
+
+    /// Maximum number of files of a rack firmware configuration that are downloaded at the
+    /// same time
+    #[serde(default = "default_rack_firmware_max_concurrent_downloads")]
+    pub rack_firmware_max_concurrent_downloads: usize,
+
+    /// RMS update targets of the Power Shelf firmware subcomponents in rack firmware
+    /// configurations, by subcomponent name. Subcomponents without a target are not
+    /// flashed. For example:
+    /// ```toml
+    /// [power_shelf_firmware_targets]
+    /// PSU = "PSU"
+    /// PMC = "PMC"
+    /// ```
+    #[serde(default)]
+    pub power_shelf_firmware_targets: HashMap<String, String>,
+
     /// Whether to use the host NIC instead of the DPUs on the compute trays.
     /// This is used to test the host NIC functionality.
     #[serde(
@@ -443,6 +469,30 @@ pub struct CarbideConfig {
     #[serde(default)]
     pub arm_pxe_boot_url_override: Option<String>,
 
+    /// Selects which iPXE script is served when a PXE request can not be
+    /// answered with boot instructions.
+    ///
+    /// ```toml
+    /// [pxe_fallback]
+    /// unknown_host = "unknown-host"
+    /// invalid_state = "error-instructions"
+    /// no_provisioning = "exit-instructions"
+    /// ```
+    #[serde(default)]
+    pub pxe_fallback: PxeFallbackConfig,
+
+    /// Limits how often PXE instructions are served to the same MAC address.
+    /// A host exceeding the limit, e.g. because it is stuck in a boot loop, is
+    /// served the `error-instructions` script instead. Disabled if absent.
+    ///
+    /// ```toml
+    /// [pxe_rate_limit]
+    /// max_requests = 20
+    /// window = "10m"
+    /// ```
+    #[serde(default)]
+    pub pxe_rate_limit: Option<PxeRateLimitConfig>,
+
     /// supernic_firmware_profiles is a nested map of FirmwareFlasherProfiles
     /// keyed by part_number and PSID. Each profile specifies the firmware to
     /// flash and optional lifecycle flags (reset, verify_image, verify_version).
@@ -473,6 +523,71 @@ pub struct DpfConfig {
     pub enabled: bool,
 }
 
+/// The iPXE scripts that can be served instead of boot instructions
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PxeFallbackTemplate {
+    /// Tells the host that it is unknown to Forge and exits
+    UnknownHost,
+    /// Prints the machine state and reports that boot can not continue
+    ErrorInstructions,
+    /// Prints the machine state and exits into the installed OS
+    ExitInstructions,
+}
+
+/// Maps each PXE failure mode onto the template that handles it.
+/// The defaults match the behavior before the mapping was configurable.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PxeFallbackConfig {
+    /// The interface belongs to neither a known machine nor an explored endpoint
+    #[serde(default = "PxeFallbackConfig::default_unknown_host")]
+    pub unknown_host: PxeFallbackTemplate,
+    /// The machine is in a state in which it is not expected to PXE boot
+    #[serde(default = "PxeFallbackConfig::default_invalid_state")]
+    pub invalid_state: PxeFallbackTemplate,
+    /// The machine is expected to boot its installed OS, and there are no
+    /// provisioning instructions to serve
+    #[serde(default = "PxeFallbackConfig::default_no_provisioning")]
+    pub no_provisioning: PxeFallbackTemplate,
+}
+
+impl PxeFallbackConfig {
+    fn default_unknown_host() -> PxeFallbackTemplate {
+        PxeFallbackTemplate::UnknownHost
+    }
+
+    fn default_invalid_state() -> PxeFallbackTemplate {
+        PxeFallbackTemplate::ErrorInstructions
+    }
+
+    fn default_no_provisioning() -> PxeFallbackTemplate {
+        PxeFallbackTemplate::ExitInstructions
+    }
+}
+
+impl Default for PxeFallbackConfig {
+    fn default() -> Self {
+        Self {
+            unknown_host: Self::default_unknown_host(),
+            invalid_state: Self::default_invalid_state(),
+            no_provisioning: Self::default_no_provisioning(),
+        }
+    }
+}
+
+/// Limits the PXE requests of a single MAC address
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PxeRateLimitConfig {
+    /// How many requests a MAC address may make within `window`
+    pub max_requests: u32,
+    /// The period over which requests are counted
+    #[serde(
+        deserialize_with = "deserialize_duration",
+        serialize_with = "as_std_duration"
+    )]
+    pub window: std::time::Duration,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct SpdmConfig {
     #[serde(default)]
@@ -1591,6 +1706,14 @@ fn default_listen() -> SocketAddr {
     "[::]:1079".parse().unwrap()
 }
 
+fn default_rack_firmware_directory() -> PathBuf {
+    PathBuf::from("/forge-boot-artifacts/blobs/internal/fw/rack_firmware")
+}
+
+fn default_rack_firmware_max_concurrent_downloads() -> usize {
+    4
+}
+
 fn default_max_database_connections() -> u32 {
     1000
 }
@@ -1903,6 +2026,18 @@ pub struct MachineUpdater {
     /// The maximum percentage of machines that have in-progress updates running.  This prevents
     /// too many machines from being put into maintenance at any given time.  If both values are given, the lesser will be used.
     pub max_concurrent_machine_updates_percent: Option<i32>,
+    /// Daily windows in which new machine updates may be started on this site.  Updates that are
+    /// already running are not interrupted outside of them.  If empty, updates can start at any time.
+    #[serde(default)]
+    pub maintenance_windows: Vec<MaintenanceWindow>,
+}
+
+impl MachineUpdater {
+    /// Whether new machine updates may be started at `now`
+    pub fn in_maintenance_window(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.maintenance_windows.is_empty()
+            || self.maintenance_windows.iter().any(|w| w.contains(now))
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -1911,6 +2046,25 @@ pub struct TimePeriod {
     pub end: chrono::DateTime<chrono::Utc>,
 }
 
+/// A window that opens every day at `start` and closes at `end`, both in UTC.
+/// If `end` is before `start`, the window spans midnight.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MaintenanceWindow {
+    pub start: chrono::NaiveTime,
+    pub end: chrono::NaiveTime,
+}
+
+impl MaintenanceWindow {
+    pub fn contains(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        let time = now.time();
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
 impl FirmwareGlobal {
     pub fn instance_updates_manual_tagging_default() -> bool {
         true
@@ -2824,6 +2978,28 @@ mod tests {
         assert_eq!(config, input);
     }
 
+    #[test]
+    fn deserialize_pxe_fallback_config() {
+        let config: PxeFallbackConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config, PxeFallbackConfig::default());
+
+        let config =
+            r#"{"unknown_host": "exit-instructions", "no_provisioning": "error-instructions"}"#;
+        let config: PxeFallbackConfig = serde_json::from_str(config).unwrap();
+        assert_eq!(
+            config,
+            PxeFallbackConfig {
+                unknown_host: PxeFallbackTemplate::ExitInstructions,
+                invalid_state: PxeFallbackTemplate::ErrorInstructions,
+                no_provisioning: PxeFallbackTemplate::ErrorInstructions,
+            }
+        );
+
+        assert!(
+            serde_json::from_str::<PxeFallbackConfig>(r#"{"unknown_host": "reboot"}"#).is_err()
+        );
+    }
+
     #[test]
     fn test_redact_config() {
         let mut config: CarbideConfig = Figment::new()
@@ -49,8 +49,8 @@ use tonic::{Request, Response, Status};
 use crate::api::{Api, log_machine_id, log_request_data, log_tenant_organization_id};
 use crate::handlers::utils::convert_and_log_machine_id;
 use crate::instance::{
-    InstanceAllocationRequest, allocate_ib_port_guid, allocate_instance, allocate_network,
-    validate_ib_partition_ownership,
+    IDEMPOTENCY_KEY_WINDOW, InstanceAllocationRequest, allocate_ib_port_guid, allocate_instance,
+    allocate_network, validate_ib_partition_ownership,
 };
 use crate::redfish::RedfishAuth;
 use crate::{CarbideError, CarbideResult};
@@ -109,6 +109,39 @@ pub(crate) async fn allocate(
     log_machine_id(&request.machine_id);
     log_tenant_organization_id(request.config.tenant.tenant_organization_id.as_str());
 
+    // A retried request returns the instance allocated by the first attempt
+    if let Some(idempotency_key) = &request.idempotency_key
+        && let Some(instance_id) = db::instance::find_id_by_idempotency_key(
+            &api.database_connection,
+            request.config.tenant.tenant_organization_id.as_str(),
+            idempotency_key,
+            chrono::Utc::now() - IDEMPOTENCY_KEY_WINDOW,
+        )
+        .await?
+    {
+        tracing::info!(
+            %instance_id,
+            %idempotency_key,
+            "Returning instance previously allocated for idempotency key"
+        );
+
+        let mut txn = api.txn_begin().await?;
+        let mh_snapshot = db::managed_host::load_by_instance_ids(
+            &mut txn,
+            &[instance_id],
+            LoadSnapshotOptions::default().with_host_health(api.runtime_config.host_health),
+        )
+        .await?
+        .pop()
+        .ok_or_else(|| CarbideError::NotFoundError {
+            kind: "instance",
+            id: instance_id.to_string(),
+        })?;
+        let _ = txn.rollback().await;
+
+        return Ok(Response::new(snapshot_to_instance(mh_snapshot)?));
+    }
+
     // Row-locking on Machine records happens in allocate_instance
     let mh_snapshot = allocate_instance(api, request, api.runtime_config.host_health).await?;
 
@@ -265,6 +298,112 @@ pub(crate) async fn find_by_machine_id(
     Ok(response)
 }
 
+/// Returns instances which have not reached the Ready tenant state within the
+/// requested threshold since they were created
+pub(crate) async fn find_stuck(
+    api: &Api,
+    request: Request<rpc::FindStuckInstancesRequest>,
+) -> Result<Response<rpc::StuckInstanceList>, Status> {
+    log_request_data(&request);
+
+    let threshold = request
+        .into_inner()
+        .threshold
+        .ok_or(CarbideError::MissingArgument("threshold"))?;
+    let threshold = chrono::TimeDelta::try_from(threshold)
+        .map_err(|e| CarbideError::InvalidArgument(format!("Invalid threshold: {e}")))?;
+
+    let now = chrono::Utc::now();
+    let stuck = db::instance::find_stuck(&api.database_connection, now - threshold).await?;
+
+    let instances = stuck
+        .into_iter()
+        .map(|instance| rpc::StuckInstance {
+            instance_id: Some(instance.id),
+            machine_id: Some(instance.machine_id),
+            created: Some(instance.requested.into()),
+            stuck_for: Some((now - instance.requested).into()),
+            state: instance.state,
+        })
+        .collect();
+
+    Ok(Response::new(rpc::StuckInstanceList { instances }))
+}
+
+/// Returns an instance together with the network segments and VPCs its interfaces are
+/// attached to, so that callers like the web UI can render an instance with a single call
+pub(crate) async fn get_detail(
+    api: &Api,
+    request: Request<InstanceId>,
+) -> Result<Response<rpc::InstanceDetail>, Status> {
+    log_request_data(&request);
+
+    let instance_id = request.into_inner();
+
+    let instance = find_by_ids(
+        api,
+        Request::new(rpc::InstancesByIdsRequest {
+            instance_ids: vec![instance_id],
+        }),
+    )
+    .await?
+    .into_inner()
+    .instances
+    .pop()
+    .ok_or_else(|| CarbideError::NotFoundError {
+        kind: "instance",
+        id: instance_id.to_string(),
+    })?;
+
+    let network_segments_ids: Vec<_> = instance
+        .config
+        .as_ref()
+        .and_then(|config| config.network.as_ref())
+        .map(|network| network.interfaces.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|iface| iface.network_segment_id)
+        .unique()
+        .collect();
+
+    // The segments and VPCs are only shown alongside the instance, so failing to
+    // load them doesn't fail the whole detail
+    let (network_segments, vpcs) = if network_segments_ids.is_empty() {
+        (Ok(Vec::new()), Ok(Vec::new()))
+    } else {
+        tokio::join!(
+            crate::handlers::network_segment::find_by_ids(
+                api,
+                Request::new(rpc::NetworkSegmentsByIdsRequest {
+                    network_segments_ids: network_segments_ids.clone(),
+                    include_history: false,
+                    include_num_free_ips: false,
+                }),
+            ),
+            db::vpc::find_by_segments(&api.database_connection, &network_segments_ids),
+        )
+    };
+
+    let network_segments = network_segments
+        .map(|response| response.into_inner().network_segments)
+        .unwrap_or_else(|e| {
+            tracing::warn!(%instance_id, error = %e, "Failed to load network segments of instance");
+            Vec::new()
+        });
+    let vpcs = vpcs
+        .map(|vpcs| vpcs.into_iter().map(rpc::Vpc::from).collect())
+        .unwrap_or_else(|e| {
+            tracing::warn!(%instance_id, error = %e, "Failed to load VPCs of instance");
+            Vec::new()
+        });
+
+    Ok(Response::new(rpc::InstanceDetail {
+        instance: Some(instance),
+        network_segments,
+        vpcs,
+    }))
+}
+
 /// Creates a TenantReportedIssue health override template with issue details
 fn create_tenant_reported_issue_override(issue: &rpc::Issue) -> HealthReport {
     HealthReport {
@@ -24,7 +24,9 @@ pub use ::rpc::forge as rpc;
 use carbide_uuid::nvlink::NvLinkDomainId;
 use db::WithTransaction;
 use futures_util::FutureExt;
-use model::hardware_info::{GpuPlatformInfo, HardwareInfo, MachineNvLinkInfo, NvLinkGpu};
+use model::hardware_info::{
+    GpuPlatformInfo, HardwareInfo, MachineNvLinkInfo, NvLinkGpu, hardware_fingerprint,
+};
 use model::machine::machine_id::{from_hardware_info, host_id_from_dpu_hardware_info};
 use model::machine::machine_search_config::MachineSearchConfig;
 use model::machine::{DpuInitState, DpuInitStates, ManagedHostState};
@@ -33,6 +35,7 @@ use tonic::{Request, Response, Status};
 use crate::api::{Api, log_machine_id, log_request_data};
 use crate::cfg::file::NvLinkConfig;
 use crate::handlers::utils::convert_and_log_machine_id;
+use crate::ib::canonical_guid;
 use crate::{CarbideError, CarbideResult, attestation as attest};
 
 pub(crate) async fn discover_machine(
@@ -70,7 +73,11 @@ pub(crate) async fn discover_machine(
         })
         .ok_or_else(|| Status::invalid_argument("Discovery data is not populated"))?;
     let attest_key_info_opt = discovery_data.attest_key_info.clone();
-    let hardware_info = HardwareInfo::try_from(discovery_data).map_err(CarbideError::from)?;
+    let mut hardware_info = HardwareInfo::try_from(discovery_data).map_err(CarbideError::from)?;
+    // Store IB GUIDs in the format UFM reports them in, so that they can be looked up in UFM
+    for ib_interface in hardware_info.infiniband_interfaces.iter_mut() {
+        ib_interface.guid = canonical_guid(&ib_interface.guid);
+    }
 
     // this is an early check for certificate creation that happens later on in this method.
     // let's save us the hassle and return immediately if the below condition is not satisfied
@@ -263,6 +270,19 @@ pub(crate) async fn discover_machine(
         .await?
     };
 
+    let fingerprint = hardware_fingerprint(&hardware_info);
+    if let Some(previous_fingerprint) =
+        db::machine::update_hardware_fingerprint(&stable_machine_id, &fingerprint, &mut txn).await?
+        && previous_fingerprint != fingerprint
+    {
+        tracing::warn!(
+            machine_id = %stable_machine_id,
+            previous_fingerprint,
+            fingerprint,
+            "Hardware fingerprint changed since the last discovery. Components might have been swapped"
+        );
+    }
+
     db::machine_topology::create_or_update_with_bom_validation(
         &mut txn,
         &stable_machine_id,
@@ -117,26 +117,32 @@ pub(crate) async fn mark_machine_validation_complete(
         None => "Success".to_owned(),
     };
 
-    let result =
-        match db::machine_validation_result::validate_current_context(&mut txn, rpc_id).await? {
-            Some(error_message) => {
-                db::machine::update_failure_details_by_machine_id(
-                    &machine_id,
-                    &mut txn,
-                    FailureDetails {
-                        cause: FailureCause::MachineValidation {
-                            err: error_message.clone(),
-                        },
-                        failed_at: chrono::Utc::now(),
-                        source: FailureSource::Scout,
-                    },
-                )
-                .await?;
-                state = MachineValidationState::Failed;
-                error_message
-            }
-            None => "Success".to_owned(),
-        };
+    let findings =
+        db::machine_validation_result::validate_current_context(&mut txn, rpc_id).await?;
+    let errors: Vec<&str> = findings
+        .iter()
+        .filter(|finding| finding.is_error())
+        .map(|finding| finding.message.as_str())
+        .collect();
+    let result = if errors.is_empty() {
+        "Success".to_owned()
+    } else {
+        let error_message = errors.join(", ");
+        db::machine::update_failure_details_by_machine_id(
+            &machine_id,
+            &mut txn,
+            FailureDetails {
+                cause: FailureCause::MachineValidation {
+                    err: error_message.clone(),
+                },
+                failed_at: chrono::Utc::now(),
+                source: FailureSource::Scout,
+            },
+        )
+        .await?;
+        state = MachineValidationState::Failed;
+        error_message
+    };
 
     db::machine_validation::mark_machine_validation_complete(
         &mut txn,
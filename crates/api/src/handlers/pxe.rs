@@ -16,15 +16,85 @@
  */
 
 use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 use ::rpc::forge as rpc;
+use dashmap::DashMap;
 use db;
+use mac_address::MacAddress;
+use model::pxe::PxeInstructionRequest;
 use tonic::{Request, Response, Status};
 
 use crate::CarbideError;
 use crate::api::{Api, log_request_data};
+use crate::cfg::file::PxeRateLimitConfig;
 use crate::ipxe::PxeInstructions;
 
+/// Counts the PXE requests of every MAC address, so that hosts which are
+/// stuck in a boot loop can be detected
+pub struct PxeRateLimiter {
+    config: Option<PxeRateLimitConfig>,
+    requests: DashMap<MacAddress, RequestWindow>,
+    /// When windows that have ended were last removed from `requests`
+    last_pruned: Mutex<Instant>,
+}
+
+struct RequestWindow {
+    start: Instant,
+    count: u32,
+}
+
+impl PxeRateLimiter {
+    pub fn new(config: Option<PxeRateLimitConfig>) -> Self {
+        Self {
+            config,
+            requests: DashMap::new(),
+            last_pruned: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.is_some()
+    }
+
+    /// Records a request from `mac_address` and returns whether it exceeds
+    /// the configured limit
+    fn is_limited(&self, mac_address: MacAddress) -> bool {
+        let Some(config) = &self.config else {
+            return false;
+        };
+
+        let now = Instant::now();
+        self.prune(config.window, now);
+
+        let mut window = self.requests.entry(mac_address).or_insert(RequestWindow {
+            start: now,
+            count: 0,
+        });
+        if now.duration_since(window.start) >= config.window {
+            window.start = now;
+            window.count = 0;
+        }
+        window.count += 1;
+        window.count > config.max_requests
+    }
+
+    /// Forget MAC addresses whose window has ended, at most once per `window`,
+    /// so that hosts which stopped PXE booting don't stay in memory forever
+    fn prune(&self, window: Duration, now: Instant) {
+        {
+            let mut last_pruned = self.last_pruned.lock().unwrap();
+            if now.duration_since(*last_pruned) < window {
+                return;
+            }
+            *last_pruned = now;
+        }
+        self.requests
+            .retain(|_, request_window| now.duration_since(request_window.start) < window);
+    }
+}
+
 // The carbide pxe server makes this RPC call
 pub(crate) async fn get_pxe_instructions(
     api: &Api,
@@ -34,9 +104,26 @@ pub(crate) async fn get_pxe_instructions(
 
     let mut txn = api.txn_begin().await?;
 
-    let request = request.into_inner().try_into()?;
+    let request: PxeInstructionRequest = request.into_inner().try_into()?;
 
-    let pxe_script = PxeInstructions::get_pxe_instructions(&mut txn, request).await?;
+    if api.pxe_rate_limiter.is_enabled() {
+        let interface = db::machine_interface::find_one(&mut txn, request.interface_id).await?;
+        if api.pxe_rate_limiter.is_limited(interface.mac_address) {
+            tracing::warn!(
+                mac_address = %interface.mac_address,
+                interface_id = %request.interface_id,
+                "PXE request rate limit exceeded, the host might be stuck in a boot loop"
+            );
+            txn.commit().await?;
+            return Ok(Response::new(rpc::PxeInstructions {
+                pxe_script: PxeInstructions::get_rate_limited_instructions(request.interface_id),
+            }));
+        }
+    }
+
+    let pxe_script =
+        PxeInstructions::get_pxe_instructions(&mut txn, request, &api.runtime_config.pxe_fallback)
+            .await?;
 
     txn.commit().await?;
 
@@ -195,3 +282,29 @@ pub(crate) async fn get_cloud_init_instructions(
 
     Ok(Response::new(instructions))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_prunes_ended_windows() {
+        let limiter = PxeRateLimiter::new(Some(PxeRateLimitConfig {
+            max_requests: 1,
+            window: Duration::from_secs(60),
+        }));
+        let stale: MacAddress = "00:11:22:33:44:55".parse().unwrap();
+        let active: MacAddress = "00:11:22:33:44:66".parse().unwrap();
+
+        assert!(!limiter.is_limited(stale));
+        assert!(!limiter.is_limited(active));
+        assert_eq!(limiter.requests.len(), 2);
+
+        let later = Instant::now() + Duration::from_secs(90);
+        limiter.requests.get_mut(&active).unwrap().start = later;
+        limiter.prune(Duration::from_secs(60), later);
+
+        assert_eq!(limiter.requests.len(), 1);
+        assert!(limiter.requests.contains_key(&active));
+    }
+}
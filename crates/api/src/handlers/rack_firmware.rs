@@ -15,29 +15,48 @@
  * limitations under the License.
  */
 
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use carbide_uuid::rack::RackId;
+use dashmap::DashMap;
 use db::DatabaseError;
-use db::rack_firmware::RackFirmware as DbRackFirmware;
+use db::rack_firmware::{RackFirmware as DbRackFirmware, RackFirmwareApplyAttempt};
 use forge_secrets::credentials::{CredentialKey, CredentialReader, Credentials};
+use librms::protos::rack_manager::FirmwareJobState;
 use rpc::forge::{
-    DeviceUpdateResult, NodeJobInfo, RackFirmware, RackFirmwareApplyRequest,
-    RackFirmwareApplyResponse, RackFirmwareCreateRequest, RackFirmwareDeleteRequest,
-    RackFirmwareGetRequest, RackFirmwareJobStatusRequest, RackFirmwareJobStatusResponse,
-    RackFirmwareList, RackFirmwareListRequest,
+    DeviceUpdateResult, FirmwareJobComponentResult, FirmwareJobNodeResult, NodeJobInfo,
+    RackFirmware, RackFirmwareApplyRequest, RackFirmwareApplyResponse,
+    RackFirmwareBulkApplyRequest, RackFirmwareBulkApplyResponse,
+    RackFirmwareBulkApplyStatusRequest, RackFirmwareCachePurgeRequest,
+    RackFirmwareCachePurgeResponse, RackFirmwareCompareRequest, RackFirmwareComponentChangeType,
+    RackFirmwareComponentDiff, RackFirmwareCreateRequest, RackFirmwareDeleteRequest,
+    RackFirmwareDiff, RackFirmwareDownloadEvent, RackFirmwareDownloadEventType,
+    RackFirmwareDownloadFile, RackFirmwareDownloadPreview, RackFirmwareDownloadPreviewRequest,
+    RackFirmwareDownloadWatchRequest, RackFirmwareGetRequest, RackFirmwareJobStatusRequest,
+    RackFirmwareJobStatusResponse, RackFirmwareList, RackFirmwareListRequest,
+    RackFirmwareRackApplyResult, RackFirmwareRackApplyStatus,
+    RackFirmwareRebuildLookupTableRequest, RackFirmwareUpdateLabelsRequest,
 };
+use rpc::rack_firmware::{POWER_SHELF_FIRMWARE_COMPONENT, validate_config};
+use rpc::validation::ValidationFinding;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
+use tokio::io::AsyncReadExt;
+use tokio::sync::broadcast;
 use tokio::task::JoinSet;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::BroadcastStream;
 use tonic::{Request, Response, Status};
 
-use crate::api::Api;
+use crate::api::{Api, WatchRackFirmwareDownloadType};
 use crate::errors::CarbideError;
 // Structs for parsing rack firmware JSON
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct ParsedFirmwareComponents {
+pub(crate) struct ParsedFirmwareComponents {
     board_skus: Vec<BoardSkuFirmware>,
 }
 
@@ -61,49 +80,135 @@ struct FirmwareComponent {
     subcomponents: Vec<FirmwareSubComponent>,
 }
 
+impl FirmwareComponent {
+    /// Firmware type ("prod" or "dev") in lowercase. Defaults to prod if not specified.
+    fn firmware_type(&self) -> String {
+        self.component_type
+            .as_ref()
+            .map(|t| t.to_lowercase())
+            .unwrap_or_else(|| "prod".to_string())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct FirmwareSubComponent {
     component: String,
     version: String,
     skuid: Option<String>,
+    /// Name of the firmware file of the component that the subcomponent is flashed from
+    #[serde(default)]
+    filename: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct FirmwareLocation {
-    location: String,
-    location_type: String,
-    firmware_type: Option<String>,
+pub(crate) struct FirmwareLocation {
+    pub(crate) location: String,
+    pub(crate) location_type: String,
+    pub(crate) firmware_type: Option<String>,
+    /// Expected SHA-256 of the file (hex), if the config provides one
+    #[serde(default)]
+    pub(crate) sha256: Option<String>,
+    /// Expected size of the file in bytes, if the config provides one
+    #[serde(default)]
+    pub(crate) size: Option<u64>,
 }
 
 // Structs for firmware lookup table
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct FirmwareLookupTable {
+pub(crate) struct FirmwareLookupTable {
     /// Map of device_type -> component_name -> FirmwareLookupEntry
-    devices:
+    pub(crate) devices:
         std::collections::HashMap<String, std::collections::HashMap<String, FirmwareLookupEntry>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-struct FirmwareLookupEntry {
+pub(crate) struct FirmwareLookupEntry {
     /// Path to the downloaded firmware file (relative to firmware_id directory)
-    filename: String,
+    pub(crate) filename: String,
     /// Target identifier for RMS update command
-    target: String,
+    pub(crate) target: String,
     /// Component name (e.g., "HMC", "BMC")
-    component: String,
+    pub(crate) component: String,
     /// Bundle identifier (e.g., "P4975", "P4972")
-    bundle: String,
+    pub(crate) bundle: String,
     /// Firmware type: "prod" or "dev"
-    firmware_type: String,
+    pub(crate) firmware_type: String,
     /// Version of the firmware bundle
-    version: Option<String>,
+    pub(crate) version: Option<String>,
     /// Subcomponents with individual versions
     subcomponents: Vec<FirmwareSubComponent>,
 }
 
+// Structs for parsing the result of an RMS firmware job
+
+/// Result data RMS reports for a finished firmware job, e.g.
+/// `{"nodes": [{"node_id": "n1", "status": "success", "components": [{"target": "bmc", ...}]}]}`
+///
+/// Unknown fields are ignored and missing ones default, so newer RMS versions keep parsing.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub(crate) struct RmsFirmwareJobResult {
+    pub(crate) nodes: Vec<RmsFirmwareJobNodeResult>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub(crate) struct RmsFirmwareJobNodeResult {
+    pub(crate) node_id: String,
+    pub(crate) status: String,
+    pub(crate) message: String,
+    pub(crate) components: Vec<RmsFirmwareJobComponentResult>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub(crate) struct RmsFirmwareJobComponentResult {
+    pub(crate) target: String,
+    pub(crate) status: String,
+    pub(crate) message: String,
+    pub(crate) version: Option<String>,
+}
+
+impl From<RmsFirmwareJobNodeResult> for FirmwareJobNodeResult {
+    fn from(node: RmsFirmwareJobNodeResult) -> Self {
+        FirmwareJobNodeResult {
+            node_id: node.node_id,
+            status: node.status,
+            message: node.message,
+            component_results: node
+                .components
+                .into_iter()
+                .map(|c| FirmwareJobComponentResult {
+                    target: c.target,
+                    status: c.status,
+                    message: c.message,
+                    version: c.version.unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Parse the `result_json` of an RMS firmware job. Returns None if RMS did not report a result or
+/// reported one in a shape we don't know.
+pub(crate) fn parse_firmware_job_result(result_json: &str) -> Option<RmsFirmwareJobResult> {
+    if result_json.trim().is_empty() {
+        return None;
+    }
+    serde_json::from_str(result_json)
+        .inspect_err(|e| {
+            tracing::warn!(
+                error = %e,
+                result_json = %result_json,
+                "Failed to parse RMS firmware job result"
+            );
+        })
+        .ok()
+}
+
 /// Parse rack firmware JSON to extract firmware components
-fn parse_rack_firmware_json(config: &Value) -> Result<ParsedFirmwareComponents, String> {
+pub(crate) fn parse_rack_firmware_json(config: &Value) -> Result<ParsedFirmwareComponents, String> {
     let board_skus = config
         .get("BoardSKUs")
         .and_then(|v| v.as_array())
@@ -194,6 +299,11 @@ fn parse_rack_firmware_json(config: &Value) -> Result<ParsedFirmwareComponents,
                             .unwrap_or("")
                             .to_string(),
                         firmware_type,
+                        sha256: location
+                            .get("SHA256")
+                            .and_then(|v| v.as_str())
+                            .map(|s| s.to_lowercase()),
+                        size: location.get("Size").and_then(|v| v.as_u64()),
                     };
                     locations.push(loc);
                 }
@@ -224,11 +334,17 @@ fn parse_rack_firmware_json(config: &Value) -> Result<ParsedFirmwareComponents,
                         .and_then(|v| v.as_str())
                         .map(|s| s.to_string());
 
+                    let sub_filename = subcomp
+                        .get("Filename")
+                        .and_then(|v| v.as_str())
+                        .map(|s| s.to_string());
+
                     if !sub_component.is_empty() && !sub_version.is_empty() {
                         subcomponents.push(FirmwareSubComponent {
                             component: sub_component,
                             version: sub_version,
                             skuid: sub_skuid,
+                            filename: sub_filename,
                         });
                     }
                 }
@@ -268,6 +384,21 @@ pub async fn create(
     let config: serde_json::Value = serde_json::from_str(&req.config_json)
         .map_err(|e| Status::invalid_argument(format!("Invalid JSON: {}", e)))?;
 
+    // Apply the same checks as `rack-firmware validate` in the admin CLI
+    let (errors, warnings): (Vec<_>, Vec<_>) = validate_config(&config)
+        .into_iter()
+        .partition(ValidationFinding::is_error);
+    if !errors.is_empty() {
+        let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        return Err(Status::invalid_argument(format!(
+            "Invalid rack firmware config: {}",
+            errors.join("; ")
+        )));
+    }
+    for warning in &warnings {
+        tracing::warn!("Rack firmware config: {}", warning);
+    }
+
     // Extract ID from JSON - use "Id" field (UUID)
     let id = config
         .get("Id")
@@ -277,6 +408,8 @@ pub async fn create(
         })?
         .to_string();
 
+    validate_labels(&req.labels)?;
+
     // Validate token is provided
     if req.artifactory_token.is_empty() {
         return Err(Status::invalid_argument("Artifactory token is required"));
@@ -326,7 +459,8 @@ pub async fn create(
         .await
         .map_err(|e| CarbideError::from(DatabaseError::new("begin create", e)))?;
 
-    let db_config = DbRackFirmware::create(&mut txn, &id, config, parsed_components).await?;
+    let db_config =
+        DbRackFirmware::create(&mut txn, &id, config, parsed_components, &req.labels).await?;
 
     txn.commit()
         .await
@@ -343,6 +477,11 @@ pub async fn create(
                 parsed_struct,
                 api.credential_manager.clone() as Arc<dyn CredentialReader>,
                 api.database_connection.clone(),
+                api.rack_firmware_downloads.clone(),
+                api.runtime_config.rack_firmware_directory.join(&id),
+                api.runtime_config.power_shelf_firmware_targets.clone(),
+                api.runtime_config.rack_firmware_preflight_downloads,
+                api.runtime_config.rack_firmware_max_concurrent_downloads,
             );
             tracing::info!(
                 firmware_id = %id,
@@ -381,7 +520,13 @@ pub async fn list(
         .await
         .map_err(|e| CarbideError::from(DatabaseError::new("begin list", e)))?;
 
-    let db_configs = DbRackFirmware::list_all(&mut txn, req.only_available).await?;
+    let db_configs = DbRackFirmware::list_all(
+        &mut txn,
+        req.only_available,
+        req.firmware_type.as_deref(),
+        &req.label_selector,
+    )
+    .await?;
 
     txn.commit()
         .await
@@ -395,6 +540,38 @@ pub async fn list(
     Ok(Response::new(RackFirmwareList { configs }))
 }
 
+/// Replace the labels of a Rack firmware configuration
+pub async fn update_labels(
+    api: &Api,
+    request: Request<RackFirmwareUpdateLabelsRequest>,
+) -> Result<Response<RackFirmware>, Status> {
+    let req = request.into_inner();
+    validate_labels(&req.labels)?;
+
+    let mut txn = api
+        .database_connection
+        .begin()
+        .await
+        .map_err(|e| CarbideError::from(DatabaseError::new("begin update labels", e)))?;
+
+    let db_config = DbRackFirmware::set_labels(&mut txn, &req.id, &req.labels)
+        .await
+        .map_err(CarbideError::from)?;
+
+    txn.commit()
+        .await
+        .map_err(|e| CarbideError::from(DatabaseError::new("commit update labels", e)))?;
+
+    Ok(Response::new((&db_config).into()))
+}
+
+fn validate_labels(labels: &HashMap<String, String>) -> Result<(), Status> {
+    if labels.keys().any(|key| key.trim().is_empty()) {
+        return Err(Status::invalid_argument("Label keys must not be empty"));
+    }
+    Ok(())
+}
+
 /// Delete a Rack firmware configuration
 pub async fn delete(
     api: &Api,
@@ -419,38 +596,517 @@ pub async fn delete(
     Ok(Response::new(()))
 }
 
+/// Remove the directories under the firmware cache root that do not belong to any Rack
+/// firmware configuration, e.g. left behind by deleted configurations. With `dry_run`, the
+/// orphaned directories are only listed.
+pub async fn purge_orphaned_cache(
+    api: &Api,
+    request: Request<RackFirmwareCachePurgeRequest>,
+) -> Result<Response<RackFirmwareCachePurgeResponse>, Status> {
+    let req = request.into_inner();
+    let cache_root = &api.runtime_config.rack_firmware_directory;
+
+    let mut cache_dirs = Vec::new();
+    match tokio::fs::read_dir(cache_root).await {
+        Ok(mut entries) => {
+            while let Some(entry) = entries.next_entry().await.map_err(|e| {
+                Status::internal(format!("Failed to list firmware cache directory: {e}"))
+            })? {
+                if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
+                    cache_dirs.push(entry.file_name().to_string_lossy().into_owned());
+                }
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => {
+            return Err(Status::internal(format!(
+                "Failed to list firmware cache directory: {e}"
+            )));
+        }
+    }
+
+    let mut txn = api
+        .database_connection
+        .begin()
+        .await
+        .map_err(|e| CarbideError::from(DatabaseError::new("begin purge cache", e)))?;
+
+    let firmware_ids: BTreeSet<String> =
+        DbRackFirmware::list_all(&mut txn, false, None, &HashMap::new())
+            .await?
+            .into_iter()
+            .map(|config| config.id)
+            .collect();
+
+    txn.commit()
+        .await
+        .map_err(|e| CarbideError::from(DatabaseError::new("commit purge cache", e)))?;
+
+    let mut orphaned_directories: Vec<String> = cache_dirs
+        .into_iter()
+        .filter(|dir| !firmware_ids.contains(dir))
+        .collect();
+    orphaned_directories.sort();
+
+    if !req.dry_run {
+        for dir in &orphaned_directories {
+            tokio::fs::remove_dir_all(cache_root.join(dir))
+                .await
+                .map_err(|e| {
+                    Status::internal(format!(
+                        "Failed to remove firmware cache directory {dir}: {e}"
+                    ))
+                })?;
+            tracing::info!(directory = %dir, "Removed orphaned firmware cache directory");
+        }
+    }
+
+    Ok(Response::new(RackFirmwareCachePurgeResponse {
+        orphaned_directories,
+        dry_run: req.dry_run,
+    }))
+}
+
+/// Rebuild the firmware lookup table of a Rack firmware configuration from its config, e.g.
+/// after support for a new device type was added. The files are not downloaded again.
+pub async fn rebuild_lookup_table(
+    api: &Api,
+    request: Request<RackFirmwareRebuildLookupTableRequest>,
+) -> Result<Response<RackFirmware>, Status> {
+    let req = request.into_inner();
+
+    let fw_config = DbRackFirmware::find_by_id(&api.database_connection, &req.id)
+        .await
+        .map_err(CarbideError::from)?;
+
+    // Until its files are downloaded, the parsed components are what the download works from
+    if !fw_config.available && fw_config.available_types.is_empty() {
+        return Err(Status::failed_precondition(format!(
+            "Rack firmware config {} has no downloaded files to build a lookup table for",
+            req.id
+        )));
+    }
+
+    let lookup_table = lookup_table_from_config(
+        &fw_config.config,
+        &api.runtime_config.power_shelf_firmware_targets,
+    )
+    .map_err(Status::internal)?;
+    let lookup_json = serde_json::to_value(&lookup_table)
+        .map_err(|e| Status::internal(format!("Failed to serialize lookup table: {}", e)))?;
+
+    let mut txn = api
+        .database_connection
+        .begin()
+        .await
+        .map_err(|e| CarbideError::from(DatabaseError::new("begin rebuild lookup table", e)))?;
+
+    let db_config = DbRackFirmware::set_parsed_components(&mut txn, &req.id, lookup_json)
+        .await
+        .map_err(CarbideError::from)?;
+
+    txn.commit()
+        .await
+        .map_err(|e| CarbideError::from(DatabaseError::new("commit rebuild lookup table", e)))?;
+
+    tracing::info!(
+        firmware_id = %req.id,
+        device_types = lookup_table.devices.len(),
+        "Rebuilt firmware lookup table"
+    );
+
+    Ok(Response::new((&db_config).into()))
+}
+
+/// Build the firmware lookup table of a Rack firmware configuration by parsing its `config` again
+fn lookup_table_from_config(
+    config: &Value,
+    power_shelf_targets: &HashMap<String, String>,
+) -> Result<FirmwareLookupTable, String> {
+    let parsed_components = parse_rack_firmware_json(config)
+        .map_err(|e| format!("Failed to parse stored config: {}", e))?;
+    Ok(build_firmware_lookup_table(
+        &parsed_components,
+        power_shelf_targets,
+    ))
+}
+
+/// Compare the firmware lookup tables of two Rack firmware configurations
+pub async fn compare(
+    api: &Api,
+    request: Request<RackFirmwareCompareRequest>,
+) -> Result<Response<RackFirmwareDiff>, Status> {
+    let req = request.into_inner();
+
+    let base = load_lookup_table(api, &req.base_id).await?;
+    let target = load_lookup_table(api, &req.target_id).await?;
+
+    Ok(Response::new(RackFirmwareDiff {
+        components: diff_lookup_tables(&base, &target),
+        base_id: req.base_id,
+        target_id: req.target_id,
+    }))
+}
+
+async fn load_lookup_table(api: &Api, id: &str) -> Result<FirmwareLookupTable, Status> {
+    let fw_config = DbRackFirmware::find_by_id(&api.database_connection, id)
+        .await
+        .map_err(CarbideError::from)?;
+
+    fw_config
+        .parsed_components
+        .and_then(|parsed| serde_json::from_value(parsed.0).ok())
+        .ok_or_else(|| {
+            Status::failed_precondition(format!(
+                "Rack firmware config {} has no firmware lookup table",
+                id
+            ))
+        })
+}
+
+/// The components whose version differs between `base` and `target`, or that only one of them
+/// has, ordered by device type and component
+fn diff_lookup_tables(
+    base: &FirmwareLookupTable,
+    target: &FirmwareLookupTable,
+) -> Vec<RackFirmwareComponentDiff> {
+    let empty = HashMap::new();
+    let device_types: BTreeSet<&String> =
+        base.devices.keys().chain(target.devices.keys()).collect();
+
+    let mut diffs = Vec::new();
+    for device_type in device_types {
+        let base_components = base.devices.get(device_type).unwrap_or(&empty);
+        let target_components = target.devices.get(device_type).unwrap_or(&empty);
+        let components: BTreeSet<&String> = base_components
+            .keys()
+            .chain(target_components.keys())
+            .collect();
+
+        for component in components {
+            let base_entry = base_components.get(component);
+            let target_entry = target_components.get(component);
+            let change_type = match (base_entry, target_entry) {
+                (None, Some(_)) => RackFirmwareComponentChangeType::ComponentAdded,
+                (Some(_), None) => RackFirmwareComponentChangeType::ComponentRemoved,
+                (Some(b), Some(t)) if b.version != t.version => {
+                    RackFirmwareComponentChangeType::ComponentVersionChanged
+                }
+                _ => continue,
+            };
+            diffs.push(RackFirmwareComponentDiff {
+                device_type: device_type.clone(),
+                component: component.clone(),
+                change_type: change_type as i32,
+                base_version: base_entry.and_then(|e| e.version.clone()),
+                target_version: target_entry.and_then(|e| e.version.clone()),
+            });
+        }
+    }
+    diffs
+}
+
+/// List the files creating a Rack firmware configuration would download, without downloading them
+pub async fn preview_downloads(
+    _api: &Api,
+    request: Request<RackFirmwareDownloadPreviewRequest>,
+) -> Result<Response<RackFirmwareDownloadPreview>, Status> {
+    let req = request.into_inner();
+
+    let config: serde_json::Value = serde_json::from_str(&req.config_json)
+        .map_err(|e| Status::invalid_argument(format!("Invalid JSON: {}", e)))?;
+    let parsed_components = parse_rack_firmware_json(&config).map_err(Status::invalid_argument)?;
+
+    Ok(Response::new(RackFirmwareDownloadPreview {
+        files: download_files(&parsed_components),
+    }))
+}
+
+/// The unique set of files downloading `parsed_components` fetches, in config order.
+/// Files are cached by filename, so a filename shared by several components or board SKUs
+/// is listed, and downloaded, once.
+pub(crate) fn download_files(
+    parsed_components: &ParsedFirmwareComponents,
+) -> Vec<RackFirmwareDownloadFile> {
+    let mut files: Vec<RackFirmwareDownloadFile> = Vec::new();
+    let mut file_indexes = HashMap::new();
+
+    for board_sku in &parsed_components.board_skus {
+        for firmware_component in &board_sku.firmware_components {
+            let firmware_type = firmware_component.firmware_type();
+            for location in &firmware_component.locations {
+                let filename = firmware_filename(&location.location);
+                let index = *file_indexes.entry(filename).or_insert_with(|| {
+                    files.push(RackFirmwareDownloadFile {
+                        url: location.location.clone(),
+                        filename: filename.to_string(),
+                        location_type: location.location_type.clone(),
+                        components: Vec::new(),
+                        firmware_types: Vec::new(),
+                        size: location.size,
+                        sha256: location.sha256.clone(),
+                    });
+                    files.len() - 1
+                });
+
+                let file = &mut files[index];
+                if file.url != location.location {
+                    tracing::warn!(
+                        filename = %filename,
+                        url = %file.url,
+                        ignored_url = %location.location,
+                        "Firmware config downloads the same filename from several URLs, using the first"
+                    );
+                }
+                if !file.components.contains(&firmware_component.component) {
+                    file.components.push(firmware_component.component.clone());
+                }
+                if !file.firmware_types.contains(&firmware_type) {
+                    file.firmware_types.push(firmware_type.clone());
+                }
+            }
+        }
+    }
+
+    files
+}
+
+/// Stream the progress of the download of a Rack firmware configuration's files
+pub async fn watch_download(
+    api: &Api,
+    request: Request<RackFirmwareDownloadWatchRequest>,
+) -> Result<Response<WatchRackFirmwareDownloadType>, Status> {
+    let req = request.into_inner();
+
+    let Some(receiver) = api.rack_firmware_downloads.subscribe(&req.id) else {
+        // Distinguish an unknown config from one that is not downloading
+        DbRackFirmware::find_by_id(&api.database_connection, &req.id)
+            .await
+            .map_err(CarbideError::from)?;
+        return Err(Status::failed_precondition(format!(
+            "No download in progress for rack firmware config {}",
+            req.id
+        )));
+    };
+
+    let firmware_id = req.id;
+    let stream = BroadcastStream::new(receiver).filter_map(move |event| match event {
+        Ok(event) => Some(Ok(event)),
+        Err(e) => {
+            tracing::warn!(
+                firmware_id = %firmware_id,
+                error = %e,
+                "Rack firmware download watcher fell behind"
+            );
+            None
+        }
+    });
+
+    Ok(Response::new(Box::pin(stream)))
+}
+
+/// Capacity of the channel a firmware download publishes its events on.
+/// Watchers that fall further behind skip the events they missed.
+const DOWNLOAD_EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Minimum number of bytes received between two progress events for a file
+const DOWNLOAD_PROGRESS_INTERVAL_BYTES: usize = 1024 * 1024;
+
+/// Tracks the rack firmware downloads in progress, so that callers can watch them
+#[derive(Clone, Debug, Default)]
+pub struct RackFirmwareDownloadTracker {
+    downloads: Arc<DashMap<String, broadcast::Sender<RackFirmwareDownloadEvent>>>,
+}
+
+impl RackFirmwareDownloadTracker {
+    /// Register a download of the files of `firmware_id`, replacing any earlier one
+    pub(crate) fn start(&self, firmware_id: &str) -> DownloadProgress {
+        let (sender, _) = broadcast::channel(DOWNLOAD_EVENT_CHANNEL_CAPACITY);
+        self.downloads
+            .insert(firmware_id.to_string(), sender.clone());
+        DownloadProgress {
+            firmware_id: firmware_id.to_string(),
+            sender,
+            attempt: 0,
+        }
+    }
+
+    /// Unregister a finished download. The streams of its watchers end once
+    /// they received all of its events.
+    pub(crate) fn finish(&self, progress: DownloadProgress) {
+        self.downloads
+            .remove_if(&progress.firmware_id, |_, sender| {
+                sender.same_channel(&progress.sender)
+            });
+    }
+
+    /// Subscribe to the events of the download of `firmware_id`, if one is in progress
+    pub fn subscribe(
+        &self,
+        firmware_id: &str,
+    ) -> Option<broadcast::Receiver<RackFirmwareDownloadEvent>> {
+        self.downloads
+            .get(firmware_id)
+            .map(|sender| sender.subscribe())
+    }
+}
+
+/// Publishes the events of one firmware download to its watchers
+#[derive(Clone, Debug)]
+pub(crate) struct DownloadProgress {
+    firmware_id: String,
+    sender: broadcast::Sender<RackFirmwareDownloadEvent>,
+    /// Download attempt the published file events belong to
+    attempt: u32,
+}
+
+impl DownloadProgress {
+    /// Publishes the file events of download attempt `attempt`
+    fn for_attempt(&self, attempt: u32) -> Self {
+        Self {
+            attempt,
+            ..self.clone()
+        }
+    }
+
+    fn file_started(&self, filename: &str, total_bytes: Option<u64>) {
+        self.send(
+            RackFirmwareDownloadEventType::DownloadFileStarted,
+            filename,
+            0,
+            total_bytes,
+            format!("Downloading {filename}"),
+        );
+    }
+
+    fn file_progress(&self, filename: &str, bytes_downloaded: u64, total_bytes: Option<u64>) {
+        self.send(
+            RackFirmwareDownloadEventType::DownloadFileProgress,
+            filename,
+            bytes_downloaded,
+            total_bytes,
+            format!("Received {bytes_downloaded} bytes of {filename}"),
+        );
+    }
+
+    fn file_completed(&self, filename: &str, bytes_downloaded: u64, message: String) {
+        self.send(
+            RackFirmwareDownloadEventType::DownloadFileCompleted,
+            filename,
+            bytes_downloaded,
+            Some(bytes_downloaded),
+            message,
+        );
+    }
+
+    fn file_failed(&self, filename: &str, error: &str) {
+        self.send(
+            RackFirmwareDownloadEventType::DownloadFileFailed,
+            filename,
+            0,
+            None,
+            format!("Failed to download {filename}: {error}"),
+        );
+    }
+
+    pub(crate) fn finished(&self, message: String) {
+        self.send(
+            RackFirmwareDownloadEventType::DownloadFinished,
+            "",
+            0,
+            None,
+            message,
+        );
+    }
+
+    fn send(
+        &self,
+        event_type: RackFirmwareDownloadEventType,
+        filename: &str,
+        bytes_downloaded: u64,
+        total_bytes: Option<u64>,
+        message: String,
+    ) {
+        // Nobody watching the download is not an error
+        let _ = self.sender.send(RackFirmwareDownloadEvent {
+            firmware_id: self.firmware_id.clone(),
+            timestamp: Some(chrono::Utc::now().into()),
+            event_type: event_type.into(),
+            filename: filename.to_string(),
+            bytes_downloaded,
+            total_bytes,
+            message,
+            attempt: self.attempt,
+        });
+    }
+}
+
 /// Spawn a background task to download firmware files and mark as available when complete
+#[allow(clippy::too_many_arguments)]
 fn spawn_firmware_download_task(
     firmware_id: String,
     parsed_components: ParsedFirmwareComponents,
     credential_reader: Arc<dyn CredentialReader>,
     database_connection: sqlx::PgPool,
+    downloads: RackFirmwareDownloadTracker,
+    firmware_cache_dir: PathBuf,
+    power_shelf_targets: HashMap<String, String>,
+    preflight: bool,
+    max_concurrent: usize,
 ) {
+    // Registered before the task runs, so that the download can be watched as
+    // soon as the config is created
+    let progress = downloads.start(&firmware_id);
     tokio::spawn(async move {
-        if let Err(e) = download_firmware_files(
+        match download_firmware_files(
             &firmware_id,
             &parsed_components,
             &*credential_reader,
             &database_connection,
+            &firmware_cache_dir,
+            &power_shelf_targets,
+            preflight,
+            max_concurrent,
+            &progress,
         )
         .await
         {
-            tracing::error!(
-                firmware_id = %firmware_id,
-                error = %e,
-                "Failed to download firmware files"
-            );
+            Ok(reports) => {
+                let summary: Vec<String> = reports.iter().map(ToString::to_string).collect();
+                tracing::info!(
+                    firmware_id = %firmware_id,
+                    summary = %summary.join("; "),
+                    "Firmware download attempts"
+                );
+            }
+            Err(e) => {
+                tracing::error!(
+                    firmware_id = %firmware_id,
+                    error = %e,
+                    "Failed to download firmware files"
+                );
+                progress.finished(format!("Failed to download firmware files: {e}"));
+            }
         }
+        downloads.finish(progress);
     });
 }
 
-/// Download all firmware files for a rack firmware configuration
-async fn download_firmware_files(
+/// Download all firmware files for a rack firmware configuration, at most `max_concurrent` at a
+/// time, and report how each of them went. With `preflight`, the URLs of all files are checked
+/// with HEAD requests first, as many at a time, and nothing is downloaded if any is unreachable.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn download_firmware_files(
     firmware_id: &str,
     parsed_components: &ParsedFirmwareComponents,
     credential_reader: &dyn CredentialReader,
     database_connection: &sqlx::PgPool,
-) -> Result<(), String> {
+    firmware_cache_dir: &Path,
+    power_shelf_targets: &HashMap<String, String>,
+    preflight: bool,
+    max_concurrent: usize,
+    progress: &DownloadProgress,
+) -> Result<Vec<FileDownloadReport>, String> {
     // Retrieve token from Vault
     let credentials = credential_reader
         .get_credentials(&CredentialKey::RackFirmware {
@@ -471,73 +1127,156 @@ async fn download_firmware_files(
     );
 
     // Create firmware cache directory if it doesn't exist
-    let firmware_cache_dir = PathBuf::from("/forge-boot-artifacts/blobs/internal/fw")
-        .join("rack_firmware")
-        .join(firmware_id);
-    tokio::fs::create_dir_all(&firmware_cache_dir)
+    tokio::fs::create_dir_all(firmware_cache_dir)
         .await
         .map_err(|e| format!("Failed to create cache directory: {}", e))?;
+    remove_stale_part_files(firmware_cache_dir).await;
 
-    // Collect all download tasks
-    let mut task_set = JoinSet::new();
-    let mut total_locations = 0;
+    // A file shared by several components or board SKUs is downloaded once, since concurrent
+    // downloads of it would race on its `.part` file.
+    let files = download_files(parsed_components);
+    let total_locations = files.len();
+    let download_slots = Arc::new(tokio::sync::Semaphore::new(max_concurrent.max(1)));
 
-    for board_sku in &parsed_components.board_skus {
-        for firmware_component in &board_sku.firmware_components {
-            for location in &firmware_component.locations {
-                total_locations += 1;
-
-                let url = location.location.clone();
-                let location_type = location.location_type.clone();
-                let component = firmware_component.component.clone();
-                let bundle = firmware_component.bundle.clone();
-                let token = artifactory_token.clone();
-                let dest_dir = firmware_cache_dir.clone();
-
-                task_set.spawn(async move {
-                    download_single_file(url, location_type, component, bundle, token, dest_dir)
-                        .await
-                });
-            }
-        }
+    if preflight {
+        let urls: BTreeSet<&str> = files.iter().map(|file| file.url.as_str()).collect();
+        let sizes = preflight_firmware_urls(&urls, &artifactory_token, &download_slots).await?;
+        tracing::info!(
+            firmware_id = %firmware_id,
+            urls = urls.len(),
+            total_bytes = sizes.values().flatten().sum::<u64>(),
+            "All firmware URLs are reachable"
+        );
+    }
+
+    // Collect one download task per file, remembering the firmware types each one downloads
+    // for
+    let mut task_set = JoinSet::new();
+    let mut task_firmware_types = std::collections::HashMap::new();
+    let mut task_filenames = std::collections::HashMap::new();
+
+    for file in files {
+        let location = FirmwareLocation {
+            location: file.url,
+            location_type: file.location_type,
+            firmware_type: None,
+            sha256: file.sha256,
+            size: file.size,
+        };
+        let component = file.components.join(", ");
+        let token = artifactory_token.clone();
+        let dest_dir = firmware_cache_dir.to_path_buf();
+        let progress = progress.clone();
+        let download_slots = download_slots.clone();
+
+        let task = task_set.spawn(async move {
+            // Too many simultaneous downloads get rate limited by Artifactory
+            let _slot = match download_slots.clone().try_acquire_owned() {
+                Ok(slot) => slot,
+                Err(_) => {
+                    tracing::info!(
+                        component = %component,
+                        url = %location.location,
+                        "Waiting for a free download slot"
+                    );
+                    match download_slots.acquire_owned().await {
+                        Ok(slot) => slot,
+                        Err(e) => {
+                            return (0, Err(format!("Failed to wait for a download slot: {e}")));
+                        }
+                    }
+                }
+            };
+            download_file_with_retries(location, component, token, dest_dir, &progress).await
+        });
+        task_firmware_types.insert(task.id(), file.firmware_types);
+        task_filenames.insert(task.id(), file.filename);
     }
 
     tracing::info!(
         firmware_id = %firmware_id,
         total_locations = total_locations,
-        "Spawned download tasks for all firmware locations"
+        "Spawned download tasks for all firmware files"
     );
 
     // Wait for all downloads to complete
     let mut successful_downloads = 0;
     let mut failed_downloads = 0;
-
-    while let Some(result) = task_set.join_next().await {
-        match result {
-            Ok(Ok(_)) => successful_downloads += 1,
-            Ok(Err(e)) => {
-                tracing::warn!(error = %e, "Firmware download failed");
-                failed_downloads += 1;
+    let mut failed_firmware_types = std::collections::HashSet::new();
+    let mut checksums = BTreeMap::new();
+    let mut reports = Vec::new();
+
+    while let Some(result) = task_set.join_next_with_id().await {
+        let task_id = match result {
+            Ok((task_id, (attempts, Ok(checksum)))) => {
+                successful_downloads += 1;
+                if let Some(filename) = task_filenames.get(&task_id) {
+                    checksums.insert(filename.clone(), checksum);
+                    reports.push(FileDownloadReport {
+                        filename: filename.clone(),
+                        attempts,
+                        error: None,
+                    });
+                }
+                continue;
+            }
+            Ok((task_id, (attempts, Err(e)))) => {
+                tracing::warn!(error = %e, attempts, "Firmware download failed");
+                if let Some(filename) = task_filenames.get(&task_id) {
+                    progress.for_attempt(attempts).file_failed(filename, &e);
+                    reports.push(FileDownloadReport {
+                        filename: filename.clone(),
+                        attempts,
+                        error: Some(e),
+                    });
+                }
+                task_id
             }
             Err(join_error) => {
                 tracing::error!(error = %join_error, "Download task panicked");
-                failed_downloads += 1;
+                if let Some(filename) = task_filenames.get(&join_error.id()) {
+                    progress.file_failed(filename, &join_error.to_string());
+                    reports.push(FileDownloadReport {
+                        filename: filename.clone(),
+                        attempts: 0,
+                        error: Some(join_error.to_string()),
+                    });
+                }
+                join_error.id()
             }
+        };
+        failed_downloads += 1;
+        if let Some(firmware_types) = task_firmware_types.get(&task_id) {
+            failed_firmware_types.extend(firmware_types.iter().cloned());
         }
     }
 
+    // A firmware type is available once all of its files are downloaded
+    let mut available_types: Vec<String> = task_firmware_types
+        .into_values()
+        .flatten()
+        .filter(|firmware_type| !failed_firmware_types.contains(firmware_type))
+        .collect();
+    available_types.sort();
+    available_types.dedup();
+
     tracing::info!(
         firmware_id = %firmware_id,
         successful = successful_downloads,
         failed = failed_downloads,
         total = total_locations,
+        available_types = ?available_types,
         "Firmware download completed"
     );
 
-    // Mark firmware as available if all downloads succeeded
-    if failed_downloads == 0 {
+    // Mark firmware as available if all downloads succeeded, or as available for the firmware
+    // types whose downloads all succeeded
+    if failed_downloads == 0 || !available_types.is_empty() {
+        // Let the provisioning server look up the downloaded files by hash
+        write_checksum_index(firmware_cache_dir, &checksums).await?;
+
         // Build firmware lookup table
-        let lookup_table = build_firmware_lookup_table(parsed_components);
+        let lookup_table = build_firmware_lookup_table(parsed_components, power_shelf_targets);
         let lookup_json = serde_json::to_value(&lookup_table)
             .map_err(|e| format!("Failed to serialize lookup table: {}", e))?;
 
@@ -553,10 +1292,12 @@ async fn download_firmware_files(
             .map_err(|e| format!("Failed to begin transaction: {}", e))?;
 
         // Update parsed_components with the lookup table
-        let query = "UPDATE rack_firmware SET parsed_components = $2::jsonb, available = true, updated = NOW() WHERE id = $1";
+        let query = "UPDATE rack_firmware SET parsed_components = $2::jsonb, available = $3, available_types = $4, updated = NOW() WHERE id = $1";
         sqlx::query(query)
             .bind(firmware_id)
             .bind(sqlx::types::Json(lookup_json))
+            .bind(failed_downloads == 0)
+            .bind(&available_types)
             .execute(&mut *txn)
             .await
             .map_err(|e| format!("Failed to update firmware lookup table: {}", e))?;
@@ -567,6 +1308,7 @@ async fn download_firmware_files(
 
         tracing::info!(
             firmware_id = %firmware_id,
+            available_types = ?available_types,
             "Marked rack firmware as available with lookup table"
         );
     } else {
@@ -577,7 +1319,12 @@ async fn download_firmware_files(
         );
     }
 
-    Ok(())
+    progress.finished(format!(
+        "Downloaded {successful_downloads} of {total_locations} firmware files, {failed_downloads} failed"
+    ));
+
+    reports.sort_by(|a, b| a.filename.cmp(&b.filename));
+    Ok(reports)
 }
 
 /// Known device types based on BoardSKU SKUID patterns
@@ -588,8 +1335,6 @@ enum DeviceType {
     GB200ComputeTray,
     /// Juliet Switch (P4978) - needs switch firmware
     JulietSwitch,
-    /// Power Shelf - firmware is included in GB200ComputeTray BoardSKU
-    PowerShelf,
     /// Unknown device type
     Unknown,
 }
@@ -647,18 +1392,15 @@ fn get_firmware_components_for_device_type(
             // ("CPLD", "CPLD", "cpld"),
             ("SBIOS+EROT", "BIOS", "bios"),
         ],
-        DeviceType::PowerShelf => vec![
-            // Power Shelf firmware - found in GB200ComputeTray BoardSKU
-            // TODO: Confirm correct targets for Power Shelf components
-            ("Power Shelf FW", "PowerShelfFW", "TODO_POWERSHELF_TARGET"),
-        ],
         DeviceType::Unknown => vec![],
     }
 }
 
-/// Build a lookup table mapping device types and components to downloaded firmware files
-fn build_firmware_lookup_table(
+/// Build a lookup table mapping device types and components to downloaded firmware files.
+/// `power_shelf_targets` maps Power Shelf firmware subcomponents to their RMS update targets.
+pub(crate) fn build_firmware_lookup_table(
     parsed_components: &ParsedFirmwareComponents,
+    power_shelf_targets: &HashMap<String, String>,
 ) -> FirmwareLookupTable {
     let mut lookup = FirmwareLookupTable {
         devices: std::collections::HashMap::new(),
@@ -680,13 +1422,6 @@ fn build_firmware_lookup_table(
         // Get the firmware components we need to extract for this device type
         let components_to_extract = get_firmware_components_for_device_type(&device_type);
 
-        // For GB200ComputeTray, also extract Power Shelf firmware
-        let power_shelf_components = if device_type == DeviceType::GB200ComputeTray {
-            get_firmware_components_for_device_type(&DeviceType::PowerShelf)
-        } else {
-            vec![]
-        };
-
         let mut device_components = std::collections::HashMap::new();
         let mut power_shelf_device_components = std::collections::HashMap::new();
 
@@ -695,11 +1430,7 @@ fn build_firmware_lookup_table(
             let bundle = firmware_component.bundle.clone().unwrap_or_default();
 
             // Get firmware type (Prod/Dev), normalize to lowercase
-            let fw_type = firmware_component
-                .component_type
-                .as_ref()
-                .map(|t| t.to_lowercase())
-                .unwrap_or_else(|| "prod".to_string()); // Default to prod if not specified
+            let fw_type = firmware_component.firmware_type();
 
             // Check if this component is one we need to extract for the main device type
             for (match_name, lookup_key, target) in &components_to_extract {
@@ -737,32 +1468,14 @@ fn build_firmware_lookup_table(
                 }
             }
 
-            // Check if this component is Power Shelf firmware (embedded in GB200ComputeTray)
-            for (match_name, lookup_key, target) in &power_shelf_components {
-                if component_name == *match_name {
-                    // Power Shelf FW has subcomponents with firmware locations
-                    // For now, just record that we have Power Shelf firmware
-                    // TODO: Extract individual subcomponent firmware files
-                    let typed_key = format!("{}_{}", lookup_key, fw_type);
-                    power_shelf_device_components.insert(
-                        typed_key,
-                        FirmwareLookupEntry {
-                            filename: "".to_string(), // Subcomponents have individual files
-                            target: target.to_string(),
-                            component: component_name.clone(),
-                            bundle: bundle.clone(),
-                            firmware_type: fw_type.clone(),
-                            version: firmware_component.version.clone(),
-                            subcomponents: firmware_component.subcomponents.clone(),
-                        },
-                    );
-                    tracing::debug!(
-                        component = %component_name,
-                        target = %target,
-                        "Added Power Shelf firmware component to lookup table"
-                    );
-                    break;
-                }
+            // Power Shelf firmware is embedded in the GB200ComputeTray BoardSKU
+            if device_type == DeviceType::GB200ComputeTray
+                && component_name == POWER_SHELF_FIRMWARE_COMPONENT
+            {
+                power_shelf_device_components.extend(power_shelf_firmware_entries(
+                    firmware_component,
+                    power_shelf_targets,
+                ));
             }
         }
 
@@ -771,7 +1484,6 @@ fn build_firmware_lookup_table(
             let device_key = match device_type {
                 DeviceType::GB200ComputeTray => "Compute Node",
                 DeviceType::JulietSwitch => "Switch Tray",
-                DeviceType::PowerShelf => "Power Shelf",
                 DeviceType::Unknown => continue,
             };
             lookup
@@ -790,126 +1502,605 @@ fn build_firmware_lookup_table(
     lookup
 }
 
-/// Download a single firmware file
-async fn download_single_file(
-    url: String,
-    location_type: String,
+/// Lookup table entries for the individual subcomponents of a Power Shelf firmware component.
+///
+/// A subcomponent is flashed from the firmware file its `Filename` names, which config
+/// validation requires. Subcomponents without a target in `targets`, or whose file is not one
+/// of the component's firmware files, are skipped.
+fn power_shelf_firmware_entries(
+    firmware_component: &FirmwareComponent,
+    targets: &HashMap<String, String>,
+) -> HashMap<String, FirmwareLookupEntry> {
+    let fw_type = firmware_component.firmware_type();
+    let filenames: Vec<&str> = firmware_component
+        .locations
+        .iter()
+        .filter(|location| location.firmware_type.as_deref() == Some("Firmware"))
+        .map(|location| firmware_filename(&location.location))
+        .collect();
+
+    let mut entries = HashMap::new();
+    for subcomponent in &firmware_component.subcomponents {
+        let Some(target) = targets.get(&subcomponent.component) else {
+            tracing::warn!(
+                subcomponent = %subcomponent.component,
+                "No target configured for Power Shelf firmware subcomponent, skipping"
+            );
+            continue;
+        };
+
+        let Some(filename) = subcomponent
+            .filename
+            .as_deref()
+            .filter(|filename| filenames.contains(filename))
+        else {
+            tracing::warn!(
+                subcomponent = %subcomponent.component,
+                filename = ?subcomponent.filename,
+                "No firmware file for Power Shelf firmware subcomponent, skipping"
+            );
+            continue;
+        };
+
+        tracing::debug!(
+            subcomponent = %subcomponent.component,
+            firmware_type = %fw_type,
+            filename = %filename,
+            target = %target,
+            "Added Power Shelf firmware subcomponent to lookup table"
+        );
+        entries.insert(
+            format!("{}_{}", subcomponent.component, fw_type),
+            FirmwareLookupEntry {
+                filename: filename.to_string(),
+                target: target.clone(),
+                component: subcomponent.component.clone(),
+                bundle: firmware_component.bundle.clone().unwrap_or_default(),
+                firmware_type: fw_type.clone(),
+                version: Some(subcomponent.version.clone()),
+                subcomponents: vec![subcomponent.clone()],
+            },
+        );
+    }
+
+    entries
+}
+
+/// Number of times downloading a firmware file is attempted before giving up
+const DOWNLOAD_ATTEMPTS: u32 = 3;
+
+/// Delay between two attempts to download a firmware file
+const DOWNLOAD_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Longest delay before the next download attempt that a rate limited response can ask for
+const MAX_DOWNLOAD_RETRY_AFTER: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// How downloading one firmware file went
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FileDownloadReport {
+    pub(crate) filename: String,
+    /// Number of download attempts made, 0 if the download never started
+    pub(crate) attempts: u32,
+    /// Error of the last attempt, if the download failed
+    pub(crate) error: Option<String>,
+}
+
+impl std::fmt::Display for FileDownloadReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attempts = match self.attempts {
+            1 => "1 attempt".to_string(),
+            n => format!("{n} attempts"),
+        };
+        match &self.error {
+            None => write!(f, "{}: succeeded after {attempts}", self.filename),
+            Some(e) => write!(f, "{}: failed after {attempts}: {e}", self.filename),
+        }
+    }
+}
+
+/// Why an attempt to download a firmware file failed
+#[derive(Debug)]
+pub(crate) struct DownloadError {
+    pub(crate) message: String,
+    /// Whether another attempt might succeed. Transport errors, timeouts, rate limiting and
+    /// server errors are retried, while e.g. a missing file or a checksum mismatch would fail
+    /// the same way again.
+    pub(crate) retryable: bool,
+    /// How long the server asked to wait before the next attempt
+    pub(crate) retry_after: Option<std::time::Duration>,
+}
+
+impl DownloadError {
+    fn retryable(message: String) -> Self {
+        Self {
+            message,
+            retryable: true,
+            retry_after: None,
+        }
+    }
+
+    fn permanent(message: String) -> Self {
+        Self {
+            message,
+            retryable: false,
+            retry_after: None,
+        }
+    }
+
+    /// The server rate limited the download, and possibly said when to try again with a
+    /// `Retry-After` header
+    fn rate_limited(message: String, response: &reqwest::Response) -> Self {
+        // Only the delay in seconds form of the header is supported, not the HTTP date one
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+            .map(|seconds| std::time::Duration::from_secs(seconds).min(MAX_DOWNLOAD_RETRY_AFTER));
+        Self {
+            message,
+            retryable: true,
+            retry_after,
+        }
+    }
+}
+
+impl std::fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Download a firmware file, retrying attempts that failed with a retryable error up to
+/// `DOWNLOAD_ATTEMPTS` times in total. Returns the number of attempts made along with the
+/// outcome of the last one.
+async fn download_file_with_retries(
+    location: FirmwareLocation,
     component: String,
-    bundle: Option<String>,
     token: String,
     dest_dir: PathBuf,
-) -> Result<(), String> {
-    // Extract filename from URL
-    let filename = url
-        .split('/')
-        .next_back()
-        .ok_or_else(|| format!("Invalid URL: {}", url))?;
+    progress: &DownloadProgress,
+) -> (u32, Result<CachedFileChecksum, String>) {
+    let mut attempt = 1;
+    loop {
+        let result = download_single_file(
+            location.clone(),
+            component.clone(),
+            token.clone(),
+            dest_dir.clone(),
+            &progress.for_attempt(attempt),
+        )
+        .await;
+        match result {
+            Err(e) if e.retryable && attempt < DOWNLOAD_ATTEMPTS => {
+                let delay = e.retry_after.unwrap_or(DOWNLOAD_RETRY_DELAY);
+                tracing::warn!(
+                    component = %component,
+                    url = %location.location,
+                    attempt,
+                    error = %e,
+                    delay = ?delay,
+                    "Firmware download attempt failed, retrying"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            result => return (attempt, result.map_err(|e| e.message)),
+        }
+    }
+}
+
+/// Extension of files that are still being downloaded
+const PART_FILE_EXTENSION: &str = "part";
+
+/// Response header carrying the SHA-256 of an artifact served by Artifactory
+pub(crate) const ARTIFACTORY_SHA256_HEADER: &str = "X-Checksum-Sha256";
+
+/// Download a single firmware file, returning the checksum of the cached file
+pub(crate) async fn download_single_file(
+    location: FirmwareLocation,
+    component: String,
+    token: String,
+    dest_dir: PathBuf,
+    progress: &DownloadProgress,
+) -> Result<CachedFileChecksum, DownloadError> {
+    let url = location.location.as_str();
+    let location_type = location.location_type.as_str();
+    let filename = firmware_filename(url);
 
     let dest_path = dest_dir.join(filename);
 
-    // Skip if file already exists
+    // Skip if file already exists and matches what the config expects. A file left behind
+    // half-written, e.g. by a crash, fails verification and is downloaded again.
     if dest_path.exists() {
-        tracing::debug!(
-            component = %component,
-            filename = %filename,
-            "File already cached, skipping download"
-        );
-        return Ok(());
+        match verify_cached_file(&dest_path, &location).await {
+            Ok(checksum) => {
+                tracing::debug!(
+                    component = %component,
+                    filename = %filename,
+                    "File already cached, skipping download"
+                );
+                progress.file_completed(
+                    filename,
+                    checksum.size,
+                    format!("{filename} is already cached"),
+                );
+                return Ok(checksum);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    component = %component,
+                    filename = %filename,
+                    error = %e,
+                    "Cached firmware file failed verification, downloading again"
+                );
+            }
+        }
     }
 
     tracing::info!(
         component = %component,
-        bundle = ?bundle,
         url = %url,
         location_type = %location_type,
         "Downloading firmware file"
     );
 
-    // Build HTTP client
-    let client = reqwest::Client::builder()
+    let client = firmware_http_client().map_err(DownloadError::permanent)?;
+    let response = send_with_token_retry(&client, reqwest::Method::GET, url, &token)
+        .await
+        .map_err(DownloadError::retryable)?;
+
+    // Check if response is successful. Only server errors and rate limiting may go away on
+    // their own.
+    if !response.status().is_success() {
+        let message = format!("Download failed with status {}: {}", response.status(), url);
+        return Err(
+            if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+                DownloadError::rate_limited(message, &response)
+            } else if response.status().is_server_error() {
+                DownloadError::retryable(message)
+            } else {
+                DownloadError::permanent(message)
+            },
+        );
+    }
+
+    // Artifactory reports the checksum of the artifact it serves
+    let expected_sha256 = response
+        .headers()
+        .get(ARTIFACTORY_SHA256_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    // Download file content, reporting progress as it arrives
+    let total_bytes = response.content_length();
+    progress.file_started(filename, total_bytes);
+    let mut body = response.bytes_stream();
+    let mut bytes = Vec::new();
+    let mut hasher = Sha256::new();
+    let mut reported_bytes = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|e| {
+            DownloadError::retryable(format!("Failed to read response body: {}", e))
+        })?;
+        hasher.update(&chunk);
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() - reported_bytes >= DOWNLOAD_PROGRESS_INTERVAL_BYTES {
+            reported_bytes = bytes.len();
+            progress.file_progress(filename, reported_bytes as u64, total_bytes);
+        }
+    }
+
+    let sha256 = hex::encode(hasher.finalize());
+    if let Some(expected_sha256) = expected_sha256
+        && !sha256.eq_ignore_ascii_case(&expected_sha256)
+    {
+        return Err(DownloadError::permanent(format!(
+            "Downloaded {} has SHA-256 {}, but the server reported {}",
+            url, sha256, expected_sha256
+        )));
+    }
+
+    // Write to a temporary file first, so that an interrupted write never
+    // leaves a partial file under the final name
+    let part_path = part_file_path(&dest_dir, filename);
+    let size = bytes.len() as u64;
+    tokio::fs::write(&part_path, bytes).await.map_err(|e| {
+        DownloadError::permanent(format!(
+            "Failed to write file {}: {}",
+            part_path.display(),
+            e
+        ))
+    })?;
+    tokio::fs::rename(&part_path, &dest_path)
+        .await
+        .map_err(|e| {
+            DownloadError::permanent(format!(
+                "Failed to rename {} to {}: {}",
+                part_path.display(),
+                dest_path.display(),
+                e
+            ))
+        })?;
+
+    tracing::info!(
+        component = %component,
+        filename = %filename,
+        path = %dest_path.display(),
+        "Successfully downloaded firmware file"
+    );
+    progress.file_completed(filename, size, format!("Downloaded {filename}"));
+
+    Ok(CachedFileChecksum { sha256, size })
+}
+
+/// HTTP client firmware files are downloaded with
+fn firmware_http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
         .danger_accept_invalid_certs(true)
         .connect_timeout(std::time::Duration::from_secs(30))
         .timeout(std::time::Duration::from_secs(600)) // 10 minutes for large files
         .build()
-        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))
+}
 
-    // Try downloading without token first
-    let response = match client.get(&url).send().await {
-        Ok(resp) if resp.status().is_success() => resp,
-        Ok(resp) if resp.status() == reqwest::StatusCode::UNAUTHORIZED => {
+/// Send a `method` request for `url`, first without and then with the Artifactory `token` if
+/// the server requires authentication or the request fails
+async fn send_with_token_retry(
+    client: &reqwest::Client,
+    method: reqwest::Method,
+    url: &str,
+    token: &str,
+) -> Result<reqwest::Response, String> {
+    match client.request(method.clone(), url).send().await {
+        Ok(resp) if resp.status() != reqwest::StatusCode::UNAUTHORIZED => return Ok(resp),
+        Ok(_) => {
             tracing::debug!(
                 url = %url,
                 "Authentication required, retrying with token"
             );
-
-            // Retry with token
-            client
-                .get(&url)
-                .header("X-JFrog-Art-Api", &token)
-                .send()
-                .await
-                .map_err(|e| format!("Failed to download with token: {}", e))?
-        }
-        Ok(resp) => {
-            return Err(format!(
-                "Download failed with status {}: {}",
-                resp.status(),
-                url
-            ));
         }
         Err(e) => {
             tracing::debug!(
                 url = %url,
                 error = %e,
-                "Download without token failed, retrying with token"
+                "Request without token failed, retrying with token"
             );
+        }
+    }
 
-            // Try with token on any error
-            client
-                .get(&url)
-                .header("X-JFrog-Art-Api", &token)
-                .send()
-                .await
-                .map_err(|e| format!("Failed to download with token: {}", e))?
+    client
+        .request(method.clone(), url)
+        .header("X-JFrog-Art-Api", token)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send {} request with token: {}", method, e))
+}
+
+/// Check that all `urls` can be downloaded with HEAD requests, sending one request per slot of
+/// `download_slots` at a time. Returns the size each URL reports, or the list of unreachable URLs.
+async fn preflight_firmware_urls(
+    urls: &BTreeSet<&str>,
+    token: &str,
+    download_slots: &tokio::sync::Semaphore,
+) -> Result<std::collections::BTreeMap<String, Option<u64>>, String> {
+    let client = firmware_http_client()?;
+    let responses = futures::future::join_all(urls.iter().map(|url| async {
+        let _slot = download_slots
+            .acquire()
+            .await
+            .map_err(|e| format!("Failed to wait for a download slot: {e}"))?;
+        send_with_token_retry(&client, reqwest::Method::HEAD, url, token).await
+    }))
+    .await;
+
+    let mut sizes = std::collections::BTreeMap::new();
+    let mut unreachable = Vec::new();
+    for (url, response) in urls.iter().zip(responses) {
+        match response {
+            Ok(resp) if resp.status().is_success() => {
+                // Read the header, as the body of a HEAD response is always empty
+                let size = resp
+                    .headers()
+                    .get(reqwest::header::CONTENT_LENGTH)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok());
+                tracing::debug!(url = %url, size = ?size, "Firmware URL is reachable");
+                sizes.insert(url.to_string(), size);
+            }
+            Ok(resp) => unreachable.push(format!("{url} ({})", resp.status())),
+            Err(e) => unreachable.push(format!("{url} ({e})")),
         }
-    };
+    }
 
-    // Check if response is successful
-    if !response.status().is_success() {
+    if !unreachable.is_empty() {
         return Err(format!(
-            "Download failed with status {}: {}",
-            response.status(),
-            url
+            "Unreachable firmware URLs: {}",
+            unreachable.join(", ")
         ));
     }
+    Ok(sizes)
+}
+
+/// Name of the file in a firmware cache directory that maps each downloaded file to its
+/// checksum
+pub(crate) const CHECKSUM_INDEX_FILENAME: &str = "index.json";
+
+/// Entry of the checksum index of a firmware cache directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CachedFileChecksum {
+    pub(crate) sha256: String,
+    pub(crate) size: u64,
+}
+
+/// Write the checksum index of `dir`, mapping the name of each cached file to its SHA-256 and
+/// size as computed while it was downloaded or verified
+async fn write_checksum_index(
+    dir: &Path,
+    index: &BTreeMap<String, CachedFileChecksum>,
+) -> Result<(), String> {
+    let json = serde_json::to_vec_pretty(index)
+        .map_err(|e| format!("Failed to serialize checksum index: {}", e))?;
+    let part_path = part_file_path(dir, CHECKSUM_INDEX_FILENAME);
+    let index_path = dir.join(CHECKSUM_INDEX_FILENAME);
+    tokio::fs::write(&part_path, json)
+        .await
+        .map_err(|e| format!("Failed to write file {}: {}", part_path.display(), e))?;
+    tokio::fs::rename(&part_path, &index_path)
+        .await
+        .map_err(|e| {
+            format!(
+                "Failed to rename {} to {}: {}",
+                part_path.display(),
+                index_path.display(),
+                e
+            )
+        })
+}
+
+/// Name of the cached file a firmware file is downloaded from `url` to
+fn firmware_filename(url: &str) -> &str {
+    url.rsplit('/').next().unwrap_or(url)
+}
+
+/// Path a download of `filename` is written to before it is renamed into place
+pub(crate) fn part_file_path(dest_dir: &Path, filename: &str) -> PathBuf {
+    dest_dir.join(format!("{filename}.{PART_FILE_EXTENSION}"))
+}
+
+/// Remove `.part` files left behind by downloads that were interrupted, e.g. by a restart.
+/// Failures are only logged, since the next download of the file overwrites it anyway.
+pub(crate) async fn remove_stale_part_files(dir: &Path) {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::warn!(dir = %dir.display(), error = %e, "Failed to list firmware cache directory");
+            return;
+        }
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path
+            .extension()
+            .is_some_and(|ext| ext == PART_FILE_EXTENSION)
+        {
+            tracing::info!(path = %path.display(), "Removing partially downloaded firmware file");
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to remove partially downloaded firmware file");
+            }
+        }
+    }
+}
 
-    // Download file content
-    let bytes = response
-        .bytes()
+/// Check a previously downloaded file against the size and SHA-256 the config expects for it,
+/// returning its checksum. Files without an expected size or checksum are accepted as is.
+async fn verify_cached_file(
+    path: &Path,
+    location: &FirmwareLocation,
+) -> Result<CachedFileChecksum, String> {
+    let size = tokio::fs::metadata(path)
         .await
-        .map_err(|e| format!("Failed to read response body: {}", e))?;
+        .map_err(|e| format!("Failed to read metadata of {}: {}", path.display(), e))?
+        .len();
+    if let Some(expected_size) = location.size
+        && size != expected_size
+    {
+        return Err(format!("expected {expected_size} bytes, found {size}"));
+    }
 
-    // Write to file
-    tokio::fs::write(&dest_path, bytes)
+    let sha256 = sha256_file(path)
         .await
-        .map_err(|e| format!("Failed to write file {}: {}", dest_path.display(), e))?;
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if let Some(expected_sha256) = &location.sha256
+        && !sha256.eq_ignore_ascii_case(expected_sha256)
+    {
+        return Err(format!(
+            "expected SHA-256 {expected_sha256}, found {sha256}"
+        ));
+    }
 
-    tracing::info!(
-        component = %component,
-        filename = %filename,
-        path = %dest_path.display(),
-        "Successfully downloaded firmware file"
-    );
+    Ok(CachedFileChecksum { sha256, size })
+}
 
-    Ok(())
+/// Size of the chunks files are read in while hashing them
+pub(crate) const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Hex encoded SHA-256 of the file at `path`, read in chunks so that large firmware images
+/// are never held in memory as a whole
+pub(crate) async fn sha256_file(path: &Path) -> std::io::Result<String> {
+    let file = tokio::fs::File::open(path).await?;
+    let mut reader = tokio::io::BufReader::with_capacity(HASH_CHUNK_SIZE, file);
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0; HASH_CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
 }
 
-/// Apply firmware to all devices in a rack
+/// A repeated apply of the same firmware to a rack within this window returns
+/// the jobs created by the earlier apply instead of starting new ones.
+const APPLY_REUSE_WINDOW: chrono::Duration = chrono::Duration::minutes(10);
+
+/// An apply which claimed a rack but did not record its jobs within this lease is assumed to
+/// have died while starting them, and the rack can be claimed again.
+const APPLY_CLAIM_LEASE: chrono::Duration = chrono::Duration::minutes(2);
+
+/// Files among `filenames` that are not present in `firmware_dir`, or are empty
+async fn find_missing_firmware_files(
+    firmware_dir: &Path,
+    filenames: impl Iterator<Item = &String>,
+) -> Vec<String> {
+    let mut missing = Vec::new();
+    for filename in filenames {
+        let present = tokio::fs::metadata(firmware_dir.join(filename))
+            .await
+            .is_ok_and(|metadata| metadata.is_file() && metadata.len() > 0);
+        if !present && !missing.contains(filename) {
+            missing.push(filename.clone());
+        }
+    }
+    missing
+}
+
+/// Apply firmware to all devices in a rack, or to only some of its nodes
 pub async fn apply(
     api: &Api,
     request: Request<RackFirmwareApplyRequest>,
 ) -> Result<Response<RackFirmwareApplyResponse>, Status> {
-    let req = request.into_inner();
+    apply_to_rack(&RackFirmwareApplier::new(api), request.into_inner())
+        .await
+        .map(Response::new)
+}
+
+/// What applying firmware to a rack needs from `Api`, so that rollouts can apply from a
+/// background task
+#[derive(Clone)]
+struct RackFirmwareApplier {
+    database_connection: sqlx::PgPool,
+    rms_client: Option<Arc<dyn librms::RmsApi>>,
+    firmware_cache_dir: PathBuf,
+}
+
+impl RackFirmwareApplier {
+    fn new(api: &Api) -> Self {
+        Self {
+            database_connection: api.database_connection.clone(),
+            rms_client: api.rms_client.clone(),
+            firmware_cache_dir: api.runtime_config.rack_firmware_directory.clone(),
+        }
+    }
+}
+
+async fn apply_to_rack(
+    applier: &RackFirmwareApplier,
+    req: RackFirmwareApplyRequest,
+) -> Result<RackFirmwareApplyResponse, Status> {
     let rack_id = req
         .rack_id
         .ok_or_else(|| Status::invalid_argument("rack_id is required"))?;
@@ -922,14 +2113,14 @@ pub async fn apply(
     );
 
     // Get the RackFirmware configuration from the database
-    let fw_config = DbRackFirmware::find_by_id(&api.database_connection, &req.firmware_id)
+    let fw_config = DbRackFirmware::find_by_id(&applier.database_connection, &req.firmware_id)
         .await
         .map_err(|e| Status::internal(format!("Failed to get firmware configuration: {}", e)))?;
 
-    if !fw_config.available {
+    if !fw_config.is_available_for(&req.firmware_type) {
         return Err(Status::failed_precondition(format!(
-            "Firmware configuration '{}' is not marked as available",
-            req.firmware_id
+            "Firmware configuration '{}' is not marked as available for {} firmware",
+            req.firmware_id, req.firmware_type
         )));
     }
 
@@ -942,7 +2133,7 @@ pub async fn apply(
             serde_json::json!({})
         });
 
-    let rack = db::rack::get(&api.database_connection, rack_id)
+    let rack = db::rack::get(&applier.database_connection, rack_id)
         .await
         .map_err(|e| Status::internal(format!("Failed to get rack: {}", e)))?;
 
@@ -960,6 +2151,12 @@ pub async fn apply(
         )));
     }
 
+    let targeted_nodes = if req.node_ids.is_empty() {
+        None
+    } else {
+        Some(group_rack_nodes(&rack_proto, rack_id, &req.node_ids)?)
+    };
+
     tracing::info!(
         rack_id = %rack_id,
         compute_trays = rack_proto.compute_trays.len(),
@@ -1001,34 +2198,93 @@ pub async fn apply(
         ),
     ];
 
+    // Resolve the firmware of all device types first, so that missing files are
+    // reported before any update job is started
+    let mut device_firmware = Vec::new();
     for &(lookup_key, node_type, display_name, has_devices, activate) in device_types {
+        let has_devices = match &targeted_nodes {
+            Some(nodes) => nodes.contains_key(lookup_key),
+            None => has_devices,
+        };
         if !has_devices {
             continue;
         }
 
-        let mut firmware_components =
-            find_firmware_components_for_device(&parsed_components, lookup_key, &req.firmware_type);
+        let firmware_components = order_firmware_components_for_flashing(
+            lookup_key,
+            find_firmware_components_for_device(&parsed_components, lookup_key, &req.firmware_type),
+        )
+        .map_err(Status::failed_precondition)?;
 
-        // Sort components into the required flashing order for this device type
-        let flash_order = get_firmware_flash_order(lookup_key);
-        firmware_components.sort_by_key(|(_, _, target)| {
-            flash_order
-                .iter()
-                .position(|&t| t == target.as_str())
-                .unwrap_or(usize::MAX)
-        });
+        device_firmware.push((
+            lookup_key,
+            node_type,
+            display_name,
+            activate,
+            firmware_components,
+        ));
+    }
 
+    let firmware_dir = applier.firmware_cache_dir.join(&req.firmware_id);
+    let missing_files = find_missing_firmware_files(
+        &firmware_dir,
+        device_firmware.iter().flat_map(|(_, _, _, _, components)| {
+            components.iter().map(|(_, filename, _)| filename)
+        }),
+    )
+    .await;
+    if !missing_files.is_empty() {
+        return Err(Status::failed_precondition(format!(
+            "Firmware configuration '{}' is missing downloaded files: {}",
+            req.firmware_id,
+            missing_files.join(", ")
+        )));
+    }
+
+    // Applies to specific nodes are one-off remediations, they are never reused
+    let claimed = req.node_ids.is_empty();
+    let previous_apply = if claimed {
+        claim_apply(&applier.database_connection, rack_id, &req)
+            .await
+            .map_err(CarbideError::from)?
+    } else {
+        None
+    };
+    if let Some(previous_apply) = previous_apply {
+        let Some(response) = previous_apply.response else {
+            return Err(Status::aborted(format!(
+                "Firmware configuration '{}' is already being applied to rack {} since {}",
+                req.firmware_id, rack_id, previous_apply.created
+            )));
+        };
+        tracing::info!(
+            rack_id = %rack_id,
+            firmware_id = %req.firmware_id,
+            applied_at = %previous_apply.created,
+            "Firmware was applied recently, returning the existing jobs"
+        );
+        return Ok(response.0);
+    }
+
+    for (lookup_key, node_type, display_name, activate, firmware_components) in device_firmware {
         if firmware_components.is_empty() {
+            let message = describe_missing_firmware(
+                &parsed_components,
+                lookup_key,
+                display_name,
+                &req.firmware_type,
+            );
             tracing::warn!(
                 rack_id = %rack_id,
                 device_type = %display_name,
+                message = %message,
                 "No matching firmware found in config"
             );
             device_results.push(DeviceUpdateResult {
                 device_id: rack_id.to_string(),
                 device_type: display_name.to_string(),
                 success: false,
-                message: format!("No matching firmware found in config for {}", display_name),
+                message,
                 job_id: String::new(),
                 node_jobs: vec![],
             });
@@ -1036,7 +2292,7 @@ pub async fn apply(
             continue;
         }
 
-        let Some(rms_client) = &api.rms_client else {
+        let Some(rms_client) = &applier.rms_client else {
             tracing::warn!(
                 rack_id = %rack_id,
                 device_type = %display_name,
@@ -1059,13 +2315,9 @@ pub async fn apply(
             firmware_components
                 .iter()
                 .map(|(_component_name, filename, target)| {
-                    let full_firmware_path = format!(
-                        "/forge-boot-artifacts/blobs/internal/fw/rack_firmware/{}/{}",
-                        req.firmware_id, filename
-                    );
                     librms::protos::rack_manager::FirmwareTarget {
                         target: target.clone(),
-                        filename: full_firmware_path,
+                        filename: firmware_dir.join(filename).display().to_string(),
                     }
                 })
                 .collect();
@@ -1074,10 +2326,39 @@ pub async fn apply(
             rack_id = %rack_id,
             device_type = %display_name,
             firmware_target_count = firmware_targets.len(),
+            firmware_file_count = firmware_targets
+                .iter()
+                .map(|t| &t.filename)
+                .collect::<std::collections::HashSet<_>>()
+                .len(),
             targets = ?firmware_targets.iter().map(|t| &t.target).collect::<Vec<_>>(),
             "Applying firmware via async batch API"
         );
 
+        if let Some(node_ids) = targeted_nodes
+            .as_ref()
+            .and_then(|nodes| nodes.get(lookup_key))
+        {
+            for node_id in node_ids {
+                let result = update_node_firmware(
+                    rms_client.as_ref(),
+                    rack_id,
+                    node_id,
+                    display_name,
+                    firmware_targets.clone(),
+                    activate,
+                )
+                .await;
+                if result.success {
+                    successful_updates += 1;
+                } else {
+                    failed_updates += 1;
+                }
+                device_results.push(result);
+            }
+            continue;
+        }
+
         let rms_request = librms::protos::rack_manager::UpdateFirmwareByNodeTypeRequest {
             metadata: None,
             node_type,
@@ -1161,12 +2442,615 @@ pub async fn apply(
         "Firmware apply operation completed"
     );
 
-    Ok(Response::new(RackFirmwareApplyResponse {
+    let response = RackFirmwareApplyResponse {
         total_updates: device_results.len() as i32,
         successful_updates,
         failed_updates,
         device_results,
-    }))
+    };
+
+    // Only remember applies that started jobs, so that a failed apply can be retried right away.
+    // Failing to record is not fatal: the jobs are already running.
+    if claimed
+        && let Err(e) = finish_apply(&applier.database_connection, rack_id, &req, &response).await
+    {
+        tracing::warn!(
+            rack_id = %rack_id,
+            firmware_id = %req.firmware_id,
+            error = %e,
+            "Failed to record firmware apply"
+        );
+    }
+
+    Ok(response)
+}
+
+/// How often the RMS jobs of the racks of a rollout wave are polled
+const ROLLOUT_JOB_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long a rollout waits for the RMS jobs of a rack, unless the request says otherwise
+const DEFAULT_ROLLOUT_JOB_TIMEOUT: std::time::Duration =
+    std::time::Duration::from_secs(2 * 60 * 60);
+
+/// How long finished rollouts can still be queried
+const ROLLOUT_RETENTION: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+/// Tracks the rollouts started by this API server, so that their progress can be queried
+#[derive(Clone, Debug, Default)]
+pub struct RackFirmwareRolloutTracker {
+    rollouts: Arc<DashMap<String, TrackedRollout>>,
+}
+
+#[derive(Debug)]
+struct TrackedRollout {
+    status: RackFirmwareBulkApplyResponse,
+    finished_at: Option<std::time::Instant>,
+}
+
+impl RackFirmwareRolloutTracker {
+    /// Register a new rollout, forgetting rollouts that finished more than `ROLLOUT_RETENTION` ago
+    fn start(&self, status: RackFirmwareBulkApplyResponse) {
+        self.rollouts.retain(|_, rollout| {
+            rollout
+                .finished_at
+                .is_none_or(|finished_at| finished_at.elapsed() < ROLLOUT_RETENTION)
+        });
+        self.rollouts.insert(
+            status.rollout_id.clone(),
+            TrackedRollout {
+                status,
+                finished_at: None,
+            },
+        );
+    }
+
+    /// Record the result of the rack at `index` in the rollout order
+    fn set_rack_result(&self, rollout_id: &str, index: usize, result: RackFirmwareRackApplyResult) {
+        if let Some(mut rollout) = self.rollouts.get_mut(rollout_id) {
+            rollout.status.rack_results[index] = result;
+        }
+    }
+
+    fn finish(&self, rollout_id: &str, halted: bool) {
+        if let Some(mut rollout) = self.rollouts.get_mut(rollout_id) {
+            rollout.status.halted = halted;
+            rollout.status.finished = true;
+            rollout.finished_at = Some(std::time::Instant::now());
+        }
+    }
+
+    /// Current progress of the rollout `rollout_id`, if it is known
+    pub fn get(&self, rollout_id: &str) -> Option<RackFirmwareBulkApplyResponse> {
+        self.rollouts
+            .get(rollout_id)
+            .map(|rollout| rollout.status.clone())
+    }
+}
+
+fn rack_apply_result(
+    rack_id: RackId,
+    status: RackFirmwareRackApplyStatus,
+    message: impl Into<String>,
+    apply_response: Option<RackFirmwareApplyResponse>,
+) -> RackFirmwareRackApplyResult {
+    RackFirmwareRackApplyResult {
+        rack_id: Some(rack_id),
+        status: status as i32,
+        message: message.into(),
+        apply_response,
+    }
+}
+
+/// Start applying firmware to many racks in a background rollout, and return its initial
+/// progress. Racks are applied to in waves of `max_concurrent_racks`, and the next wave only
+/// starts once the RMS jobs of the current one finished. The remaining racks are skipped once
+/// more than `max_failure_rate` of the racks applied to so far failed.
+pub async fn bulk_apply(
+    api: &Api,
+    request: Request<RackFirmwareBulkApplyRequest>,
+) -> Result<Response<RackFirmwareBulkApplyResponse>, Status> {
+    let req = request.into_inner();
+    if req.rack_ids.is_empty() {
+        return Err(Status::invalid_argument("rack_ids is required"));
+    }
+    if !(0.0..=1.0).contains(&req.max_failure_rate) {
+        return Err(Status::invalid_argument(format!(
+            "max_failure_rate must be between 0 and 1, got {}",
+            req.max_failure_rate
+        )));
+    }
+
+    let status = RackFirmwareBulkApplyResponse {
+        rollout_id: uuid::Uuid::new_v4().to_string(),
+        rack_results: req
+            .rack_ids
+            .iter()
+            .map(|rack_id| {
+                rack_apply_result(
+                    *rack_id,
+                    RackFirmwareRackApplyStatus::RackApplyPending,
+                    "Waiting for earlier racks",
+                    None,
+                )
+            })
+            .collect(),
+        halted: false,
+        finished: false,
+    };
+
+    tracing::info!(
+        rollout_id = %status.rollout_id,
+        firmware_id = %req.firmware_id,
+        firmware_type = %req.firmware_type,
+        racks = req.rack_ids.len(),
+        max_concurrent_racks = req.max_concurrent_racks,
+        max_failure_rate = req.max_failure_rate,
+        "Starting bulk firmware apply"
+    );
+
+    api.rack_firmware_rollouts.start(status.clone());
+    let applier = RackFirmwareApplier::new(api);
+    let rollouts = api.rack_firmware_rollouts.clone();
+    let rollout_id = status.rollout_id.clone();
+    tokio::spawn(async move { run_rollout(&applier, &rollouts, &rollout_id, req).await });
+
+    Ok(Response::new(status))
+}
+
+/// Get the progress of a rollout started by `bulk_apply`
+pub fn get_bulk_apply_status(
+    api: &Api,
+    request: Request<RackFirmwareBulkApplyStatusRequest>,
+) -> Result<Response<RackFirmwareBulkApplyResponse>, Status> {
+    let req = request.into_inner();
+    api.rack_firmware_rollouts
+        .get(&req.rollout_id)
+        .map(Response::new)
+        .ok_or_else(|| Status::not_found(format!("Unknown rollout '{}'", req.rollout_id)))
+}
+
+/// Apply firmware to the racks of a rollout wave by wave, recording each rack's result in
+/// `rollouts` as it finishes
+async fn run_rollout(
+    applier: &RackFirmwareApplier,
+    rollouts: &RackFirmwareRolloutTracker,
+    rollout_id: &str,
+    req: RackFirmwareBulkApplyRequest,
+) {
+    let wave_size = (req.max_concurrent_racks as usize).max(1);
+    let job_timeout = match req.job_timeout_seconds {
+        0 => DEFAULT_ROLLOUT_JOB_TIMEOUT,
+        seconds => std::time::Duration::from_secs(seconds.into()),
+    };
+
+    let mut applied_racks = 0;
+    let mut failed_racks = 0;
+    let mut halted = false;
+    for (wave_index, wave) in req.rack_ids.chunks(wave_size).enumerate() {
+        let first_index = wave_index * wave_size;
+        if halted {
+            for (offset, rack_id) in wave.iter().enumerate() {
+                rollouts.set_rack_result(
+                    rollout_id,
+                    first_index + offset,
+                    rack_apply_result(
+                        *rack_id,
+                        RackFirmwareRackApplyStatus::RackApplySkipped,
+                        "Rollout halted after too many racks failed",
+                        None,
+                    ),
+                );
+            }
+            continue;
+        }
+
+        let results =
+            futures::future::join_all(wave.iter().enumerate().map(|(offset, rack_id)| {
+                apply_rollout_rack(
+                    applier,
+                    rollouts,
+                    rollout_id,
+                    first_index + offset,
+                    *rack_id,
+                    &req,
+                    job_timeout,
+                )
+            }))
+            .await;
+
+        applied_racks += results.len();
+        failed_racks += results
+            .iter()
+            .filter(|status| **status == RackFirmwareRackApplyStatus::RackApplyFailed)
+            .count();
+        halted = failed_racks as f32 / applied_racks as f32 > req.max_failure_rate;
+    }
+    // Halting after the last wave skipped no rack
+    let halted = halted && applied_racks < req.rack_ids.len();
+
+    tracing::info!(
+        rollout_id = %rollout_id,
+        firmware_id = %req.firmware_id,
+        applied_racks,
+        failed_racks,
+        halted,
+        "Bulk firmware apply completed"
+    );
+    rollouts.finish(rollout_id, halted);
+}
+
+/// Apply firmware to one rack of a rollout and wait for the RMS jobs it started to finish.
+/// Returns the final status of the rack.
+async fn apply_rollout_rack(
+    applier: &RackFirmwareApplier,
+    rollouts: &RackFirmwareRolloutTracker,
+    rollout_id: &str,
+    index: usize,
+    rack_id: RackId,
+    req: &RackFirmwareBulkApplyRequest,
+    job_timeout: std::time::Duration,
+) -> RackFirmwareRackApplyStatus {
+    let result = apply_to_rack(
+        applier,
+        RackFirmwareApplyRequest {
+            rack_id: Some(rack_id),
+            firmware_id: req.firmware_id.clone(),
+            firmware_type: req.firmware_type.clone(),
+            node_ids: vec![],
+        },
+    )
+    .await;
+
+    let result = match result {
+        Ok(response) => {
+            let job_ids = started_job_ids(&response);
+            rollouts.set_rack_result(
+                rollout_id,
+                index,
+                rack_apply_result(
+                    rack_id,
+                    RackFirmwareRackApplyStatus::RackApplyInProgress,
+                    format!("Waiting for {} firmware jobs", job_ids.len()),
+                    Some(response.clone()),
+                ),
+            );
+
+            // Jobs that were started are waited for even if others failed to start, so that
+            // no more than a wave of racks is ever being flashed
+            let jobs_result = match &applier.rms_client {
+                Some(rms_client) if !job_ids.is_empty() => {
+                    wait_for_firmware_jobs(rms_client.as_ref(), &job_ids, job_timeout).await
+                }
+                _ => Ok(()),
+            };
+            match jobs_result {
+                Err(message) => rack_apply_result(
+                    rack_id,
+                    RackFirmwareRackApplyStatus::RackApplyFailed,
+                    message,
+                    Some(response),
+                ),
+                Ok(()) if response.failed_updates > 0 => rack_apply_result(
+                    rack_id,
+                    RackFirmwareRackApplyStatus::RackApplyFailed,
+                    format!(
+                        "{} of {} updates started",
+                        response.successful_updates, response.total_updates
+                    ),
+                    Some(response),
+                ),
+                Ok(()) => rack_apply_result(
+                    rack_id,
+                    RackFirmwareRackApplyStatus::RackApplySucceeded,
+                    format!("{} firmware jobs completed", job_ids.len()),
+                    Some(response),
+                ),
+            }
+        }
+        Err(status) => rack_apply_result(
+            rack_id,
+            RackFirmwareRackApplyStatus::RackApplyFailed,
+            status.message(),
+            None,
+        ),
+    };
+
+    if result.status() == RackFirmwareRackApplyStatus::RackApplyFailed {
+        tracing::warn!(
+            rollout_id = %rollout_id,
+            rack_id = %rack_id,
+            firmware_id = %req.firmware_id,
+            message = %result.message,
+            "Bulk firmware apply failed for rack"
+        );
+    }
+    let status = result.status();
+    rollouts.set_rack_result(rollout_id, index, result);
+    status
+}
+
+/// Ids of the RMS jobs an apply started: the per-node jobs of each update, or the update's own
+/// job if RMS did not report per-node jobs
+fn started_job_ids(response: &RackFirmwareApplyResponse) -> Vec<String> {
+    response
+        .device_results
+        .iter()
+        .filter(|result| result.success)
+        .flat_map(|result| {
+            if result.node_jobs.is_empty() {
+                vec![result.job_id.clone()]
+            } else {
+                result
+                    .node_jobs
+                    .iter()
+                    .map(|node_job| node_job.job_id.clone())
+                    .collect()
+            }
+        })
+        .filter(|job_id| !job_id.is_empty())
+        .collect()
+}
+
+/// Poll the RMS jobs `job_ids` until all of them finished, or `timeout` passed. Returns why
+/// the jobs failed if any of them failed or did not finish in time.
+async fn wait_for_firmware_jobs(
+    rms_client: &dyn librms::RmsApi,
+    job_ids: &[String],
+    timeout: std::time::Duration,
+) -> Result<(), String> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut pending = job_ids.to_vec();
+    let mut failures = Vec::new();
+    loop {
+        let mut running = Vec::new();
+        for job_id in pending {
+            let request = librms::protos::rack_manager::GetFirmwareJobStatusRequest {
+                metadata: None,
+                job_id: job_id.clone(),
+            };
+            match rms_client.get_firmware_job_status(request).await {
+                Ok(status) => match FirmwareJobState::try_from(status.job_state) {
+                    Ok(FirmwareJobState::Completed) => {}
+                    Ok(FirmwareJobState::Failed) => {
+                        failures.push(format!("job {job_id} failed: {}", status.error_message));
+                    }
+                    Ok(FirmwareJobState::Queued | FirmwareJobState::Running) => {
+                        running.push(job_id)
+                    }
+                    // A state this version doesn't know can't be told apart from a failure
+                    Err(_) => failures.push(format!(
+                        "job {job_id} is in unknown state {}",
+                        status.job_state
+                    )),
+                },
+                // The job keeps running on RMS, so it is polled again until the timeout
+                Err(e) => {
+                    tracing::warn!(job_id = %job_id, error = %e, "Failed to get firmware job status");
+                    running.push(job_id);
+                }
+            }
+        }
+        pending = running;
+
+        if pending.is_empty() {
+            break;
+        }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            failures.push(format!(
+                "jobs {} did not finish within {} seconds",
+                pending.join(", "),
+                timeout.as_secs()
+            ));
+            break;
+        }
+        tokio::time::sleep(ROLLOUT_JOB_POLL_INTERVAL.min(deadline - now)).await;
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("; "))
+    }
+}
+
+/// Groups `node_ids` by the lookup table key of their device type, checking that each of them is a
+/// node of `rack`
+fn group_rack_nodes(
+    rack: &rpc::forge::Rack,
+    rack_id: RackId,
+    node_ids: &[String],
+) -> Result<HashMap<&'static str, Vec<String>>, Status> {
+    let mut nodes: HashMap<&'static str, Vec<String>> = HashMap::new();
+    for node_id in node_ids {
+        let lookup_key = if rack
+            .compute_trays
+            .iter()
+            .any(|id| id.to_string() == *node_id)
+        {
+            "Compute Node"
+        } else if rack
+            .power_shelves
+            .iter()
+            .any(|id| id.to_string() == *node_id)
+        {
+            "Power Shelf"
+        } else if rack.expected_nvlink_switches.contains(node_id) {
+            "Switch Tray"
+        } else {
+            return Err(Status::invalid_argument(format!(
+                "Node '{}' does not belong to rack '{}'",
+                node_id, rack_id
+            )));
+        };
+
+        let type_nodes = nodes.entry(lookup_key).or_default();
+        if !type_nodes.contains(node_id) {
+            type_nodes.push(node_id.clone());
+        }
+    }
+    Ok(nodes)
+}
+
+/// Starts the firmware update of a single node of a rack, instead of all nodes of its type
+async fn update_node_firmware(
+    rms_client: &dyn librms::RmsApi,
+    rack_id: RackId,
+    node_id: &str,
+    display_name: &str,
+    firmware_targets: Vec<librms::protos::rack_manager::FirmwareTarget>,
+    activate: bool,
+) -> DeviceUpdateResult {
+    let rms_request = librms::protos::rack_manager::UpdateNodeFirmwareRequest {
+        metadata: None,
+        rack_id: rack_id.to_string(),
+        node_id: node_id.to_string(),
+        firmware_targets,
+        activate,
+        ..Default::default()
+    };
+
+    match rms_client.update_node_firmware_async(rms_request).await {
+        Ok(response) => {
+            let success =
+                response.status == librms::protos::rack_manager::ReturnCode::Success as i32;
+            tracing::info!(
+                rack_id = %rack_id,
+                device_type = %display_name,
+                node_id = %node_id,
+                job_id = %response.job_id,
+                "Firmware update job created"
+            );
+            DeviceUpdateResult {
+                device_id: node_id.to_string(),
+                device_type: display_name.to_string(),
+                success,
+                message: format!(
+                    "Async firmware update initiated for node {}: {}",
+                    node_id, response.message
+                ),
+                job_id: response.job_id.clone(),
+                node_jobs: vec![NodeJobInfo {
+                    node_id: node_id.to_string(),
+                    job_id: response.job_id,
+                }],
+            }
+        }
+        Err(e) => {
+            tracing::warn!(
+                rack_id = %rack_id,
+                device_type = %display_name,
+                node_id = %node_id,
+                error = %e,
+                "Failed to initiate async firmware update"
+            );
+            DeviceUpdateResult {
+                device_id: node_id.to_string(),
+                device_type: display_name.to_string(),
+                success: false,
+                message: format!("RMS API Error: {}", e),
+                job_id: String::new(),
+                node_jobs: vec![],
+            }
+        }
+    }
+}
+
+/// Claims the apply of `req` to `rack_id` before any job is started, so that concurrent applies
+/// of the same firmware don't both flash the rack. Returns the existing attempt if the firmware
+/// was applied within `APPLY_REUSE_WINDOW`, or is still being applied within `APPLY_CLAIM_LEASE`.
+async fn claim_apply(
+    database_connection: &sqlx::PgPool,
+    rack_id: RackId,
+    req: &RackFirmwareApplyRequest,
+) -> Result<Option<RackFirmwareApplyAttempt>, DatabaseError> {
+    let mut txn = database_connection
+        .begin()
+        .await
+        .map_err(|e| DatabaseError::new("begin claim apply", e))?;
+    let now = chrono::Utc::now();
+    let previous_apply = RackFirmwareApplyAttempt::claim(
+        &mut txn,
+        rack_id,
+        &req.firmware_id,
+        &req.firmware_type,
+        now - APPLY_REUSE_WINDOW,
+        now - APPLY_CLAIM_LEASE,
+    )
+    .await?;
+    txn.commit()
+        .await
+        .map_err(|e| DatabaseError::new("commit claim apply", e))?;
+    Ok(previous_apply)
+}
+
+/// Records the jobs started by a claimed apply, or releases the claim if it started none
+async fn finish_apply(
+    database_connection: &sqlx::PgPool,
+    rack_id: RackId,
+    req: &RackFirmwareApplyRequest,
+    response: &RackFirmwareApplyResponse,
+) -> Result<(), DatabaseError> {
+    let mut txn = database_connection
+        .begin()
+        .await
+        .map_err(|e| DatabaseError::new("begin record apply", e))?;
+    if response.successful_updates > 0 {
+        RackFirmwareApplyAttempt::record(
+            &mut txn,
+            rack_id,
+            &req.firmware_id,
+            &req.firmware_type,
+            response,
+        )
+        .await?;
+    } else {
+        RackFirmwareApplyAttempt::release(&mut txn, rack_id, &req.firmware_id, &req.firmware_type)
+            .await?;
+    }
+    txn.commit()
+        .await
+        .map_err(|e| DatabaseError::new("commit record apply", e))
+}
+
+/// Sort the (component_name, filename, target) firmware components of a device type into the
+/// order their targets need to be flashed in, flashing each target once.
+///
+/// One downloaded file can flash several targets, e.g. a `BMC+FPGA+EROT` bundle on a switch
+/// tray. Such a file is listed once per target, and each of its targets is flashed in turn.
+/// Different files for the same target are an error, since only one of them could be flashed.
+pub(crate) fn order_firmware_components_for_flashing(
+    device_type_key: &str,
+    mut firmware_components: Vec<(String, String, String)>,
+) -> Result<Vec<(String, String, String)>, String> {
+    let flash_order = get_firmware_flash_order(device_type_key);
+    // Targets without a required order are flashed last, sorted by name for a stable order
+    firmware_components.sort_by(|(_, _, a), (_, _, b)| {
+        let position = |target: &str| {
+            flash_order
+                .iter()
+                .position(|&t| t == target)
+                .unwrap_or(usize::MAX)
+        };
+        position(a).cmp(&position(b)).then_with(|| a.cmp(b))
+    });
+
+    let mut ordered: Vec<(String, String, String)> = Vec::new();
+    for component in firmware_components {
+        if let Some((_, filename, target)) = ordered.last()
+            && *target == component.2
+        {
+            if *filename != component.1 {
+                return Err(format!(
+                    "{device_type_key} target {target} is flashed from both {filename} and {}",
+                    component.1
+                ));
+            }
+            continue;
+        }
+        ordered.push(component);
+    }
+    Ok(ordered)
 }
 
 fn get_firmware_flash_order(device_type_key: &str) -> &'static [&'static str] {
@@ -1177,10 +3061,43 @@ fn get_firmware_flash_order(device_type_key: &str) -> &'static [&'static str] {
     }
 }
 
+/// Explain why the lookup table has no `firmware_type` firmware for `hardware_type`: either the
+/// config has no firmware for the device type at all, or only for other firmware types.
+fn describe_missing_firmware(
+    parsed_components: &serde_json::Value,
+    hardware_type: &str,
+    display_name: &str,
+    firmware_type: &str,
+) -> String {
+    let device_components =
+        serde_json::from_value::<FirmwareLookupTable>(parsed_components.clone())
+            .ok()
+            .and_then(|mut table| table.devices.remove(hardware_type));
+
+    let mut available_types: Vec<String> = device_components
+        .iter()
+        .flat_map(|components| components.values())
+        .map(|entry| entry.firmware_type.to_lowercase())
+        .collect();
+    available_types.sort();
+    available_types.dedup();
+
+    if available_types.is_empty() {
+        format!("No matching firmware found in config for {}", display_name)
+    } else {
+        format!(
+            "No {} firmware found in config for {}, only {} firmware",
+            firmware_type.to_lowercase(),
+            display_name,
+            available_types.join(", ")
+        )
+    }
+}
+
 /// Helper function to find all firmware components for a specific device type using the lookup table
 /// Returns a vector of (component_name, filename, target) tuples
 /// Only returns components matching the requested firmware_type (prod or dev)
-fn find_firmware_components_for_device(
+pub(crate) fn find_firmware_components_for_device(
     parsed_components: &serde_json::Value,
     hardware_type: &str,
     firmware_type: &str, // "prod" or "dev"
@@ -1286,14 +3203,25 @@ pub async fn get_job_status(
         .map_err(|e| Status::internal(format!("RMS API error: {}", e)))?;
 
     // Map FirmwareJobState enum to human-readable string
-    let state = match rms_response.job_state {
-        0 => "QUEUED",
-        1 => "RUNNING",
-        2 => "COMPLETED",
-        3 => "FAILED",
-        _ => "UNKNOWN",
+    let state = match FirmwareJobState::try_from(rms_response.job_state) {
+        Ok(FirmwareJobState::Queued) => "QUEUED",
+        Ok(FirmwareJobState::Running) => "RUNNING",
+        Ok(FirmwareJobState::Completed) => "COMPLETED",
+        Ok(FirmwareJobState::Failed) => "FAILED",
+        Err(_) => {
+            tracing::warn!(
+                job_id = %rms_response.job_id,
+                job_state = rms_response.job_state,
+                "RMS reported an unknown firmware job state"
+            );
+            "UNKNOWN"
+        }
     };
 
+    let node_results = parse_firmware_job_result(&rms_response.result_json)
+        .map(|result| result.nodes.into_iter().map(Into::into).collect())
+        .unwrap_or_default();
+
     Ok(Response::new(RackFirmwareJobStatusResponse {
         job_id: rms_response.job_id,
         state: state.to_string(),
@@ -1302,5 +3230,6 @@ pub async fn get_job_status(
         node_id: rms_response.node_id,
         error_message: rms_response.error_message,
         result_json: rms_response.result_json,
+        node_results,
     }))
 }
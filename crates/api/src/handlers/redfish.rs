@@ -28,7 +28,7 @@ use forge_secrets::credentials::CredentialReader;
 use http::header::CONTENT_TYPE;
 use http::{HeaderMap, HeaderValue, Uri};
 use model::redfish::BMCResponse;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use utils::HostPortPair;
 use uuid::Uuid;
@@ -126,6 +126,7 @@ pub async fn redfish_create_action(
     )?;
 
     let request = request.into_inner();
+    validate_parameters(&request.action, &request.parameters)?;
 
     let mut txn = api.txn_begin().await?;
 
@@ -249,6 +250,7 @@ pub async fn redfish_apply_action(
             let pool = api.database_connection.clone();
             let credential_reader = api.credential_manager.clone();
             let bmc_proxy = api.dynamic_settings.bmc_proxy.clone();
+            let action = action_request.action.clone();
             let mut parameters = action_request.parameters.clone();
             async move {
                 // Allow tests to trigger mock behavior by inserting a `"__TEST_BEHAVIOR__": "..."`
@@ -256,6 +258,7 @@ pub async fn redfish_apply_action(
                 let test_behavior = TestBehavior::from_parameters_if_testing(&mut parameters);
 
                 let response = handle_request(
+                    &action,
                     parameters,
                     uri,
                     &pool,
@@ -294,6 +297,7 @@ async fn update_response_in_tx(
 }
 
 async fn handle_request(
+    action: &str,
     parameters: String,
     uri: Uri,
     pool: &PgPool,
@@ -331,39 +335,16 @@ async fn handle_request(
     } else if let Some(mock_response) = test_behavior.and_then(TestBehavior::into_mock_success) {
         Ok(mock_response)
     } else {
-        match http_client
-            .request(http::Method::POST, new_uri.to_string())
-            .basic_auth(metadata.user.clone(), Some(metadata.password.clone()))
-            .body(parameters)
-            .headers(headers)
-            .send()
-            .await
-        {
-            Ok(response) => {
-                let headers = response
-                    .headers()
-                    .iter()
-                    .map(|(x, y)| {
-                        (
-                            x.to_string(),
-                            String::from_utf8_lossy(y.as_bytes()).to_string(),
-                        )
-                    })
-                    .collect::<HashMap<String, String>>();
-                let status = response.status().to_string();
-                let body = response
-                    .text()
-                    .await
-                    .unwrap_or("could not decode body as text".to_owned());
-                Ok(BMCResponse {
-                    status,
-                    headers,
-                    body,
-                    completed_at: DateTime::from(Local::now()),
-                })
-            }
-            Err(e) => Err(e.into()),
-        }
+        send_request(
+            action,
+            parameters,
+            new_uri,
+            headers,
+            &metadata,
+            &http_client,
+            JobPollOptions::default(),
+        )
+        .await
     };
 
     match result {
@@ -460,6 +441,322 @@ async fn create_client(
     Ok((metadata, new_uri, headers, http_client))
 }
 
+/// Action of redfish actions which PATCH `target` with the parameters, and wait for the job the
+/// BMC creates to apply the change
+pub const PATCH_AND_WAIT_ACTION: &str = "PATCH";
+
+/// Action of redfish actions which reorder the boot options of the `target` system to the
+/// `BootOrder` given in the parameters
+pub const BOOT_ORDER_ACTION: &str = "BootOrder";
+
+/// Parameters of a `BOOT_ORDER_ACTION`
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BootOrderParameters {
+    boot_order: Vec<String>,
+}
+
+/// Rejects parameters which can never be applied by `action`
+fn validate_parameters(action: &str, parameters: &str) -> Result<(), CarbideError> {
+    match action {
+        PATCH_AND_WAIT_ACTION => serde_json::from_str::<serde_json::Value>(parameters)
+            .map(|_| ())
+            .map_err(|e| CarbideError::InvalidArgument(format!("Invalid PATCH body: {e}"))),
+        BOOT_ORDER_ACTION => serde_json::from_str::<BootOrderParameters>(parameters)
+            .map(|_| ())
+            .map_err(|e| CarbideError::InvalidArgument(format!("Invalid boot order: {e}"))),
+        _ => Ok(()),
+    }
+}
+
+/// Sends the request of a redfish action to the BMC.
+///
+/// Actions are POSTed to `uri`, except for `PATCH_AND_WAIT_ACTION` and `BOOT_ORDER_ACTION`.
+pub(crate) async fn send_request(
+    action: &str,
+    parameters: String,
+    uri: Uri,
+    headers: HeaderMap,
+    metadata: &rpc::forge::BmcMetaDataGetResponse,
+    http_client: &reqwest::Client,
+    job_poll_options: JobPollOptions,
+) -> Result<BMCResponse, RequestErrorInfo> {
+    match action {
+        PATCH_AND_WAIT_ACTION => {
+            let body = serde_json::from_str(&parameters)
+                .map_err(|e| CarbideError::InvalidArgument(format!("Invalid PATCH body: {e}")))?;
+            let job_state =
+                patch_and_wait_for_job(http_client, metadata, uri, headers, body, job_poll_options)
+                    .await?;
+            Ok(BMCResponse {
+                headers: HashMap::new(),
+                status: job_state.clone(),
+                body: format!("Job finished in state {job_state}"),
+                completed_at: DateTime::from(Local::now()),
+            })
+        }
+        BOOT_ORDER_ACTION => {
+            let parameters: BootOrderParameters = serde_json::from_str(&parameters)
+                .map_err(|e| CarbideError::InvalidArgument(format!("Invalid boot order: {e}")))?;
+            remediate_boot_order(http_client, metadata, uri, headers, &parameters.boot_order).await
+        }
+        _ => {
+            let response = http_client
+                .request(http::Method::POST, uri.to_string())
+                .basic_auth(metadata.user.clone(), Some(metadata.password.clone()))
+                .body(parameters)
+                .headers(headers)
+                .send()
+                .await?;
+            let headers = response
+                .headers()
+                .iter()
+                .map(|(x, y)| {
+                    (
+                        x.to_string(),
+                        String::from_utf8_lossy(y.as_bytes()).to_string(),
+                    )
+                })
+                .collect::<HashMap<String, String>>();
+            let status = response.status().to_string();
+            let body = response
+                .text()
+                .await
+                .unwrap_or("could not decode body as text".to_owned());
+            Ok(BMCResponse {
+                status,
+                headers,
+                body,
+                completed_at: DateTime::from(Local::now()),
+            })
+        }
+    }
+}
+
+/// Reads the boot order of the system at `uri`, and applies the PATCHes planned by
+/// `plan_boot_order_remediation` to change it to `desired`
+async fn remediate_boot_order(
+    http_client: &reqwest::Client,
+    metadata: &rpc::forge::BmcMetaDataGetResponse,
+    uri: Uri,
+    headers: HeaderMap,
+    desired: &[String],
+) -> Result<BMCResponse, RequestErrorInfo> {
+    let system = http_client
+        .request(http::Method::GET, uri.to_string())
+        .basic_auth(metadata.user.clone(), Some(metadata.password.clone()))
+        .headers(headers.clone())
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let system: serde_json::Value = serde_json::from_str(&system)
+        .map_err(|e| CarbideError::internal(format!("Invalid system {uri}: {e}")))?;
+    let current: Vec<String> = serde_json::from_value(system["Boot"]["BootOrder"].clone())
+        .map_err(|e| CarbideError::internal(format!("Invalid boot order of {uri}: {e}")))?;
+
+    let plan = plan_boot_order_remediation(uri.path(), &current, desired)?;
+    let mut status = http::StatusCode::OK.to_string();
+    for patch in &plan {
+        let patch_uri = uri_with_path(&uri, &patch.path)?;
+        status = http_client
+            .request(http::Method::PATCH, patch_uri.to_string())
+            .basic_auth(metadata.user.clone(), Some(metadata.password.clone()))
+            .headers(headers.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(patch.body.to_string())
+            .send()
+            .await?
+            .error_for_status()?
+            .status()
+            .to_string();
+    }
+
+    Ok(BMCResponse {
+        headers: HashMap::new(),
+        status,
+        body: if plan.is_empty() {
+            "Boot order already matches".to_string()
+        } else {
+            format!("Boot order changed from {current:?} to {desired:?}")
+        },
+        completed_at: DateTime::from(Local::now()),
+    })
+}
+
+/// Returns `uri` with its path replaced by `path_and_query`
+fn uri_with_path(uri: &Uri, path_and_query: &str) -> Result<Uri, CarbideError> {
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(
+        path_and_query
+            .parse()
+            .map_err(|e| CarbideError::internal(format!("invalid path {path_and_query}: {e}")))?,
+    );
+    Uri::from_parts(parts).map_err(|e| CarbideError::internal(format!("invalid url parts {e}")))
+}
+
+/// Job states after which a BMC job will not make any further progress
+const TERMINAL_JOB_STATES: &[&str] = &[
+    "Completed",
+    "CompletedWithErrors",
+    "Failed",
+    "Exception",
+    "Cancelled",
+    "Killed",
+];
+
+/// Terminal job state of a BMC job which applied its changes without errors
+const SUCCESSFUL_JOB_STATE: &str = "Completed";
+
+/// How long to wait for a BMC job, and how often to check on it
+#[derive(Debug, Clone, Copy)]
+pub struct JobPollOptions {
+    pub timeout: std::time::Duration,
+    pub interval: std::time::Duration,
+}
+
+impl Default for JobPollOptions {
+    fn default() -> Self {
+        Self {
+            timeout: std::time::Duration::from_secs(300),
+            interval: std::time::Duration::from_secs(5),
+        }
+    }
+}
+
+/// PATCHes `uri` with `body`, and waits for the job the BMC created to apply the change.
+///
+/// BMCs like iDRAC apply configuration changes through a job, and return the job in the
+/// `Location` header of the response. The job is polled until it reaches a terminal state,
+/// which is returned if the job completed successfully. Jobs which end in any other terminal
+/// state, like `Failed` or `Exception`, are returned as an error. `uri`, `headers` and
+/// `http_client` are expected to come from `create_client`.
+pub(crate) async fn patch_and_wait_for_job(
+    http_client: &reqwest::Client,
+    metadata: &rpc::forge::BmcMetaDataGetResponse,
+    uri: Uri,
+    headers: HeaderMap,
+    body: serde_json::Value,
+    options: JobPollOptions,
+) -> Result<String, CarbideError> {
+    let mut patch_headers = headers.clone();
+    patch_headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    let response = http_client
+        .request(http::Method::PATCH, uri.to_string())
+        .basic_auth(metadata.user.clone(), Some(metadata.password.clone()))
+        .headers(patch_headers)
+        .body(body.to_string())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| CarbideError::internal(format!("PATCH {uri} failed: {e}")))?;
+
+    let location = response
+        .headers()
+        .get(http::header::LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .ok_or_else(|| CarbideError::internal(format!("PATCH {uri} did not return a job")))?;
+    // Only take the path from the job location, so that the job is fetched through the same
+    // (possibly proxied) authority as the PATCH
+    let job_path = location
+        .parse::<Uri>()
+        .ok()
+        .and_then(|location| location.path_and_query().cloned())
+        .ok_or_else(|| CarbideError::internal(format!("Invalid job location: {location}")))?;
+    let job_uri = uri_with_path(&uri, job_path.as_str())?;
+
+    let started = std::time::Instant::now();
+    loop {
+        let job = http_client
+            .request(http::Method::GET, job_uri.to_string())
+            .basic_auth(metadata.user.clone(), Some(metadata.password.clone()))
+            .headers(headers.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| CarbideError::internal(format!("GET {job_uri} failed: {e}")))?
+            .text()
+            .await
+            .map_err(|e| CarbideError::internal(format!("Error reading job {job_uri}: {e}")))?;
+        let job: serde_json::Value = serde_json::from_str(&job)
+            .map_err(|e| CarbideError::internal(format!("Invalid job {job_uri}: {e}")))?;
+
+        let job_state = job
+            .get("JobState")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| CarbideError::internal(format!("Job {job_uri} has no JobState")))?;
+        if job_state == SUCCESSFUL_JOB_STATE {
+            return Ok(job_state.to_string());
+        }
+        if TERMINAL_JOB_STATES.contains(&job_state) {
+            let message = job
+                .get("Message")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default();
+            return Err(CarbideError::internal(format!(
+                "Job {job_uri} finished in state {job_state}: {message}"
+            )));
+        }
+
+        if started.elapsed() >= options.timeout {
+            return Err(CarbideError::internal(format!(
+                "Timed out waiting for job {job_uri}. Last state: {job_state}"
+            )));
+        }
+        tokio::time::sleep(options.interval).await;
+    }
+}
+
+/// A Redfish PATCH that is part of a remediation plan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedfishPatch {
+    /// Path of the resource to PATCH, e.g. `/redfish/v1/Systems/System.Embedded.1`
+    pub path: String,
+    pub body: serde_json::Value,
+}
+
+/// Computes the PATCHes that change a system's boot order from `current` to `desired`.
+///
+/// Redfish replaces the whole `Boot.BootOrder` array at once, so a boot order that differs in
+/// any way is fixed by a single PATCH of `target`, and one that already matches needs none.
+/// `target` is the system resource, or its `/Settings` resource on BMCs that only accept boot
+/// order changes there. `desired` must be a reordering of `current`: boot options can be moved,
+/// but not added or removed.
+pub(crate) fn plan_boot_order_remediation(
+    target: &str,
+    current: &[String],
+    desired: &[String],
+) -> Result<Vec<RedfishPatch>, CarbideError> {
+    let mut remaining: Vec<&String> = current.iter().collect();
+    for option in desired {
+        let Some(index) = remaining.iter().position(|o| *o == option) else {
+            return Err(CarbideError::InvalidArgument(format!(
+                "Boot option {option} is not in the current boot order, or is listed twice"
+            )));
+        };
+        remaining.swap_remove(index);
+    }
+    if !remaining.is_empty() {
+        return Err(CarbideError::InvalidArgument(format!(
+            "Desired boot order is missing boot options: {}",
+            remaining
+                .iter()
+                .map(|o| o.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )));
+    }
+
+    if current == desired {
+        return Ok(vec![]);
+    }
+    Ok(vec![RedfishPatch {
+        path: target.to_string(),
+        body: serde_json::json!({"Boot": {"BootOrder": desired}}),
+    }])
+}
+
 pub async fn redfish_cancel_action(
     api: &crate::api::Api,
     request: tonic::Request<::rpc::forge::RedfishActionId>,
@@ -557,11 +854,21 @@ impl TestBehavior {
 
 // Subset of the data we care about from reqwest::Error, so that we can mock it (we can't build our
 // own reqwest::Error as its constructors are all private.)
+#[derive(Debug)]
 pub struct RequestErrorInfo {
     pub status_code: Option<http::status::StatusCode>,
     pub description: String,
 }
 
+impl From<CarbideError> for RequestErrorInfo {
+    fn from(e: CarbideError) -> Self {
+        Self {
+            status_code: None,
+            description: e.to_string(),
+        }
+    }
+}
+
 impl From<reqwest::Error> for RequestErrorInfo {
     fn from(e: reqwest::Error) -> Self {
         Self {
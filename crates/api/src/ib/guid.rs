@@ -0,0 +1,160 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+
+use crate::{CarbideError, CarbideResult};
+
+/// Number of hex digits in an InfiniBand port GUID (64 bits)
+const GUID_HEX_LEN: usize = 16;
+
+/// An InfiniBand GUID in canonical form: 16 lowercase hex digits, without
+/// a `0x` prefix and without separators (e.g. `1070fd0300176625`).
+///
+/// This is the format UFM uses and the format stored in the database.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Guid(String);
+
+impl Guid {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Guid> for String {
+    fn from(guid: Guid) -> Self {
+        guid.0
+    }
+}
+
+impl std::str::FromStr for Guid {
+    type Err = CarbideError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        normalize_guid(s)
+    }
+}
+
+/// Converts a GUID from any of the textual forms we encounter into its
+/// canonical form.
+///
+/// Accepted inputs are case-insensitive, may carry a `0x` prefix and may use
+/// `:` or `-` separators, e.g. `0x1070FD0300176625` or `10:70:fd:03:00:17:66:25`.
+/// Surrounding whitespace is ignored.
+pub fn normalize_guid(guid: &str) -> CarbideResult<Guid> {
+    let trimmed = guid.trim();
+    let digits = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+
+    let normalized: String = digits
+        .chars()
+        .filter(|c| *c != ':' && *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+
+    if normalized.len() != GUID_HEX_LEN {
+        return Err(CarbideError::InvalidArgument(format!(
+            "Invalid IB GUID \"{guid}\": expected {GUID_HEX_LEN} hex digits, found {}",
+            normalized.len()
+        )));
+    }
+    if !normalized.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(CarbideError::InvalidArgument(format!(
+            "Invalid IB GUID \"{guid}\": contains non-hex characters"
+        )));
+    }
+
+    Ok(Guid(normalized))
+}
+
+/// Canonical form of a GUID received from another system, e.g. UFM or a
+/// machine's hardware info, for use as a lookup key.
+///
+/// GUIDs that can't be normalized are kept as they are, so that they still
+/// show up in comparisons and reports instead of being dropped.
+pub fn canonical_guid(guid: &str) -> String {
+    normalize_guid(guid)
+        .map(String::from)
+        .unwrap_or_else(|_| guid.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_equivalent_forms() {
+        let inputs = [
+            "1070fd0300176625",
+            "1070FD0300176625",
+            "0x1070fd0300176625",
+            "0X1070FD0300176625",
+            "10:70:fd:03:00:17:66:25",
+            "10:70:FD:03:00:17:66:25",
+            "1070-fd03-0017-6625",
+            "  1070fd0300176625\n",
+        ];
+        for input in inputs {
+            let guid = normalize_guid(input).unwrap();
+            assert_eq!(guid.as_str(), "1070fd0300176625", "input: {input}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_invalid() {
+        let inputs = [
+            "",
+            "0x",
+            "1070fd030017662",
+            "1070fd03001766250",
+            "1070fd030017662g",
+            "10:70:fd:03:00:17:66",
+            "0x0x1070fd0300176625",
+            "1070fd03 00176625",
+        ];
+        for input in inputs {
+            assert!(
+                matches!(normalize_guid(input), Err(CarbideError::InvalidArgument(_))),
+                "input: {input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_canonical_guid() {
+        assert_eq!(
+            canonical_guid("10:70:FD:03:00:17:66:25"),
+            "1070fd0300176625"
+        );
+        assert_eq!(canonical_guid("not-a-guid"), "not-a-guid");
+    }
+
+    #[test]
+    fn test_parse_guid() {
+        let guid: Guid = "0x946DAE03006104F8".parse().unwrap();
+        assert_eq!(guid.to_string(), "946dae03006104f8");
+        assert_eq!(String::from(guid), "946dae03006104f8");
+    }
+}
@@ -22,6 +22,7 @@ use async_trait::async_trait;
 use forge_secrets::credentials::{CredentialKey, CredentialReader, Credentials};
 pub use model::ib::{IBMtu, IBRateLimit, IBServiceLevel};
 
+pub use self::guid::{canonical_guid, normalize_guid};
 #[cfg(test)]
 pub use self::iface::Filter;
 pub use self::iface::{
@@ -30,6 +31,7 @@ pub use self::iface::{
 use crate::{CarbideError, cfg};
 
 mod disable;
+mod guid;
 mod iface;
 mod rest;
 mod ufmclient;
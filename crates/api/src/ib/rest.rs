@@ -29,7 +29,7 @@ use super::ufmclient::{
     self, Partition, PartitionKey, PartitionQoS, Port, PortConfig, PortMembership, SmConfig,
     UFMCert, UFMConfig, UFMError, Ufm,
 };
-use super::{IBFabric, IBFabricConfig, IBFabricVersions};
+use super::{IBFabric, IBFabricConfig, IBFabricVersions, canonical_guid};
 use crate::CarbideError;
 
 pub struct RestIBFabric {
@@ -286,7 +286,10 @@ impl TryFrom<&Partition> for IBNetwork {
             pkey: p.pkey.into(),
             ipoib: p.ipoib,
             qos_conf: p.qos.as_ref().map(|qos| qos.try_into()).transpose()?,
-            associated_guids: p.guids.clone(),
+            associated_guids: p
+                .guids
+                .as_ref()
+                .map(|guids| guids.iter().map(|guid| canonical_guid(guid)).collect()),
             membership: p.membership.map(Into::into),
             // Not implemented yet
             // enable_sharp: false,
@@ -359,7 +362,7 @@ impl From<&Port> for IBPort {
     fn from(p: &Port) -> Self {
         IBPort {
             name: p.name.clone(),
-            guid: p.guid.clone(),
+            guid: canonical_guid(&p.guid),
             lid: p.lid,
             state: IBPortState::try_from(p.logical_state.clone()).ok(),
         }
@@ -711,4 +714,45 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn ufm_guids_are_normalized() {
+        let port = Port {
+            guid: "0x1070FD0300176625".to_string(),
+            name: "1070fd0300176625_2".to_string(),
+            system_id: "1070fd0300176624".to_string(),
+            lid: 4,
+            dname: "2".to_string(),
+            system_name: "MT4119 ConnectX5   Mellanox Technologies".to_string(),
+            physical_state: "Link Up".to_string(),
+            logical_state: "Active".to_string(),
+        };
+        assert_eq!(IBPort::from(port).guid, "1070fd0300176625");
+
+        let partition = Partition {
+            name: "PartitionTest".to_string(),
+            pkey: PartitionKey::try_from(0x7).unwrap(),
+            ipoib: true,
+            qos: None,
+            guids: Some(
+                [
+                    "10:70:FD:03:00:17:66:25".to_string(),
+                    "1070FD0300176374".to_string(),
+                ]
+                .into(),
+            ),
+            membership: None,
+        };
+        let network = IBNetwork::try_from(partition).unwrap();
+        assert_eq!(
+            network.associated_guids,
+            Some(
+                [
+                    "1070fd0300176625".to_string(),
+                    "1070fd0300176374".to_string(),
+                ]
+                .into()
+            )
+        );
+    }
 }
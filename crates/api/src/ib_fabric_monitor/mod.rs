@@ -45,7 +45,7 @@ use tokio::sync::oneshot;
 use tracing::Instrument;
 
 use crate::cfg::file::{CarbideConfig, IbFabricDefinition};
-use crate::ib::{GetPartitionOptions, IBFabricManager, IBFabricManagerType};
+use crate::ib::{GetPartitionOptions, IBFabricManager, IBFabricManagerType, canonical_guid};
 use crate::periodic_timer::PeriodicTimer;
 use crate::{CarbideError, CarbideResult};
 
@@ -539,7 +539,7 @@ impl FabricData {
             };
 
             for guid in associated_guids.iter() {
-                let guid_partitions = partitions_by_guid.entry(guid.clone()).or_default();
+                let guid_partitions = partitions_by_guid.entry(canonical_guid(guid)).or_default();
                 guid_partitions.insert(*pkey);
             }
         }
@@ -565,7 +565,7 @@ async fn get_ports_information(
             None => "unknown".to_string(),
         };
         *ports_by_state.entry(state).or_default() += 1;
-        ports_by_guid.insert(port.guid.clone(), port);
+        ports_by_guid.insert(canonical_guid(&port.guid), port);
     }
     metrics.ports_by_state = Some(ports_by_state);
 
@@ -716,14 +716,15 @@ async fn record_machine_infiniband_status_observation(
             let Some(expected_pkey) = partition_data.status.as_ref().and_then(|s| s.pkey) else {
                 continue;
             };
-            expected_pkeys.insert(guid.clone(), expected_pkey);
+            expected_pkeys.insert(canonical_guid(guid), expected_pkey);
         }
     }
 
-    // The list of GUIDs that are part of this Machine
+    // The list of GUIDs that are part of this Machine. Hardware info stored before GUIDs were
+    // normalized on discovery may use another format than UFM.
     let mut guids: Vec<String> = Vec::new();
     for ib_interface in ib_hw_info.iter() {
-        guids.push(ib_interface.guid.clone());
+        guids.push(canonical_guid(&ib_interface.guid));
     }
 
     let mut prev = mh_snapshot
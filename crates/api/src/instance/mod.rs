@@ -47,6 +47,7 @@ use model::vpc_prefix::VpcPrefix;
 use sqlx::PgConnection;
 
 use crate::api::Api;
+use crate::ib::normalize_guid;
 use crate::network_segment::allocate::PrefixAllocator;
 use crate::{CarbideError, CarbideResult};
 
@@ -70,8 +71,15 @@ pub struct InstanceAllocationRequest {
 
     /// Allow allocation on unhealthy machines
     pub allow_unhealthy_machine: bool,
+
+    /// Key under which the allocated instance is remembered, so that a
+    /// retried request returns it instead of allocating another one
+    pub idempotency_key: Option<String>,
 }
 
+/// How long an idempotency key keeps pointing at the instance it created
+pub const IDEMPOTENCY_KEY_WINDOW: chrono::Duration = chrono::Duration::hours(24);
+
 impl TryFrom<rpc::InstanceAllocationRequest> for InstanceAllocationRequest {
     type Error = CarbideError;
 
@@ -107,6 +115,8 @@ impl TryFrom<rpc::InstanceAllocationRequest> for InstanceAllocationRequest {
 
         let allow_unhealthy_machine = request.allow_unhealthy_machine;
 
+        let idempotency_key = request.idempotency_key.filter(|key| !key.is_empty());
+
         Ok(InstanceAllocationRequest {
             instance_id,
             instance_type_id,
@@ -114,6 +124,7 @@ impl TryFrom<rpc::InstanceAllocationRequest> for InstanceAllocationRequest {
             config,
             metadata,
             allow_unhealthy_machine,
+            idempotency_key,
         })
     }
 }
@@ -261,10 +272,11 @@ pub fn allocate_ib_port_guid(
 
         if let Some(sorted_ibs) = ib_hw_map.get(&request.device) {
             if let Some(ib) = sorted_ibs.get(request.device_instance as usize) {
-                request.pf_guid = Some(ib.guid.clone());
-                request.guid = Some(ib.guid.clone());
-                guids.push(ib.guid.clone());
-                tracing::debug!("select IB device GUID {}", ib.guid.clone());
+                let guid: String = normalize_guid(&ib.guid)?.into();
+                request.pf_guid = Some(guid.clone());
+                request.guid = Some(guid.clone());
+                tracing::debug!("select IB device GUID {}", guid);
+                guids.push(guid);
             } else {
                 return Err(CarbideError::InvalidArgument(format!(
                     "not enough ib device {} (machine {})",
@@ -284,7 +296,9 @@ pub fn allocate_ib_port_guid(
         if let Some(ib_interfaces_status) = &machine.infiniband_status_observation {
             for guid in guids.iter() {
                 for ib_status in ib_interfaces_status.ib_interfaces.iter() {
-                    if *guid == ib_status.guid && ib_status.lid == 0xffff_u16 {
+                    let status_guid_matches = normalize_guid(&ib_status.guid)
+                        .is_ok_and(|status_guid| status_guid.as_str() == *guid);
+                    if status_guid_matches && ib_status.lid == 0xffff_u16 {
                         return Err(CarbideError::InvalidArgument(format!(
                             "UFM detected inactive state for GUID: {guid} (machine {})",
                             machine.id
@@ -653,6 +667,29 @@ pub async fn batch_allocate_instances(
 
     let _persisted_instances = db::instance::batch_persist(new_instances, &mut txn).await?;
 
+    // Remember idempotency keys in the same transaction, so that a key only
+    // ever points at an instance that exists. Keys which expired are pruned
+    // while at it.
+    let idempotency_key_since = chrono::Utc::now() - IDEMPOTENCY_KEY_WINDOW;
+    if processed_requests
+        .iter()
+        .any(|(request, _)| request.idempotency_key.is_some())
+    {
+        db::instance::delete_expired_idempotency_keys(&mut txn, idempotency_key_since).await?;
+    }
+    for (request, _) in &processed_requests {
+        if let Some(idempotency_key) = &request.idempotency_key {
+            db::instance::record_idempotency_key(
+                &mut txn,
+                request.config.tenant.tenant_organization_id.as_str(),
+                idempotency_key,
+                request.instance_id,
+                idempotency_key_since,
+            )
+            .await?;
+        }
+    }
+
     // ==== Phase 7: Process configs (IPs, inband interfaces, IB GUIDs) ====
     // These need to be done per-instance but we collect results for batch update
     // Tuple format: (instance_id, expected_version, config)
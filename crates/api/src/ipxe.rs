@@ -27,6 +27,13 @@ use model::pxe::PxeInstructionRequest;
 use sqlx::PgConnection;
 
 use crate::CarbideError;
+use crate::cfg::file::{PxeFallbackConfig, PxeFallbackTemplate};
+
+static UNKNOWN_HOST_INSTRUCTIONS: &str = r#"
+echo this is an unknown ARM host, not PXE booting ||
+sleep 5 ||
+exit ||
+        "#;
 
 pub struct PxeInstructions;
 
@@ -103,23 +110,58 @@ impl PxeInstructions {
         }.serialize_pxe_instructions()
     }
 
+    /// Renders the script that is served in place of boot instructions.
+    /// `machine` is absent if the interface is not associated with a machine.
+    fn get_fallback_instructions(
+        template: PxeFallbackTemplate,
+        interface_id: MachineInterfaceId,
+        machine: Option<(MachineId, &ManagedHostState)>,
+    ) -> String {
+        let header = match machine {
+            Some((machine_id, state)) => format!(
+                "echo Machine ID: {machine_id}\necho Interface ID: {interface_id}\necho Current state: {state}\n"
+            ),
+            None => format!("echo Interface ID: {interface_id}\n"),
+        };
+
+        match template {
+            PxeFallbackTemplate::UnknownHost => UNKNOWN_HOST_INSTRUCTIONS.to_string(),
+            PxeFallbackTemplate::ErrorInstructions => format!(
+                r#"
+{header}echo Could not continue boot due to invalid state ||
+sleep 5 ||
+exit ||
+"#
+            ),
+            PxeFallbackTemplate::ExitInstructions => format!(
+                r#"
+{header}echo This state assumes an OS is provisioned and will exit into the OS in 5 seconds. To re-run iPXE instructions and OS installation, trigger a reboot request with flag rebootWithCustomIpxe/boot_with_custom_ipxe set. ||
+sleep 5 ||
+exit ||
+"#
+            ),
+        }
+    }
+
+    /// Renders the script that is served to a host which requests PXE
+    /// instructions more often than the rate limit allows
+    pub fn get_rate_limited_instructions(interface_id: MachineInterfaceId) -> String {
+        Self::get_fallback_instructions(PxeFallbackTemplate::ErrorInstructions, interface_id, None)
+    }
+
     pub async fn get_pxe_instructions(
         txn: &mut PgConnection,
         target: PxeInstructionRequest,
+        fallback: &PxeFallbackConfig,
     ) -> Result<String, CarbideError> {
         let error_instructions = |machine_id: MachineId,
                                   interface_id: MachineInterfaceId,
                                   state: &ManagedHostState|
          -> String {
-            format!(
-                r#"
-echo Machine ID: {machine_id}
-echo Interface ID: {interface_id}
-echo Current state: {state}
-echo Could not continue boot due to invalid state ||
-sleep 5 ||
-exit ||
-"#
+            Self::get_fallback_instructions(
+                fallback.invalid_state,
+                interface_id,
+                Some((machine_id, state)),
             )
         };
 
@@ -127,24 +169,13 @@ exit ||
                                  interface_id: MachineInterfaceId,
                                  state: &ManagedHostState|
          -> String {
-            format!(
-                r#"
-echo Machine ID: {machine_id}
-echo Interface ID: {interface_id}
-echo Current state: {state}
-echo This state assumes an OS is provisioned and will exit into the OS in 5 seconds. To re-run iPXE instructions and OS installation, trigger a reboot request with flag rebootWithCustomIpxe/boot_with_custom_ipxe set. ||
-sleep 5 ||
-exit ||
-"#
+            Self::get_fallback_instructions(
+                fallback.no_provisioning,
+                interface_id,
+                Some((machine_id, state)),
             )
         };
 
-        static UNKNOWN_HOST_INSTRUCTIONS: &str = r#"
-echo this is an unknown ARM host, not PXE booting ||
-sleep 5 ||
-exit ||
-        "#;
-
         let mut console = "ttyS0";
         let mut qcow_imager_url = "chain ${base-url}/internal/x86_64/qcow-imager.efi loglevel=7 console=tty0 pci=realloc=off ";
         let interface = db::machine_interface::find_one(txn, target.interface_id).await?;
@@ -198,7 +229,11 @@ exit ||
                 // This only happens if someone powered on a host manually before we ingested it,
                 // which is unlikely but possible.
                 tracing::info!(interface = ?interface, "Request for PXE instructions for unknown interface, skipping PXE boot");
-                return Ok(UNKNOWN_HOST_INSTRUCTIONS.to_string());
+                return Ok(Self::get_fallback_instructions(
+                    fallback.unknown_host,
+                    target.interface_id,
+                    None,
+                ));
             };
 
             let (machine_type, console) = match target.arch {
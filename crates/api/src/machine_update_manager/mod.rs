@@ -26,6 +26,7 @@ use std::sync::atomic::Ordering;
 use std::time::Duration;
 
 use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, Utc};
 use db::work_lock_manager::WorkLockManagerHandle;
 use db::{DatabaseError, ObjectFilter, Transaction};
 use host_firmware::HostFirmwareUpdate;
@@ -40,7 +41,7 @@ use tokio::sync::oneshot;
 use self::dpu_nic_firmware::DpuNicFirmwareUpdate;
 use self::metrics::MachineUpdateManagerMetrics;
 use crate::CarbideResult;
-use crate::cfg::file::{CarbideConfig, MaxConcurrentUpdates};
+use crate::cfg::file::{CarbideConfig, MachineUpdater, MaxConcurrentUpdates};
 use crate::periodic_timer::PeriodicTimer;
 
 /// The MachineUpdateManager periodically runs [modules](machine_update_module::MachineUpdateModule) to initiate upgrades of machine components.
@@ -52,9 +53,12 @@ use crate::periodic_timer::PeriodicTimer;
 /// Config from [CarbideConfig]:
 /// * `max_concurrent_machine_updates` the maximum number of updates allowed across all modules
 /// * `machine_update_run_interval` how often the manager calls the modules to start updates
+/// * `machine_updater.maintenance_windows` when the modules may start updates
 pub struct MachineUpdateManager {
     database_connection: PgPool,
     max_concurrent_machine_updates: MaxConcurrentUpdates,
+    machine_updater: MachineUpdater,
+    clock: fn() -> DateTime<Utc>,
     run_interval: Duration,
     update_modules: Vec<Box<dyn MachineUpdateModule>>,
     metrics: Option<MachineUpdateManagerMetrics>,
@@ -77,6 +81,8 @@ impl MachineUpdateManager {
         MachineUpdateManager {
             database_connection,
             max_concurrent_machine_updates: config.max_concurrent_machine_updates(),
+            machine_updater: config.machine_updater.clone(),
+            clock: Utc::now,
             run_interval: Duration::from_secs(config.machine_update_run_interval.unwrap_or(300)),
             update_modules: modules,
             metrics: None,
@@ -85,6 +91,13 @@ impl MachineUpdateManager {
         }
     }
 
+    /// Replace the clock used to check maintenance windows.
+    #[cfg(test)]
+    pub fn with_clock(mut self, clock: fn() -> DateTime<Utc>) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Create a MachineUpdateManager with the default modules.
     pub fn new(
         database_connection: sqlx::PgPool,
@@ -110,6 +123,8 @@ impl MachineUpdateManager {
         MachineUpdateManager {
             database_connection,
             max_concurrent_machine_updates: config.max_concurrent_machine_updates(),
+            machine_updater: config.machine_updater.clone(),
+            clock: Utc::now,
             run_interval: Duration::from_secs(config.machine_update_run_interval.unwrap_or(300)),
             update_modules,
             metrics: Some(machine_update_metrics),
@@ -224,8 +239,18 @@ impl MachineUpdateManager {
             .max_concurrent_machine_updates
             .max_concurrent_updates(all_count, unhealthy_count)
             .unwrap_or(MachineUpdateManager::DEFAULT_MAX_CONCURRENT_MACHINE_UPDATES); // XXX
+
+        // Outside of maintenance windows, only track the updates already running. New
+        // updates start on the first iteration after a window opens.
+        let in_maintenance_window = self.machine_updater.in_maintenance_window((self.clock)());
+        if !in_maintenance_window {
+            tracing::info!("Outside of machine update maintenance windows, not starting updates");
+        }
+
         for update_module in self.update_modules.iter() {
-            if (current_updating_machines.len() as i32) >= max_concurrent_updates {
+            if !in_maintenance_window
+                || (current_updating_machines.len() as i32) >= max_concurrent_updates
+            {
                 break;
             }
             tracing::debug!("in progress: {:?}", current_updating_machines);
@@ -18,7 +18,7 @@
 #[cfg(test)]
 pub mod test_support {
     use std::sync::Arc;
-    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
 
     use librms::protos::rack_manager as rms;
     use librms::{RackManagerError, RmsApi};
@@ -29,6 +29,9 @@ pub mod test_support {
         fail_add_node: Arc<AtomicBool>,
         fail_inventory_get: Arc<AtomicBool>,
         registered_nodes: Arc<Mutex<Vec<rms::NodeInventoryInfo>>>,
+        firmware_update_calls: Arc<AtomicUsize>,
+        node_firmware_updates: Arc<Mutex<Vec<String>>>,
+        firmware_job_state: Arc<AtomicI32>,
     }
 
     impl Default for RmsSim {
@@ -37,6 +40,11 @@ pub mod test_support {
                 fail_add_node: Arc::new(AtomicBool::new(false)),
                 fail_inventory_get: Arc::new(AtomicBool::new(false)),
                 registered_nodes: Arc::new(Mutex::new(Vec::new())),
+                firmware_update_calls: Arc::new(AtomicUsize::new(0)),
+                node_firmware_updates: Arc::new(Mutex::new(Vec::new())),
+                firmware_job_state: Arc::new(AtomicI32::new(
+                    rms::FirmwareJobState::Completed as i32,
+                )),
             }
         }
     }
@@ -48,6 +56,9 @@ pub mod test_support {
                 fail_add_node: self.fail_add_node.clone(),
                 fail_inventory_get: self.fail_inventory_get.clone(),
                 registered_nodes: self.registered_nodes.clone(),
+                firmware_update_calls: self.firmware_update_calls.clone(),
+                node_firmware_updates: self.node_firmware_updates.clone(),
+                firmware_job_state: self.firmware_job_state.clone(),
             }))
         }
 
@@ -64,6 +75,24 @@ pub mod test_support {
         pub fn set_fail_inventory_get(&self, fail: bool) {
             self.fail_inventory_get.store(fail, Ordering::Relaxed);
         }
+
+        /// Number of async firmware updates that have been started, e.g.
+        /// by rack firmware apply.
+        pub fn firmware_update_calls(&self) -> usize {
+            self.firmware_update_calls.load(Ordering::Relaxed)
+        }
+
+        /// Ids of the nodes whose firmware was updated individually, rather
+        /// than by node type.
+        pub async fn node_firmware_updates(&self) -> Vec<String> {
+            self.node_firmware_updates.lock().await.clone()
+        }
+
+        /// Set the `FirmwareJobState` that `get_firmware_job_status` reports
+        /// for every job. Jobs are reported as completed by default.
+        pub fn set_firmware_job_state(&self, job_state: i32) {
+            self.firmware_job_state.store(job_state, Ordering::Relaxed);
+        }
     }
 
     #[derive(Debug, Clone)]
@@ -71,6 +100,9 @@ pub mod test_support {
         fail_add_node: Arc<AtomicBool>,
         fail_inventory_get: Arc<AtomicBool>,
         registered_nodes: Arc<Mutex<Vec<rms::NodeInventoryInfo>>>,
+        firmware_update_calls: Arc<AtomicUsize>,
+        node_firmware_updates: Arc<Mutex<Vec<String>>>,
+        firmware_job_state: Arc<AtomicI32>,
     }
 
     #[async_trait::async_trait]
@@ -282,21 +314,36 @@ pub mod test_support {
         }
         async fn update_node_firmware_async(
             &self,
-            _cmd: rms::UpdateNodeFirmwareRequest,
+            cmd: rms::UpdateNodeFirmwareRequest,
         ) -> Result<rms::UpdateNodeFirmwareResponse, RackManagerError> {
-            Ok(rms::UpdateNodeFirmwareResponse::default())
+            let call = self.firmware_update_calls.fetch_add(1, Ordering::Relaxed);
+            self.node_firmware_updates.lock().await.push(cmd.node_id);
+            Ok(rms::UpdateNodeFirmwareResponse {
+                status: rms::ReturnCode::Success as i32,
+                job_id: format!("mock-firmware-job-{call}"),
+                ..Default::default()
+            })
         }
         async fn update_firmware_by_node_type_async(
             &self,
             _cmd: rms::UpdateFirmwareByNodeTypeRequest,
         ) -> Result<rms::UpdateFirmwareByNodeTypeAsyncResponse, RackManagerError> {
-            Ok(rms::UpdateFirmwareByNodeTypeAsyncResponse::default())
+            let call = self.firmware_update_calls.fetch_add(1, Ordering::Relaxed);
+            Ok(rms::UpdateFirmwareByNodeTypeAsyncResponse {
+                status: rms::ReturnCode::Success as i32,
+                job_id: format!("mock-firmware-job-{call}"),
+                ..Default::default()
+            })
         }
         async fn get_firmware_job_status(
             &self,
-            _cmd: rms::GetFirmwareJobStatusRequest,
+            cmd: rms::GetFirmwareJobStatusRequest,
         ) -> Result<rms::GetFirmwareJobStatusResponse, RackManagerError> {
-            Ok(rms::GetFirmwareJobStatusResponse::default())
+            Ok(rms::GetFirmwareJobStatusResponse {
+                job_id: cmd.job_id,
+                job_state: self.firmware_job_state.load(Ordering::Relaxed),
+                ..Default::default()
+            })
         }
     }
 }
@@ -15,9 +15,11 @@
  * limitations under the License.
  */
 
+use std::collections::{BTreeSet, HashMap};
 use std::net::IpAddr;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
@@ -26,7 +28,8 @@ use forge_secrets::SecretsError;
 use forge_secrets::credentials::{
     BmcCredentialType, CredentialKey, CredentialReader, CredentialType, Credentials,
 };
-use libredfish::model::BootProgress;
+use libredfish::model::service_root::ServiceRoot;
+use libredfish::model::{BootProgress, BootSourceOverrideTarget};
 use libredfish::{Endpoint, PowerState, Redfish, RedfishError, SystemPowerControl};
 use mac_address::MacAddress;
 use model::machine::Machine;
@@ -553,6 +556,150 @@ pub async fn did_dpu_finish_booting(
     }
 }
 
+/// How long a fetched BMC service root is reused before it is requested again
+pub const SERVICE_ROOT_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Caches the service root of BMCs for a short amount of time.
+///
+/// Many operations start by fetching the service root of a BMC in order to discover
+/// the vendor or the location of other resources. Operations which run back to back
+/// against the same BMC can use this cache to avoid fetching it over and over again.
+pub struct ServiceRootCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, CachedServiceRoot>>,
+}
+
+struct CachedServiceRoot {
+    fetched_at: Instant,
+    service_root: Arc<ServiceRoot>,
+}
+
+impl Default for ServiceRootCache {
+    fn default() -> Self {
+        Self::new(SERVICE_ROOT_CACHE_TTL)
+    }
+}
+
+impl ServiceRootCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the service root of the BMC identified by `bmc`, using `client` to
+    /// fetch it if there is no cached entry younger than the TTL
+    pub async fn get(
+        &self,
+        bmc: &str,
+        client: &dyn Redfish,
+    ) -> Result<Arc<ServiceRoot>, RedfishError> {
+        if let Some(entry) = self.entries.lock().unwrap().get(bmc)
+            && entry.fetched_at.elapsed() < self.ttl
+        {
+            return Ok(entry.service_root.clone());
+        }
+
+        let service_root = Arc::new(client.get_service_root().await?);
+
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, entry| entry.fetched_at.elapsed() < self.ttl);
+        entries.insert(
+            bmc.to_string(),
+            CachedServiceRoot {
+                fetched_at: Instant::now(),
+                service_root: service_root.clone(),
+            },
+        );
+
+        Ok(service_root)
+    }
+}
+
+/// Summary of what a BMC advertises in its service root
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServiceRootCapabilities {
+    pub vendor: Option<String>,
+    /// Names of the resources linked from the service root, e.g. `Systems` or `UpdateService`
+    pub services: BTreeSet<String>,
+}
+
+impl ServiceRootCapabilities {
+    pub fn from_service_root(service_root: &ServiceRoot) -> Self {
+        let links = [
+            ("Systems", service_root.systems.is_some()),
+            ("Chassis", service_root.chassis.is_some()),
+            ("Managers", service_root.managers.is_some()),
+            ("UpdateService", service_root.update_service.is_some()),
+            ("AccountService", service_root.account_service.is_some()),
+            ("SessionService", service_root.session_service.is_some()),
+            ("EventService", service_root.event_service.is_some()),
+            (
+                "ComponentIntegrity",
+                service_root.component_integrity.is_some(),
+            ),
+        ];
+        let services = links
+            .into_iter()
+            .filter(|(_, linked)| *linked)
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        Self {
+            vendor: service_root.vendor.clone(),
+            services,
+        }
+    }
+}
+
+/// The `BootSourceOverrideTarget` a BMC reports once `target` is applied
+fn boot_override_target(target: &libredfish::Boot) -> BootSourceOverrideTarget {
+    match target {
+        libredfish::Boot::Pxe => BootSourceOverrideTarget::Pxe,
+        libredfish::Boot::HardDisk => BootSourceOverrideTarget::Hdd,
+        libredfish::Boot::UefiHttp => BootSourceOverrideTarget::UefiHttp,
+    }
+}
+
+/// Configures the system to boot once from `target`, and reads the system back to
+/// confirm that the BMC applied the override.
+///
+/// Some BMCs accept the request but silently ignore targets they don't support, and
+/// keep reporting another target we set. Any other report can't be verified and is
+/// trusted: no target, `None` once a one-time override was already consumed, or
+/// targets we never set, e.g. vendor specific ones.
+pub async fn boot_once_and_verify(
+    client: &dyn Redfish,
+    target: libredfish::Boot,
+) -> Result<(), RedfishError> {
+    let expected = boot_override_target(&target);
+    client.boot_once(target).await?;
+
+    let system = client.get_system().await?;
+    match system.boot.boot_source_override_target {
+        Some(reported) if reported == expected => Ok(()),
+        Some(
+            reported @ (BootSourceOverrideTarget::Pxe
+            | BootSourceOverrideTarget::Hdd
+            | BootSourceOverrideTarget::UefiHttp),
+        ) => Err(RedfishError::GenericError {
+            error: format!(
+                "BMC did not apply boot override: requested {expected:?}, BMC reports {reported:?}"
+            ),
+        }),
+        reported => {
+            tracing::warn!(
+                system = %system.id,
+                requested = ?expected,
+                reported = ?reported,
+                "Can't verify that the BMC applied the boot override"
+            );
+            Ok(())
+        }
+    }
+}
+
 // Some BMC implementation may return passwords in response body and
 // we can display them to user. This function is helper to remove
 // password leak for password-related refish functions.
@@ -658,6 +805,9 @@ pub mod test_support {
         users: HashMap<String, String>,
         fw_version: Arc<String>,
         secure_boot: AtomicBool,
+        service_root_requests: usize,
+        ignore_boot_override: bool,
+        reported_boot_override: Option<BootSourceOverrideTarget>,
     }
 
     #[derive(Debug)]
@@ -665,6 +815,7 @@ pub mod test_support {
         power: PowerState,
         lockdown: libredfish::EnabledDisabled,
         actions: Vec<RedfishSimAction>,
+        boot_override: Option<BootSourceOverrideTarget>,
     }
 
     impl Default for RedfishSimHostState {
@@ -673,6 +824,7 @@ pub mod test_support {
                 power: PowerState::default(),
                 lockdown: libredfish::EnabledDisabled::Disabled,
                 actions: Vec::default(),
+                boot_override: None,
             }
         }
     }
@@ -697,6 +849,21 @@ pub mod test_support {
             }
         }
 
+        /// Returns how often the service root was requested from any simulated BMC
+        pub fn service_root_requests(&self) -> usize {
+            self.state.lock().unwrap().service_root_requests
+        }
+
+        /// Makes simulated BMCs accept boot override requests without applying them
+        pub fn ignore_boot_overrides(&self) {
+            self.state.lock().unwrap().ignore_boot_override = true;
+        }
+
+        /// Makes simulated BMCs report `target` as their boot override, whatever was requested
+        pub fn report_boot_override(&self, target: BootSourceOverrideTarget) {
+            self.state.lock().unwrap().reported_boot_override = Some(target);
+        }
+
         pub fn actions_since(&self, timepoint: &RedfishSimTimepoint) -> RedfishSimActions {
             let state = self.state.lock().unwrap();
             RedfishSimActions {
@@ -861,7 +1028,12 @@ pub mod test_support {
             })
         }
 
-        async fn boot_once(&self, _target: libredfish::Boot) -> Result<(), RedfishError> {
+        async fn boot_once(&self, target: libredfish::Boot) -> Result<(), RedfishError> {
+            let mut state = self.state.lock().unwrap();
+            if !state.ignore_boot_override {
+                let host_state = state.hosts.get_mut(&self._host).unwrap();
+                host_state.boot_override = Some(super::boot_override_target(&target));
+            }
             Ok(())
         }
 
@@ -1108,7 +1280,7 @@ pub mod test_support {
         }
 
         async fn get_system(&self) -> Result<libredfish::model::ComputerSystem, RedfishError> {
-            Ok(libredfish::model::ComputerSystem {
+            let system = libredfish::model::ComputerSystem {
                 id: "Bluefield".to_string(),
                 boot_progress: Some(libredfish::model::BootProgress {
                     last_state: Some(libredfish::model::BootProgressTypes::OSRunning),
@@ -1116,7 +1288,22 @@ pub mod test_support {
                     oem_last_state: Some("OSRunning".to_string()),
                 }),
                 ..Default::default()
-            })
+            };
+
+            let boot_override = {
+                let state = self.state.lock().unwrap();
+                state
+                    .reported_boot_override
+                    .clone()
+                    .or_else(|| state.hosts[&self._host].boot_override.clone())
+            };
+            let Some(target) = boot_override else {
+                return Ok(system);
+            };
+            let mut system = serde_json::to_value(system).unwrap();
+            system["Boot"]["BootSourceOverrideTarget"] = serde_json::to_value(target).unwrap();
+            system["Boot"]["BootSourceOverrideEnabled"] = "Once".into();
+            Ok(serde_json::from_value(system).unwrap())
         }
 
         async fn get_secure_boot(
@@ -1264,6 +1451,7 @@ pub mod test_support {
         async fn get_service_root(
             &self,
         ) -> Result<libredfish::model::service_root::ServiceRoot, RedfishError> {
+            self.state.lock().unwrap().service_root_requests += 1;
             Ok(ServiceRoot {
                 vendor: Some("Nvidia".to_string()),
                 component_integrity: Some(ODataId {
@@ -2047,6 +2235,104 @@ mod tests {
         assert_eq!(PowerState::Off, client.get_power_state().await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_service_root_cache() {
+        let sim = RedfishSim::default();
+        let client = sim
+            .create_client("localhost", None, RedfishAuth::Anonymous, false)
+            .await
+            .unwrap();
+
+        let cache = ServiceRootCache::new(Duration::from_secs(60));
+        let first = cache.get("localhost", client.as_ref()).await.unwrap();
+        let second = cache.get("localhost", client.as_ref()).await.unwrap();
+        assert_eq!(sim.service_root_requests(), 1);
+        assert_eq!(first.vendor, second.vendor);
+
+        // Entries for other BMCs are fetched separately
+        cache.get("otherhost", client.as_ref()).await.unwrap();
+        assert_eq!(sim.service_root_requests(), 2);
+
+        let capabilities = ServiceRootCapabilities::from_service_root(&first);
+        assert_eq!(capabilities.vendor.as_deref(), Some("Nvidia"));
+        assert!(capabilities.services.contains("ComponentIntegrity"));
+    }
+
+    #[tokio::test]
+    async fn test_service_root_cache_expiry() {
+        let sim = RedfishSim::default();
+        let client = sim
+            .create_client("localhost", None, RedfishAuth::Anonymous, false)
+            .await
+            .unwrap();
+
+        let cache = ServiceRootCache::new(Duration::ZERO);
+        cache.get("localhost", client.as_ref()).await.unwrap();
+        cache.get("localhost", client.as_ref()).await.unwrap();
+        assert_eq!(sim.service_root_requests(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_boot_once_and_verify() {
+        let sim = RedfishSim::default();
+        let client = sim
+            .create_client("localhost", None, RedfishAuth::Anonymous, false)
+            .await
+            .unwrap();
+
+        boot_once_and_verify(client.as_ref(), libredfish::Boot::UefiHttp)
+            .await
+            .unwrap();
+        boot_once_and_verify(client.as_ref(), libredfish::Boot::Pxe)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_boot_once_and_verify_ignored_override() {
+        let sim = RedfishSim::default();
+        let client = sim
+            .create_client("localhost", None, RedfishAuth::Anonymous, false)
+            .await
+            .unwrap();
+
+        // The BMC reports the previous override after ignoring the new one
+        boot_once_and_verify(client.as_ref(), libredfish::Boot::Pxe)
+            .await
+            .unwrap();
+        sim.ignore_boot_overrides();
+
+        let err = boot_once_and_verify(client.as_ref(), libredfish::Boot::UefiHttp)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("requested UefiHttp, BMC reports Pxe"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_boot_once_and_verify_unverifiable_override() {
+        let sim = RedfishSim::default();
+        let client = sim
+            .create_client("localhost", None, RedfishAuth::Anonymous, false)
+            .await
+            .unwrap();
+
+        // The one-time override was already consumed
+        sim.report_boot_override(BootSourceOverrideTarget::None);
+        boot_once_and_verify(client.as_ref(), libredfish::Boot::UefiHttp)
+            .await
+            .unwrap();
+
+        // A target we never set
+        sim.report_boot_override(BootSourceOverrideTarget::UefiShell);
+        boot_once_and_verify(client.as_ref(), libredfish::Boot::UefiHttp)
+            .await
+            .unwrap();
+    }
+
     #[test]
     fn password_redact_from_error() {
         const PASSWORD: &str = "1234";
@@ -52,6 +52,8 @@ use crate::dynamic_settings::DynamicSettings;
 use crate::errors::CarbideError;
 use crate::firmware_downloader::FirmwareDownloader;
 use crate::handlers::machine_validation::apply_config_on_startup;
+use crate::handlers::pxe::PxeRateLimiter;
+use crate::handlers::rack_firmware::{RackFirmwareDownloadTracker, RackFirmwareRolloutTracker};
 use crate::ib::{self, IBFabricManager};
 use crate::ib_fabric_monitor::IbFabricMonitor;
 use crate::ipmitool::{IPMITool, IPMIToolImpl, IPMIToolTestImpl};
@@ -385,6 +387,9 @@ pub async fn start_api(
         credential_manager,
         database_connection: db_pool.clone(),
         dpu_health_log_limiter: LogLimiter::default(),
+        pxe_rate_limiter: PxeRateLimiter::new(carbide_config.pxe_rate_limit.clone()),
+        rack_firmware_downloads: RackFirmwareDownloadTracker::default(),
+        rack_firmware_rollouts: RackFirmwareRolloutTracker::default(),
         dynamic_settings,
         endpoint_explorer: bmc_explorer,
         eth_data,
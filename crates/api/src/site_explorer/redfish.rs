@@ -33,7 +33,10 @@ use model::site_explorer::{
 };
 use regex::Regex;
 
-use crate::redfish::{RedfishAuth, RedfishClientCreationError, RedfishClientPool, redact_password};
+use crate::redfish::{
+    RedfishAuth, RedfishClientCreationError, RedfishClientPool, ServiceRootCache,
+    ServiceRootCapabilities, redact_password,
+};
 
 const NOT_FOUND: u16 = 404;
 
@@ -42,12 +45,14 @@ const NOT_FOUND: u16 = 404;
 // Eventually, this file should only have code related to generating the site exploration report.
 pub struct RedfishClient {
     redfish_client_pool: Arc<dyn RedfishClientPool>,
+    service_root_cache: ServiceRootCache,
 }
 
 impl RedfishClient {
     pub fn new(redfish_client_pool: Arc<dyn RedfishClientPool>) -> Self {
         Self {
             redfish_client_pool,
+            service_root_cache: ServiceRootCache::default(),
         }
     }
 
@@ -107,7 +112,11 @@ impl RedfishClient {
             .await
             .map_err(map_redfish_client_creation_error)?;
 
-        let service_root = client.get_service_root().await.map_err(map_redfish_error)?;
+        let service_root = self
+            .service_root_cache
+            .get(&bmc_ip_address.to_string(), client.as_ref())
+            .await
+            .map_err(map_redfish_error)?;
 
         let Some(vendor) = service_root.vendor() else {
             tracing::info!("No vendor found for BMC at {bmc_ip_address}");
@@ -236,8 +245,17 @@ impl RedfishClient {
             .await
             .map_err(map_redfish_client_creation_error)?;
 
-        let service_root = client.get_service_root().await.map_err(map_redfish_error)?;
+        let service_root = self
+            .service_root_cache
+            .get(&bmc_ip_address.to_string(), client.as_ref())
+            .await
+            .map_err(map_redfish_error)?;
         let vendor = service_root.vendor().map(|v| v.into());
+        tracing::debug!(
+            %bmc_ip_address,
+            capabilities = ?ServiceRootCapabilities::from_service_root(&service_root),
+            "BMC service root capabilities"
+        );
 
         let manager = fetch_manager(client.as_ref())
             .await
@@ -482,7 +500,11 @@ impl RedfishClient {
             .await
             .map_err(map_redfish_client_creation_error)?;
 
-        let service_root = client.get_service_root().await.map_err(map_redfish_error)?;
+        let service_root = self
+            .service_root_cache
+            .get(&bmc_ip_address.to_string(), client.as_ref())
+            .await
+            .map_err(map_redfish_error)?;
         let system = client.get_system().await.map_err(map_redfish_error)?;
         let manager = client.get_manager().await.map_err(map_redfish_error)?;
         Ok(
@@ -3369,8 +3369,7 @@ impl DpuMachineStateHandler {
                 // guaranteed to have POSTed), it will loop through all the interfaces between
                 // IPv4, IPv6 so it may take a while.
                 //
-                dpu_redfish_client
-                    .boot_once(Boot::UefiHttp)
+                crate::redfish::boot_once_and_verify(dpu_redfish_client.as_ref(), Boot::UefiHttp)
                     .map_err(|e| StateHandlerError::RedfishError {
                         operation: "boot_once",
                         error: e,
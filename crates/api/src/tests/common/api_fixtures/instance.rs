@@ -125,6 +125,7 @@ impl<'a, 'b> TestInstanceBuilder<'a, 'b> {
                 config: Some(self.config),
                 metadata: self.metadata,
                 allow_unhealthy_machine: false,
+                idempotency_key: None,
             }))
             .await
             .expect("Create instance failed.")
@@ -92,6 +92,8 @@ use crate::cfg::file::{
     VpcPeeringPolicy, default_max_find_by_ids,
 };
 use crate::ethernet_virtualization::{EthVirtData, SiteFabricPrefixList};
+use crate::handlers::pxe::PxeRateLimiter;
+use crate::handlers::rack_firmware::{RackFirmwareDownloadTracker, RackFirmwareRolloutTracker};
 use crate::ib::{self, IBFabricManagerImpl, IBFabricManagerType};
 use crate::ib_fabric_monitor::IbFabricMonitor;
 use crate::ipmitool::IPMIToolTestImpl;
@@ -1102,6 +1104,7 @@ pub fn get_config() -> CarbideConfig {
             instance_autoreboot_period: None,
             max_concurrent_machine_updates_absolute: Some(10),
             max_concurrent_machine_updates_percent: None,
+            maintenance_windows: vec![],
         },
         max_find_by_ids: default_max_find_by_ids(),
         network_security_group: NetworkSecurityGroupConfig::default(),
@@ -1152,6 +1155,10 @@ pub fn get_config() -> CarbideConfig {
         rms_api_url: Some(
             SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080).to_string(),
         ),
+        rack_firmware_directory: std::env::temp_dir().join("carbide-rack-firmware"),
+        rack_firmware_preflight_downloads: false,
+        rack_firmware_max_concurrent_downloads: 4,
+        power_shelf_firmware_targets: HashMap::new(),
         spdm_state_controller: SpdmStateControllerConfig {
             controller: StateControllerConfig::default(),
         },
@@ -1164,6 +1171,8 @@ pub fn get_config() -> CarbideConfig {
         dpf: crate::cfg::file::DpfConfig::default(),
         x86_pxe_boot_url_override: None,
         arm_pxe_boot_url_override: None,
+        pxe_fallback: crate::cfg::file::PxeFallbackConfig::default(),
+        pxe_rate_limit: None,
         supernic_firmware_profiles: HashMap::default(),
     }
 }
@@ -1426,6 +1435,9 @@ pub async fn create_test_env_with_overrides(
         dynamic_settings: dyn_settings,
         endpoint_explorer: bmc_explorer,
         dpu_health_log_limiter: LogLimiter::default(),
+        pxe_rate_limiter: PxeRateLimiter::new(config.pxe_rate_limit.clone()),
+        rack_firmware_downloads: RackFirmwareDownloadTracker::default(),
+        rack_firmware_rollouts: RackFirmwareRolloutTracker::default(),
         scout_stream_registry: scout_stream::ConnectionRegistry::new(),
         rms_client: rms_sim.as_rms_client(),
         nmxm_pool: nmxm_sim.clone(),
@@ -82,6 +82,7 @@ pub struct InstanceAllocationRequest {
     pub instance_type_id: ::core::option::Option<::prost::alloc::string::String>,
     pub metadata: ::core::option::Option<::rpc::forge::Metadata>,
     pub allow_unhealthy_machine: bool,
+    pub idempotency_key: ::core::option::Option<::prost::alloc::string::String>,
 }
 
 // Reflection of rpc::forge::InstanceConfig. It should contain exactly
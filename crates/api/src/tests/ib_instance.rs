@@ -1052,6 +1052,7 @@ pub async fn try_allocate_instance(
                 labels: Vec::new(),
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await?;
 
@@ -74,7 +74,7 @@ use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use tonic::Request;
 
 use crate::cfg::file::VmaasConfig;
-use crate::instance::{allocate_instance, allocate_network};
+use crate::instance::{IDEMPOTENCY_KEY_WINDOW, allocate_instance, allocate_network};
 use crate::network_segment::allocate::PrefixAllocator;
 use crate::tests::common;
 use crate::tests::common::api_fixtures::instance::{
@@ -1645,6 +1645,7 @@ async fn test_can_not_create_instance_for_dpu(_: PgPoolOptions, options: PgConne
             labels: HashMap::new(),
         },
         allow_unhealthy_machine: false,
+        idempotency_key: None,
     };
 
     // Note: This also requests a background task in the DB for creating managed
@@ -1919,6 +1920,151 @@ async fn test_create_instance_with_allow_unhealthy_machine_true(
     assert_eq!(instance.id(), instance_id);
 }
 
+#[crate::sqlx_test]
+async fn test_allocate_instance_with_idempotency_key_returns_existing_instance(
+    _: PgPoolOptions,
+    options: PgConnectOptions,
+) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let (host_machine_id, _dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let allocate = || {
+        env.api.allocate_instance(
+            InstanceAllocationRequest::builder(false)
+                .machine_id(host_machine_id)
+                .config(
+                    InstanceConfig::default_tenant_and_os()
+                        .network(single_interface_network_config(segment_id)),
+                )
+                .idempotency_key("retry-key-1")
+                .tonic_request(),
+        )
+    };
+
+    let first = allocate()
+        .await
+        .expect("Create instance failed.")
+        .into_inner();
+    // The retry would otherwise fail, since the machine is already in use
+    let second = allocate()
+        .await
+        .expect("Retried create instance failed.")
+        .into_inner();
+
+    assert!(first.id.is_some());
+    assert_eq!(first.id, second.id);
+
+    let instances = env
+        .api
+        .find_instance_by_machine_id(tonic::Request::new(host_machine_id))
+        .await
+        .unwrap()
+        .into_inner()
+        .instances;
+    assert_eq!(instances.len(), 1);
+    assert_eq!(instances[0].id, first.id);
+}
+
+#[crate::sqlx_test]
+async fn test_allocate_instance_prunes_expired_idempotency_keys(
+    _: PgPoolOptions,
+    options: PgConnectOptions,
+) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let (host_machine_id, _dpu_machine_id) = create_managed_host(&env).await.into();
+
+    let record_key = |idempotency_key: &'static str, age: chrono::Duration| {
+        sqlx::query(
+            "INSERT INTO instance_idempotency_keys
+                (tenant_organization_id, idempotency_key, instance_id, created)
+            VALUES ('other-tenant', $1, gen_random_uuid(), $2)",
+        )
+        .bind(idempotency_key)
+        .bind(Utc::now() - age)
+        .execute(&env.pool)
+    };
+    record_key(
+        "expired-key",
+        IDEMPOTENCY_KEY_WINDOW + chrono::Duration::hours(1),
+    )
+    .await
+    .unwrap();
+    record_key("recent-key", chrono::Duration::hours(1))
+        .await
+        .unwrap();
+
+    env.api
+        .allocate_instance(
+            InstanceAllocationRequest::builder(false)
+                .machine_id(host_machine_id)
+                .config(
+                    InstanceConfig::default_tenant_and_os()
+                        .network(single_interface_network_config(segment_id)),
+                )
+                .idempotency_key("retry-key-1")
+                .tonic_request(),
+        )
+        .await
+        .expect("Create instance failed.");
+
+    let keys: Vec<String> = sqlx::query_scalar(
+        "SELECT idempotency_key FROM instance_idempotency_keys ORDER BY idempotency_key",
+    )
+    .fetch_all(&env.pool)
+    .await
+    .unwrap();
+    assert_eq!(keys, ["recent-key", "retry-key-1"]);
+}
+
+#[crate::sqlx_test]
+async fn test_find_stuck_instances(_: PgPoolOptions, options: PgConnectOptions) {
+    let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let (stuck_host_id, _) = create_managed_host(&env).await.into();
+    let (recent_host_id, _) = create_managed_host(&env).await.into();
+
+    let mut instance_ids = Vec::new();
+    for machine_id in [stuck_host_id, recent_host_id] {
+        let instance = env
+            .api
+            .allocate_instance(
+                InstanceAllocationRequest::builder(false)
+                    .machine_id(machine_id)
+                    .config(
+                        InstanceConfig::default_tenant_and_os()
+                            .network(single_interface_network_config(segment_id)),
+                    )
+                    .tonic_request(),
+            )
+            .await
+            .expect("Create instance failed.")
+            .into_inner();
+        instance_ids.push(instance.id.unwrap());
+    }
+    let stuck_instance_id = instance_ids[0];
+
+    // Neither instance is Ready, but only one was created long enough ago
+    sqlx::query("UPDATE instances SET requested = NOW() - INTERVAL '2 hours' WHERE id = $1")
+        .bind(stuck_instance_id)
+        .execute(&env.pool)
+        .await
+        .unwrap();
+
+    let stuck = db::instance::find_stuck(&env.pool, Utc::now() - chrono::Duration::hours(1))
+        .await
+        .unwrap();
+    assert_eq!(stuck.len(), 1);
+    assert_eq!(stuck[0].id, stuck_instance_id);
+    assert_eq!(stuck[0].machine_id, stuck_host_id);
+    assert!(Utc::now() - stuck[0].requested >= chrono::Duration::hours(2));
+    assert!(stuck[0].state.contains("assigned"));
+}
+
 #[crate::sqlx_test]
 async fn test_instance_phone_home(_: PgPoolOptions, options: PgConnectOptions) {
     let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
@@ -3214,6 +3360,7 @@ async fn test_network_details_migration(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap()
@@ -3294,6 +3441,7 @@ async fn test_network_details_migration(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap()
@@ -5393,6 +5541,7 @@ async fn test_allocate_instance_with_duplicate_extension_services(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await;
     println!("instance: {:?}", instance);
@@ -5891,6 +6040,7 @@ async fn test_allocate_instance_with_invalid_os_image(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await;
 
@@ -5952,6 +6102,7 @@ async fn test_allocate_instance_with_invalid_ib_partition(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await;
 
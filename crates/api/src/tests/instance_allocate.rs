@@ -222,6 +222,7 @@ async fn test_zero_dpu_instance_allocation_explicit_network_config(
             instance_id: None,
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }),
     )
     .await
@@ -307,6 +308,7 @@ async fn test_zero_dpu_instance_allocation_no_network_config(
             instance_id: None,
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }),
     )
     .await
@@ -401,6 +403,7 @@ async fn test_zero_dpu_instance_allocation_multi_segment_no_network_config(
             instance_id: None,
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }),
     )
     .await
@@ -526,6 +529,7 @@ async fn test_reject_single_dpu_instance_allocation_no_network_config(
             instance_id: None,
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }),
     )
     .await;
@@ -593,6 +597,7 @@ async fn test_reject_single_dpu_instance_allocation_host_inband_network_config(
             instance_id: None,
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }),
     )
     .await;
@@ -729,6 +734,7 @@ async fn test_reject_zero_dpu_instance_allocation_multiple_vpcs(
             instance_id: None,
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }),
     )
     .await;
@@ -797,6 +803,7 @@ async fn test_single_dpu_instance_allocation(
             instance_id: None,
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }),
     )
     .await
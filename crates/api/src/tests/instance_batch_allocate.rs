@@ -127,6 +127,7 @@ async fn test_batch_allocate_instances_rollback_on_failure(
                     labels: vec![],
                 }),
                 allow_unhealthy_machine: false,
+                idempotency_key: None,
             },
             build_test_instance_allocation_request(&env, &mh2, segment_id),
         ],
@@ -267,5 +268,6 @@ fn build_test_instance_allocation_request(
             labels: vec![],
         }),
         allow_unhealthy_machine: false,
+        idempotency_key: None,
     }
 }
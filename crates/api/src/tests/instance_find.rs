@@ -421,3 +421,75 @@ async fn test_find_instances_by_machine_id_none(pool: sqlx::PgPool) {
     assert!(response.is_ok(),);
     assert_eq!(response.unwrap().into_inner().instances.len(), 0);
 }
+
+#[crate::sqlx_test]
+async fn test_get_instance_detail(pool: sqlx::PgPool) {
+    let env = create_test_env(pool.clone()).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+    let mh = create_managed_host(&env).await;
+    let instance = mh
+        .instance_builer(&env)
+        .single_interface_network_config(segment_id)
+        .build()
+        .await;
+
+    let detail = env
+        .api
+        .get_instance_detail(tonic::Request::new(instance.id))
+        .await
+        .unwrap()
+        .into_inner();
+
+    // Fetch the same data using one call per object type
+    let expected_instance = env
+        .api
+        .find_instances_by_ids(tonic::Request::new(rpc::InstancesByIdsRequest {
+            instance_ids: vec![instance.id],
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .instances
+        .remove(0);
+    let expected_segments = env
+        .api
+        .find_network_segments_by_ids(tonic::Request::new(rpc::NetworkSegmentsByIdsRequest {
+            network_segments_ids: vec![segment_id],
+            include_history: false,
+            include_num_free_ips: false,
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .network_segments;
+    let expected_vpcs = env
+        .api
+        .find_vpcs_by_ids(tonic::Request::new(rpc::VpcsByIdsRequest {
+            vpc_ids: expected_segments
+                .iter()
+                .filter_map(|segment| segment.vpc_id)
+                .collect(),
+        }))
+        .await
+        .unwrap()
+        .into_inner()
+        .vpcs;
+
+    assert_eq!(detail.instance, Some(expected_instance));
+    assert_eq!(detail.network_segments, expected_segments);
+    assert_eq!(detail.vpcs.len(), 1);
+    assert_eq!(detail.vpcs, expected_vpcs);
+}
+
+#[crate::sqlx_test]
+async fn test_get_instance_detail_not_found(pool: sqlx::PgPool) {
+    let env = create_test_env(pool.clone()).await;
+
+    let instance_id: InstanceId = uuid::Uuid::new_v4().into();
+    let err = env
+        .api
+        .get_instance_detail(tonic::Request::new(instance_id))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+}
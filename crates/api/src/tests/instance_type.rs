@@ -463,6 +463,7 @@ async fn test_instance_type_delete(pool: sqlx::PgPool) -> Result<(), Box<dyn std
             }),
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap()
@@ -657,6 +658,7 @@ async fn test_instance_type_associate(
             }),
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap_err();
@@ -684,6 +686,7 @@ async fn test_instance_type_associate(
             }),
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap()
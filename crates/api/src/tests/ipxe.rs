@@ -18,7 +18,9 @@ use std::collections::HashMap;
 
 use carbide_uuid::machine::{MachineId, MachineInterfaceId};
 use chrono::Utc;
-use common::api_fixtures::{TestEnv, create_test_env};
+use common::api_fixtures::{
+    TestEnv, TestEnvOverrides, create_test_env, create_test_env_with_overrides, get_config,
+};
 use db::{self};
 use futures_util::FutureExt;
 use mac_address::MacAddress;
@@ -26,6 +28,7 @@ use model::machine::{DpuInitState, HostReprovisionState, MachineState, ManagedHo
 use rpc::forge::CloudInitInstructionsRequest;
 use rpc::forge::forge_server::Forge;
 
+use crate::cfg::file::{PxeFallbackConfig, PxeFallbackTemplate, PxeRateLimitConfig};
 use crate::tests::common;
 use crate::tests::common::api_fixtures::managed_host::ManagedHostConfig;
 use crate::tests::common::api_fixtures::site_explorer::MockExploredHost;
@@ -271,6 +274,140 @@ async fn test_pxe_when_dpu_is_not_ingested(pool: sqlx::PgPool) -> eyre::Result<(
     Ok(())
 }
 
+#[crate::sqlx_test]
+async fn test_pxe_fallback_templates(pool: sqlx::PgPool) {
+    let mut config = get_config();
+    config.pxe_fallback = PxeFallbackConfig {
+        unknown_host: PxeFallbackTemplate::ExitInstructions,
+        invalid_state: PxeFallbackTemplate::UnknownHost,
+        no_provisioning: PxeFallbackTemplate::ErrorInstructions,
+    };
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+
+    // Unknown host
+    let unknown_interface_id = common::api_fixtures::dpu::dpu_discover_dhcp(
+        &env,
+        &DPU_OOB_MAC_ADDRESS_POOL.allocate().to_string(),
+    )
+    .await;
+    let instructions = get_pxe_instructions(
+        &env,
+        unknown_interface_id,
+        rpc::forge::MachineArchitecture::X86,
+        None,
+    )
+    .await;
+    assert!(
+        instructions
+            .pxe_script
+            .contains(&format!("Interface ID: {unknown_interface_id}")),
+        "Actual script: {}",
+        instructions.pxe_script
+    );
+    assert!(instructions.pxe_script.contains(
+        "This state assumes an OS is provisioned and will exit into the OS in 5 seconds."
+    ));
+
+    let (host_id, dpu_id) = common::api_fixtures::create_managed_host(&env).await.into();
+    let mut txn = env.pool.begin().await.unwrap();
+    let interfaces = db::machine_interface::find_by_machine_ids(&mut txn, &[host_id, dpu_id])
+        .await
+        .unwrap();
+    txn.commit().await.unwrap();
+
+    // Invalid state
+    move_machine_to_needed_state(host_id, &ManagedHostState::ForceDeletion, &env.pool).await;
+    let instructions = get_pxe_instructions(
+        &env,
+        interfaces[&host_id][0].id,
+        rpc::forge::MachineArchitecture::X86,
+        None,
+    )
+    .await;
+    assert!(
+        instructions
+            .pxe_script
+            .contains("this is an unknown ARM host, not PXE booting"),
+        "Actual script: {}",
+        instructions.pxe_script
+    );
+
+    // No provisioning instructions
+    move_machine_to_needed_state(dpu_id, &ManagedHostState::Ready, &env.pool).await;
+    let instructions = get_pxe_instructions(
+        &env,
+        interfaces[&dpu_id][0].id,
+        rpc::forge::MachineArchitecture::Arm,
+        Some("Fake Bluefield".to_string()),
+    )
+    .await;
+    assert!(
+        instructions.pxe_script.contains("Current state: Ready"),
+        "Actual script: {}",
+        instructions.pxe_script
+    );
+    assert!(
+        instructions
+            .pxe_script
+            .contains("Could not continue boot due to invalid state")
+    );
+}
+
+#[crate::sqlx_test]
+async fn test_pxe_rate_limit(pool: sqlx::PgPool) {
+    let mut config = get_config();
+    config.pxe_rate_limit = Some(PxeRateLimitConfig {
+        max_requests: 3,
+        window: std::time::Duration::from_secs(3600),
+    });
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+    let (host_id, _dpu_id) = common::api_fixtures::create_managed_host(&env).await.into();
+    let mut txn = env.pool.begin().await.unwrap();
+    let host_interface_id = db::machine_interface::find_by_machine_ids(&mut txn, &[host_id])
+        .await
+        .unwrap()[&host_id][0]
+        .id;
+    txn.commit().await.unwrap();
+    move_machine_to_needed_state(
+        host_id,
+        &ManagedHostState::HostInit {
+            machine_state: MachineState::WaitingForDiscovery,
+        },
+        &env.pool,
+    )
+    .await;
+
+    for _ in 0..3 {
+        let instructions = get_pxe_instructions(
+            &env,
+            host_interface_id,
+            rpc::forge::MachineArchitecture::X86,
+            None,
+        )
+        .await;
+        assert!(instructions.pxe_script.contains("x86_64/scout.efi"));
+    }
+
+    // The host keeps rebooting into PXE and exceeds the limit
+    let instructions = get_pxe_instructions(
+        &env,
+        host_interface_id,
+        rpc::forge::MachineArchitecture::X86,
+        None,
+    )
+    .await;
+    assert!(
+        !instructions.pxe_script.contains("x86_64/scout.efi"),
+        "Actual script: {}",
+        instructions.pxe_script
+    );
+    assert!(
+        instructions
+            .pxe_script
+            .contains("Could not continue boot due to invalid state")
+    );
+}
+
 #[crate::sqlx_test]
 async fn test_pxe_host(pool: sqlx::PgPool) {
     let env = create_test_env(pool).await;
@@ -17,12 +17,13 @@
 use std::net::IpAddr;
 use std::str::FromStr;
 
+use carbide_uuid::machine::MachineId;
 use common::api_fixtures::dpu::create_dpu_machine;
 use common::api_fixtures::host::host_discover_dhcp;
 use common::api_fixtures::{FIXTURE_DHCP_RELAY_ADDRESS, create_managed_host, create_test_env};
 use itertools::Itertools;
 use mac_address::MacAddress;
-use model::hardware_info::HardwareInfo;
+use model::hardware_info::{BlockDevice, HardwareInfo, hardware_fingerprint};
 use rpc::forge::forge_server::Forge;
 use tonic::Request;
 
@@ -146,6 +147,74 @@ async fn test_reject_host_machine_with_disabled_tpm(
     Ok(())
 }
 
+#[crate::sqlx_test]
+async fn test_discover_machine_records_changed_hardware_fingerprint(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let host_config = env.managed_host_config();
+    let dpu_machine_id = create_dpu_machine(&env, &host_config).await;
+    let host_machine_interface_id = host_discover_dhcp(&env, &host_config, &dpu_machine_id).await;
+
+    let api = &env.api;
+    let pool = &env.pool;
+    let discover = move |hardware_info: HardwareInfo| {
+        api.discover_machine(tonic::Request::new(rpc::MachineDiscoveryInfo {
+            machine_interface_id: Some(host_machine_interface_id),
+            discovery_data: Some(rpc::DiscoveryData::Info(
+                rpc::DiscoveryInfo::try_from(hardware_info).unwrap(),
+            )),
+            create_machine: true,
+        }))
+    };
+    let stored_fingerprint = move |machine_id: MachineId| {
+        sqlx::query_scalar::<_, Option<String>>(
+            "SELECT hardware_fingerprint FROM machines WHERE id=$1",
+        )
+        .bind(machine_id)
+        .fetch_one(pool)
+    };
+
+    let hardware_info = HardwareInfo::from(&host_config);
+    let host_machine_id = discover(hardware_info.clone())
+        .await?
+        .into_inner()
+        .machine_id
+        .unwrap();
+    let fingerprint = hardware_fingerprint(&hardware_info);
+    assert_eq!(
+        stored_fingerprint(host_machine_id).await?,
+        Some(fingerprint.clone())
+    );
+
+    // Swapping a disk keeps the machine id but changes the stored fingerprint
+    let mut swapped = hardware_info.clone();
+    swapped.block_devices.push(BlockDevice {
+        model: "SwappedDisk".to_string(),
+        revision: "1.0".to_string(),
+        serial: "SWAPPED0001".to_string(),
+        device_type: "disk".to_string(),
+    });
+    let swapped_fingerprint = hardware_fingerprint(&swapped);
+    assert_ne!(swapped_fingerprint, fingerprint);
+
+    let machine_id = discover(swapped).await?.into_inner().machine_id.unwrap();
+    assert_eq!(machine_id, host_machine_id);
+    assert_eq!(
+        stored_fingerprint(host_machine_id).await?,
+        Some(swapped_fingerprint.clone())
+    );
+
+    // The previously stored fingerprint is what a discovery is compared against
+    let mut txn = env.pool.begin().await?;
+    let previous =
+        db::machine::update_hardware_fingerprint(&host_machine_id, &fingerprint, &mut txn).await?;
+    txn.rollback().await?;
+    assert_eq!(previous, Some(swapped_fingerprint));
+
+    Ok(())
+}
+
 #[crate::sqlx_test]
 async fn test_discover_2_managed_hosts(
     pool: sqlx::PgPool,
@@ -21,6 +21,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use carbide_uuid::machine::MachineId;
+use chrono::{DateTime, NaiveTime, TimeZone, Utc};
 use common::api_fixtures::create_test_env;
 use figment::Figment;
 use figment::providers::{Format, Toml};
@@ -32,7 +33,7 @@ use model::machine_update_module::{
 use sqlx::PgConnection;
 
 use crate::CarbideResult;
-use crate::cfg::file::CarbideConfig;
+use crate::cfg::file::{CarbideConfig, MaintenanceWindow};
 use crate::machine_update_manager::MachineUpdateManager;
 use crate::machine_update_manager::machine_update_module::{
     MachineUpdateModule, create_host_update_health_report,
@@ -151,6 +152,62 @@ async fn test_max_outstanding_updates(
     Ok(())
 }
 
+#[crate::sqlx_test]
+async fn test_updates_only_start_in_maintenance_window(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    create_managed_host(&env).await;
+
+    let mut config: CarbideConfig = Figment::new()
+        .merge(Toml::file(format!("{TEST_DATA_DIR}/full_config.toml")))
+        .extract()
+        .unwrap();
+    // Open from 22:00 to 02:00 UTC
+    config.machine_updater.maintenance_windows = vec![MaintenanceWindow {
+        start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+        end: NaiveTime::from_hms_opt(2, 0, 0).unwrap(),
+    }];
+    let config = Arc::new(config);
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 12, 12, 0, 0).unwrap()
+    }
+    fn after_midnight() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 13, 1, 30, 0).unwrap()
+    }
+
+    // Closed window: completed updates are still cleared, but nothing new starts
+    let module = Box::new(TestUpdateModule::new(vec![], HashSet::default()));
+    MachineUpdateManager::new_with_modules(
+        env.pool.clone(),
+        config.clone(),
+        vec![module.clone()],
+        env.api.work_lock_manager_handle.clone(),
+    )
+    .with_clock(noon)
+    .run_single_iteration()
+    .await?;
+    assert_eq!(module.get_clear_completed_updates_called(), 1);
+    assert_eq!(module.get_start_updates_called(), 0);
+
+    // Open window
+    let module = Box::new(TestUpdateModule::new(vec![], HashSet::default()));
+    MachineUpdateManager::new_with_modules(
+        env.pool.clone(),
+        config,
+        vec![module.clone()],
+        env.api.work_lock_manager_handle.clone(),
+    )
+    .with_clock(after_midnight)
+    .run_single_iteration()
+    .await?;
+    assert_eq!(module.get_clear_completed_updates_called(), 1);
+    assert_eq!(module.get_start_updates_called(), 1);
+
+    Ok(())
+}
+
 #[crate::sqlx_test]
 async fn test_remove_machine_update_markers(
     pool: sqlx::PgPool,
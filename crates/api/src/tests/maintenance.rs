@@ -95,6 +95,7 @@ async fn test_maintenance(db_pool: sqlx::PgPool) -> Result<(), eyre::Report> {
             labels: Vec::new(),
         }),
         allow_unhealthy_machine: false,
+        idempotency_key: None,
     };
     match env.api.allocate_instance(tonic::Request::new(req)).await {
         Ok(_) => {
@@ -167,6 +168,7 @@ async fn test_maintenance(db_pool: sqlx::PgPool) -> Result<(), eyre::Report> {
             labels: Vec::new(),
         }),
         allow_unhealthy_machine: false,
+        idempotency_key: None,
     };
     env.api.allocate_instance(tonic::Request::new(req)).await?;
 
@@ -213,6 +215,7 @@ async fn test_maintenance_multi_dpu(db_pool: sqlx::PgPool) -> Result<(), eyre::R
             labels: Vec::new(),
         }),
         allow_unhealthy_machine: false,
+        idempotency_key: None,
     };
     match env.api.allocate_instance(tonic::Request::new(req)).await {
         Ok(_) => {
@@ -280,6 +283,7 @@ async fn test_maintenance_multi_dpu(db_pool: sqlx::PgPool) -> Result<(), eyre::R
             labels: Vec::new(),
         }),
         allow_unhealthy_machine: false,
+        idempotency_key: None,
     };
     env.api.allocate_instance(tonic::Request::new(req)).await?;
 
@@ -927,6 +927,7 @@ async fn test_network_security_group_delete(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap()
@@ -1225,6 +1226,7 @@ async fn test_network_security_group_propagation_impl(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap();
@@ -1457,6 +1459,7 @@ async fn test_network_security_group_propagation_impl(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap();
@@ -1816,6 +1819,7 @@ async fn test_network_security_group_get_attachments(
                 labels: vec![],
             }),
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         }))
         .await
         .unwrap();
@@ -15,14 +15,43 @@
  * limitations under the License.
  */
 
-use common::api_fixtures::create_test_env;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use carbide_uuid::power_shelf::PowerShelfId;
+use carbide_uuid::rack::RackId;
+use common::api_fixtures::site_explorer::TestRackDbBuilder;
+use common::api_fixtures::{
+    TestEnv, TestEnvOverrides, create_test_env, create_test_env_with_overrides, get_config,
+};
 use db::rack_firmware::RackFirmware as DbRackFirmware;
+use librms::protos::rack_manager::FirmwareJobState;
+use model::rack::RackConfig;
 use rpc::forge::{
-    RackFirmwareCreateRequest, RackFirmwareDeleteRequest, RackFirmwareGetRequest,
-    RackFirmwareListRequest,
+    FirmwareJobNodeResult, RackFirmwareApplyRequest, RackFirmwareBulkApplyRequest,
+    RackFirmwareBulkApplyResponse, RackFirmwareBulkApplyStatusRequest,
+    RackFirmwareCachePurgeRequest, RackFirmwareCompareRequest, RackFirmwareComponentChangeType,
+    RackFirmwareComponentDiff, RackFirmwareCreateRequest, RackFirmwareDeleteRequest,
+    RackFirmwareDownloadEvent, RackFirmwareDownloadEventType, RackFirmwareDownloadPreviewRequest,
+    RackFirmwareGetRequest, RackFirmwareJobStatusRequest, RackFirmwareListRequest,
+    RackFirmwareRackApplyStatus, RackFirmwareRebuildLookupTableRequest,
 };
 use rpc::protos::forge::forge_server::Forge;
-
+use sha2::{Digest, Sha256};
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::BroadcastStream;
+
+use crate::handlers::rack_firmware::{
+    ARTIFACTORY_SHA256_HEADER, CHECKSUM_INDEX_FILENAME, CachedFileChecksum, DownloadProgress,
+    FileDownloadReport, FirmwareLocation, HASH_CHUNK_SIZE, ParsedFirmwareComponents,
+    RackFirmwareDownloadTracker, RmsFirmwareJobResult, build_firmware_lookup_table, download_files,
+    download_firmware_files, download_single_file, find_firmware_components_for_device,
+    order_firmware_components_for_flashing, parse_firmware_job_result, parse_rack_firmware_json,
+    part_file_path, remove_stale_part_files, sha256_file,
+};
 use crate::tests::common;
 
 /// Helper function to create a valid rack firmware JSON config
@@ -105,6 +134,7 @@ async fn test_create_rack_firmware(pool: sqlx::PgPool) -> Result<(), Box<dyn std
     let request = tonic::Request::new(RackFirmwareCreateRequest {
         config_json: config_json.clone(),
         artifactory_token: "test-token-123".to_string(),
+        labels: HashMap::new(),
     });
 
     let response = env.api.create_rack_firmware(request).await?;
@@ -133,6 +163,139 @@ async fn test_create_rack_firmware(pool: sqlx::PgPool) -> Result<(), Box<dyn std
     Ok(())
 }
 
+// ============================================================================
+// PREVIEW TESTS
+// ============================================================================
+
+#[crate::sqlx_test()]
+async fn test_preview_rack_firmware_downloads(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    let firmware_id = "preview-firmware-001";
+    let config_json = serde_json::json!({
+        "Id": firmware_id,
+        "BoardSKUs": [
+            {
+                "SKUID": "sku-001",
+                "Type": "ComputeTray",
+                "Components": {
+                    "Firmware": [
+                        {
+                            "Component": "BMC",
+                            "Type": "Prod",
+                            "Locations": [
+                                {
+                                    "Location": "artifactory.example.com/bmc/bmc-2.0.0.fwpkg",
+                                    "LocationType": "Artifactory",
+                                    "Type": "Firmware",
+                                    "Size": 1024,
+                                    "SHA256": "ABCDEF"
+                                },
+                                {
+                                    "Location": "artifactory.example.com/bmc/bmc-2.0.0.crt",
+                                    "LocationType": "Artifactory",
+                                    "Type": "Certificate"
+                                }
+                            ]
+                        },
+                        {
+                            "Component": "PSU",
+                            "Type": "Dev",
+                            "Locations": [
+                                {
+                                    "Location": "artifactory.example.com/psu/psu-1.5.0.bin",
+                                    "LocationType": "Artifactory",
+                                    "Type": "Firmware"
+                                }
+                            ]
+                        }
+                    ]
+                }
+            },
+            {
+                "SKUID": "sku-002",
+                "Type": "ComputeTray",
+                "Components": {
+                    "Firmware": [
+                        {
+                            "Component": "BMC",
+                            "Locations": [
+                                {
+                                    "Location": "artifactory.example.com/bmc/bmc-2.0.0.fwpkg",
+                                    "LocationType": "Artifactory",
+                                    "Type": "Firmware"
+                                }
+                            ]
+                        }
+                    ]
+                }
+            }
+        ]
+    })
+    .to_string();
+
+    let preview = env
+        .api
+        .preview_rack_firmware_downloads(tonic::Request::new(RackFirmwareDownloadPreviewRequest {
+            config_json,
+        }))
+        .await?
+        .into_inner();
+
+    // The certificate is not downloaded, and the BMC package shared by both
+    // board SKUs is listed once
+    let urls: Vec<&str> = preview.files.iter().map(|f| f.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        [
+            "artifactory.example.com/bmc/bmc-2.0.0.fwpkg",
+            "artifactory.example.com/psu/psu-1.5.0.bin",
+        ]
+    );
+
+    let bmc = &preview.files[0];
+    assert_eq!(bmc.filename, "bmc-2.0.0.fwpkg");
+    assert_eq!(bmc.location_type, "Artifactory");
+    assert_eq!(bmc.components, ["BMC"]);
+    assert_eq!(bmc.firmware_types, ["prod"]);
+    assert_eq!(bmc.size, Some(1024));
+    assert_eq!(bmc.sha256.as_deref(), Some("abcdef"));
+
+    let psu = &preview.files[1];
+    assert_eq!(psu.components, ["PSU"]);
+    assert_eq!(psu.firmware_types, ["dev"]);
+    assert_eq!(psu.size, None);
+
+    // Nothing is stored
+    assert!(
+        DbRackFirmware::find_by_id(&env.pool, firmware_id)
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_preview_rack_firmware_downloads_invalid_config(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    let err = env
+        .api
+        .preview_rack_firmware_downloads(tonic::Request::new(RackFirmwareDownloadPreviewRequest {
+            config_json: r#"{"Id": "no-board-skus"}"#.to_string(),
+        }))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+    Ok(())
+}
+
 // ============================================================================
 // GET TESTS
 // ============================================================================
@@ -148,6 +311,7 @@ async fn test_get_rack_firmware(pool: sqlx::PgPool) -> Result<(), Box<dyn std::e
     let create_request = tonic::Request::new(RackFirmwareCreateRequest {
         config_json: config_json.clone(),
         artifactory_token: "test-token".to_string(),
+        labels: HashMap::new(),
     });
     env.api.create_rack_firmware(create_request).await?;
 
@@ -180,6 +344,8 @@ async fn test_list_rack_firmware_empty(
 
     let request = tonic::Request::new(RackFirmwareListRequest {
         only_available: false,
+        firmware_type: None,
+        label_selector: HashMap::new(),
     });
 
     let response = env.api.list_rack_firmware(request).await?;
@@ -204,6 +370,7 @@ async fn test_list_rack_firmware_multiple(
         let request = tonic::Request::new(RackFirmwareCreateRequest {
             config_json,
             artifactory_token: format!("test-token-{}", i),
+            labels: HashMap::new(),
         });
         env.api.create_rack_firmware(request).await?;
     }
@@ -211,6 +378,8 @@ async fn test_list_rack_firmware_multiple(
     // List all
     let request = tonic::Request::new(RackFirmwareListRequest {
         only_available: false,
+        firmware_type: None,
+        label_selector: HashMap::new(),
     });
 
     let response = env.api.list_rack_firmware(request).await?;
@@ -226,6 +395,94 @@ async fn test_list_rack_firmware_multiple(
     Ok(())
 }
 
+#[crate::sqlx_test()]
+async fn test_list_rack_firmware_by_label_selector(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let labels = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+
+    let mut txn = env.pool.begin().await?;
+    for (id, config_labels) in [
+        (
+            "label-canary-approved",
+            labels(&[("channel", "canary"), ("approved-by", "ops")]),
+        ),
+        ("label-canary", labels(&[("channel", "canary")])),
+        ("label-stable", labels(&[("channel", "stable")])),
+        ("label-none", labels(&[])),
+    ] {
+        DbRackFirmware::create(
+            &mut txn,
+            id,
+            serde_json::from_str(&create_valid_rack_firmware_json(id))?,
+            None,
+            &config_labels,
+        )
+        .await?;
+    }
+
+    let ids = |configs: Vec<DbRackFirmware>| -> Vec<String> {
+        let mut ids: Vec<String> = configs.into_iter().map(|c| c.id).collect();
+        ids.sort();
+        ids
+    };
+
+    let all = DbRackFirmware::list_all(&mut txn, false, None, &HashMap::new()).await?;
+    assert_eq!(all.len(), 4);
+
+    let canary =
+        DbRackFirmware::list_all(&mut txn, false, None, &labels(&[("channel", "canary")])).await?;
+    assert_eq!(ids(canary), ["label-canary", "label-canary-approved"]);
+
+    let approved_canary = DbRackFirmware::list_all(
+        &mut txn,
+        false,
+        None,
+        &labels(&[("channel", "canary"), ("approved-by", "ops")]),
+    )
+    .await?;
+    assert_eq!(ids(approved_canary), ["label-canary-approved"]);
+
+    let missing =
+        DbRackFirmware::list_all(&mut txn, false, None, &labels(&[("channel", "beta")])).await?;
+    assert!(missing.is_empty());
+
+    // Labels can be replaced after creation
+    let updated =
+        DbRackFirmware::set_labels(&mut txn, "label-stable", &labels(&[("channel", "canary")]))
+            .await?;
+    assert_eq!(updated.labels, labels(&[("channel", "canary")]));
+    let canary =
+        DbRackFirmware::list_all(&mut txn, false, None, &labels(&[("channel", "canary")])).await?;
+    assert_eq!(
+        ids(canary),
+        ["label-canary", "label-canary-approved", "label-stable"]
+    );
+    txn.commit().await?;
+
+    // The label selector is applied by the API as well
+    let list = env
+        .api
+        .list_rack_firmware(tonic::Request::new(RackFirmwareListRequest {
+            only_available: false,
+            firmware_type: None,
+            label_selector: labels(&[("approved-by", "ops")]),
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(list.configs.len(), 1);
+    assert_eq!(list.configs[0].id, "label-canary-approved");
+    assert_eq!(list.configs[0].labels.get("channel").unwrap(), "canary");
+
+    Ok(())
+}
+
 // ============================================================================
 // DELETE TESTS
 // ============================================================================
@@ -241,6 +498,7 @@ async fn test_delete_rack_firmware(pool: sqlx::PgPool) -> Result<(), Box<dyn std
     let create_request = tonic::Request::new(RackFirmwareCreateRequest {
         config_json,
         artifactory_token: "test-token".to_string(),
+        labels: HashMap::new(),
     });
     env.api.create_rack_firmware(create_request).await?;
 
@@ -278,6 +536,7 @@ async fn test_rack_firmware_full_lifecycle(
     let create_request = tonic::Request::new(RackFirmwareCreateRequest {
         config_json: config_json.clone(),
         artifactory_token: "test-token".to_string(),
+        labels: HashMap::new(),
     });
     let create_response = env.api.create_rack_firmware(create_request).await?;
     let created_firmware = create_response.into_inner();
@@ -295,6 +554,8 @@ async fn test_rack_firmware_full_lifecycle(
     // 3. List (should contain our firmware)
     let list_request = tonic::Request::new(RackFirmwareListRequest {
         only_available: false,
+        firmware_type: None,
+        label_selector: HashMap::new(),
     });
     let list_response = env.api.list_rack_firmware(list_request).await?;
     let list = list_response.into_inner();
@@ -441,6 +702,7 @@ async fn test_rack_firmware_with_multiple_components(
     let request = tonic::Request::new(RackFirmwareCreateRequest {
         config_json,
         artifactory_token: "test-token".to_string(),
+        labels: HashMap::new(),
     });
 
     let response = env.api.create_rack_firmware(request).await?;
@@ -469,3 +731,1852 @@ async fn test_rack_firmware_with_multiple_components(
 
     Ok(())
 }
+
+#[crate::sqlx_test()]
+async fn test_rebuild_rack_firmware_lookup_table(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "rebuild-test-001";
+
+    // Config of a GB200 compute tray
+    let config = |id: &str| {
+        serde_json::json!({
+            "Id": id,
+            "BoardSKUs": [
+                {
+                    "SKUID": "699-24764-0001-TS1",
+                    "Name": "GB200 Compute Tray",
+                    "Type": "ComputeTray",
+                    "Components": {
+                        "Firmware": [
+                            {
+                                "Component": "BMC",
+                                "Bundle": "P4975",
+                                "Version": "25.01",
+                                "Type": "Prod",
+                                "Locations": [
+                                    {
+                                        "Location": "artifactory.example.com/bmc/bmc-25.01.fwpkg",
+                                        "LocationType": "Artifactory",
+                                        "Type": "Firmware"
+                                    }
+                                ]
+                            }
+                        ]
+                    }
+                }
+            ]
+        })
+    };
+    // A lookup table built before compute trays were supported
+    let outdated_lookup_table = serde_json::json!({ "devices": {} });
+
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::create(
+        &mut txn,
+        firmware_id,
+        config(firmware_id),
+        None,
+        &HashMap::new(),
+    )
+    .await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    DbRackFirmware::set_parsed_components(&mut txn, firmware_id, outdated_lookup_table).await?;
+    txn.commit().await?;
+
+    let rebuild = |id: &str| {
+        env.api
+            .rebuild_rack_firmware_lookup_table(tonic::Request::new(
+                RackFirmwareRebuildLookupTableRequest { id: id.to_string() },
+            ))
+    };
+    let assert_rebuilt = |parsed_components: &serde_json::Value| {
+        let bmc = &parsed_components["devices"]["Compute Node"]["BMC_prod"];
+        assert_eq!(bmc["filename"], "bmc-25.01.fwpkg");
+        assert_eq!(bmc["target"], "FW_BMC_0");
+    };
+
+    // The lookup table is rebuilt from the config, as often as needed
+    let firmware = rebuild(firmware_id).await?.into_inner();
+    assert_rebuilt(&serde_json::from_str(&firmware.parsed_components)?);
+    let firmware = rebuild(firmware_id).await?.into_inner();
+    assert_rebuilt(&serde_json::from_str(&firmware.parsed_components)?);
+
+    // Until its files are downloaded, a config has no lookup table to rebuild
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::create(
+        &mut txn,
+        "rebuild-test-002",
+        config("rebuild-test-002"),
+        None,
+        &HashMap::new(),
+    )
+    .await?;
+    txn.commit().await?;
+    let err = rebuild("rebuild-test-002").await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+    let err = rebuild("rebuild-test-unknown").await.unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_compare_rack_firmware(pool: sqlx::PgPool) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+
+    let entry = |component: &str, version: &str| {
+        serde_json::json!({
+            "filename": format!("{component}-{version}.fwpkg"),
+            "target": component,
+            "component": component,
+            "bundle": "bundle",
+            "firmware_type": "prod",
+            "version": version,
+            "subcomponents": []
+        })
+    };
+    let canary = serde_json::json!({
+        "devices": {
+            "Compute Node": {
+                "BMC_prod": entry("BMC", "25.01"),
+                "BIOS_prod": entry("BIOS", "1.2.0"),
+            },
+            "Switch Tray": {
+                "NVOS_prod": entry("NVOS", "4.0.0"),
+            }
+        }
+    });
+    let prod = serde_json::json!({
+        "devices": {
+            "Compute Node": {
+                "BMC_prod": entry("BMC", "25.04"),
+                "BIOS_prod": entry("BIOS", "1.2.0"),
+                "HMC_prod": entry("HMC", "3.1.0"),
+            }
+        }
+    });
+
+    let mut txn = env.pool.begin().await?;
+    for (id, lookup_table) in [("compare-canary", canary), ("compare-prod", prod)] {
+        DbRackFirmware::create(
+            &mut txn,
+            id,
+            serde_json::from_str(&create_valid_rack_firmware_json(id))?,
+            Some(lookup_table),
+            &HashMap::new(),
+        )
+        .await?;
+    }
+    txn.commit().await?;
+
+    let compare = |base_id: &str, target_id: &str| {
+        env.api
+            .compare_rack_firmware(tonic::Request::new(RackFirmwareCompareRequest {
+                base_id: base_id.to_string(),
+                target_id: target_id.to_string(),
+            }))
+    };
+
+    let diff = compare("compare-canary", "compare-prod")
+        .await?
+        .into_inner();
+    assert_eq!(diff.base_id, "compare-canary");
+    assert_eq!(diff.target_id, "compare-prod");
+    assert_eq!(
+        diff.components,
+        [
+            RackFirmwareComponentDiff {
+                device_type: "Compute Node".to_string(),
+                component: "BMC_prod".to_string(),
+                change_type: RackFirmwareComponentChangeType::ComponentVersionChanged as i32,
+                base_version: Some("25.01".to_string()),
+                target_version: Some("25.04".to_string()),
+            },
+            RackFirmwareComponentDiff {
+                device_type: "Compute Node".to_string(),
+                component: "HMC_prod".to_string(),
+                change_type: RackFirmwareComponentChangeType::ComponentAdded as i32,
+                base_version: None,
+                target_version: Some("3.1.0".to_string()),
+            },
+            RackFirmwareComponentDiff {
+                device_type: "Switch Tray".to_string(),
+                component: "NVOS_prod".to_string(),
+                change_type: RackFirmwareComponentChangeType::ComponentRemoved as i32,
+                base_version: Some("4.0.0".to_string()),
+                target_version: None,
+            },
+        ]
+    );
+
+    let diff = compare("compare-prod", "compare-prod").await?.into_inner();
+    assert!(diff.components.is_empty());
+
+    let err = compare("compare-canary", "compare-unknown")
+        .await
+        .expect_err("config does not exist");
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_purge_orphaned_rack_firmware_cache(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Other tests share the default cache root, so use one of our own
+    let cache_root = temp_dir::TempDir::new()?;
+    let mut config = get_config();
+    config.rack_firmware_directory = cache_root.path().to_path_buf();
+    let env = create_test_env_with_overrides(pool, TestEnvOverrides::with_config(config)).await;
+
+    let live_id = "purge-live";
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::create(
+        &mut txn,
+        live_id,
+        serde_json::from_str(&create_valid_rack_firmware_json(live_id))?,
+        None,
+        &HashMap::new(),
+    )
+    .await?;
+    txn.commit().await?;
+
+    let live_dir = cache_root.path().join(live_id);
+    let orphan_dir = cache_root.path().join("purge-deleted");
+    for dir in [&live_dir, &orphan_dir] {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("bmc.fwpkg"), b"firmware")?;
+    }
+
+    let purge = |dry_run: bool| {
+        env.api
+            .purge_orphaned_rack_firmware_cache(tonic::Request::new(
+                RackFirmwareCachePurgeRequest { dry_run },
+            ))
+    };
+
+    let response = purge(true).await?.into_inner();
+    assert_eq!(response.orphaned_directories, vec!["purge-deleted"]);
+    assert!(response.dry_run);
+    assert!(orphan_dir.exists(), "dry run must not remove anything");
+
+    let response = purge(false).await?.into_inner();
+    assert_eq!(response.orphaned_directories, vec!["purge-deleted"]);
+    assert!(!orphan_dir.exists());
+    assert!(live_dir.join("bmc.fwpkg").exists());
+
+    let response = purge(false).await?.into_inner();
+    assert!(response.orphaned_directories.is_empty());
+
+    Ok(())
+}
+
+// ============================================================================
+// APPLY TESTS
+// ============================================================================
+
+/// Creates a rack with a power shelf and a firmware config whose lookup table has Prod and Dev
+/// power shelf firmware, along with the downloaded files. The config is not marked as available.
+async fn create_rack_and_firmware(
+    env: &TestEnv,
+    firmware_id: &str,
+) -> Result<RackId, Box<dyn std::error::Error>> {
+    let rack_id = create_rack_with_power_shelf(env).await?;
+    let mut txn = env.pool.begin().await?;
+    let psu_entry = |firmware_type: &str| {
+        serde_json::json!({
+            "filename": format!("psu_{firmware_type}.fwpkg"),
+            "target": "PSU",
+            "component": "PSU",
+            "bundle": "psu-bundle-v1.5",
+            "firmware_type": firmware_type,
+            "version": "1.5.0",
+            "subcomponents": []
+        })
+    };
+    let lookup_table = serde_json::json!({
+        "devices": {
+            "Power Shelf": {
+                "PSU_prod": psu_entry("prod"),
+                "PSU_dev": psu_entry("dev"),
+            }
+        }
+    });
+    DbRackFirmware::create(
+        &mut txn,
+        firmware_id,
+        serde_json::from_str(&create_valid_rack_firmware_json(firmware_id))?,
+        Some(lookup_table),
+        &HashMap::new(),
+    )
+    .await?;
+    txn.commit().await?;
+
+    let firmware_dir = firmware_dir(env, firmware_id);
+    std::fs::create_dir_all(&firmware_dir)?;
+    for firmware_type in ["prod", "dev"] {
+        std::fs::write(
+            firmware_dir.join(format!("psu_{firmware_type}.fwpkg")),
+            b"firmware",
+        )?;
+    }
+
+    Ok(rack_id)
+}
+
+/// Creates a rack with a single power shelf
+async fn create_rack_with_power_shelf(env: &TestEnv) -> Result<RackId, Box<dyn std::error::Error>> {
+    let mut txn = env.pool.begin().await?;
+    let rack_id = TestRackDbBuilder::new().persist(&mut txn).await?;
+    let config = RackConfig {
+        compute_trays: vec![],
+        power_shelves: vec![PowerShelfId::from(uuid::Uuid::new_v4())],
+        expected_compute_trays: vec![],
+        expected_power_shelves: vec![],
+    };
+    db::rack::update(&mut txn, rack_id, &config).await?;
+    txn.commit().await?;
+    Ok(rack_id)
+}
+
+/// Directory the files of `firmware_id` are downloaded to
+fn firmware_dir(env: &TestEnv, firmware_id: &str) -> std::path::PathBuf {
+    env.config.rack_firmware_directory.join(firmware_id)
+}
+
+#[crate::sqlx_test()]
+async fn test_apply_rack_firmware_twice_reuses_jobs(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "apply-test-001";
+    let rack_id = create_rack_and_firmware(&env, firmware_id).await?;
+
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    txn.commit().await?;
+
+    let apply_request = RackFirmwareApplyRequest {
+        rack_id: Some(rack_id),
+        firmware_id: firmware_id.to_string(),
+        firmware_type: "prod".to_string(),
+        node_ids: vec![],
+    };
+
+    let first = env
+        .api
+        .apply_rack_firmware(tonic::Request::new(apply_request.clone()))
+        .await?
+        .into_inner();
+    assert_eq!(first.successful_updates, 1);
+    assert!(!first.device_results[0].job_id.is_empty());
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    // A retry returns the jobs from the first apply without calling RMS again
+    let second = env
+        .api
+        .apply_rack_firmware(tonic::Request::new(apply_request))
+        .await?
+        .into_inner();
+    assert_eq!(second, first);
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_concurrent_apply_rack_firmware_flashes_once(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "apply-test-concurrent";
+    let rack_id = create_rack_and_firmware(&env, firmware_id).await?;
+
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    txn.commit().await?;
+
+    let apply_request = |firmware_type: &str| RackFirmwareApplyRequest {
+        rack_id: Some(rack_id),
+        firmware_id: firmware_id.to_string(),
+        firmware_type: firmware_type.to_string(),
+        node_ids: vec![],
+    };
+
+    let (first, second) = tokio::join!(
+        env.api
+            .apply_rack_firmware(tonic::Request::new(apply_request("prod"))),
+        env.api
+            .apply_rack_firmware(tonic::Request::new(apply_request("prod"))),
+    );
+    // Whichever apply claimed the rack first started the jobs. The other one either returns
+    // them, or is told the apply is still in progress.
+    let results = [first, second];
+    let applied: Vec<_> = results
+        .iter()
+        .filter_map(|result| result.as_ref().ok())
+        .map(|response| response.get_ref())
+        .collect();
+    assert!(!applied.is_empty());
+    assert!(applied.iter().all(|response| *response == applied[0]));
+    for result in &results {
+        if let Err(status) = result {
+            assert_eq!(status.code(), tonic::Code::Aborted, "{status}");
+        }
+    }
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    // Applies of another firmware type are not reused
+    env.api
+        .apply_rack_firmware(tonic::Request::new(apply_request("dev")))
+        .await?;
+    assert_eq!(env.rms_sim.firmware_update_calls(), 2);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_apply_rack_firmware_reclaims_stale_claim(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "apply-test-stale-claim";
+    let rack_id = create_rack_and_firmware(&env, firmware_id).await?;
+
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    txn.commit().await?;
+
+    let apply_request = RackFirmwareApplyRequest {
+        rack_id: Some(rack_id),
+        firmware_id: firmware_id.to_string(),
+        firmware_type: "prod".to_string(),
+        node_ids: vec![],
+    };
+    // Leave a claim behind, as an apply does which dies before it recorded its jobs
+    let claim = |claimed_ago: chrono::Duration| {
+        let pool = env.pool.clone();
+        async move {
+            sqlx::query(
+                "INSERT INTO rack_firmware_apply (rack_id, firmware_id, firmware_type, created)
+                VALUES ($1, $2, 'prod', $3)
+                ON CONFLICT (rack_id, firmware_id, firmware_type) DO UPDATE
+                SET response = NULL, created = EXCLUDED.created",
+            )
+            .bind(rack_id)
+            .bind(firmware_id)
+            .bind(chrono::Utc::now() - claimed_ago)
+            .execute(&pool)
+            .await
+        }
+    };
+
+    // A recent claim is still being applied
+    claim(chrono::Duration::seconds(30)).await?;
+    let status = env
+        .api
+        .apply_rack_firmware(tonic::Request::new(apply_request.clone()))
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), tonic::Code::Aborted, "{status}");
+    assert_eq!(env.rms_sim.firmware_update_calls(), 0);
+
+    // A claim past its lease is taken over, although it is within the reuse window
+    claim(chrono::Duration::minutes(5)).await?;
+    env.api
+        .apply_rack_firmware(tonic::Request::new(apply_request))
+        .await?;
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_get_rack_firmware_job_status(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let job_state = |job_state: i32| {
+        env.rms_sim.set_firmware_job_state(job_state);
+        env.api
+            .get_rack_firmware_job_status(tonic::Request::new(RackFirmwareJobStatusRequest {
+                job_id: "job-1".to_string(),
+            }))
+    };
+
+    for (state, expected) in [
+        (FirmwareJobState::Queued, "QUEUED"),
+        (FirmwareJobState::Running, "RUNNING"),
+        (FirmwareJobState::Completed, "COMPLETED"),
+        (FirmwareJobState::Failed, "FAILED"),
+    ] {
+        let status = job_state(state as i32).await?.into_inner();
+        assert_eq!(status.job_id, "job-1");
+        assert_eq!(status.state, expected);
+    }
+    // A state added to RMS later is reported, rather than mistaken for a known one
+    let status = job_state(42).await?.into_inner();
+    assert_eq!(status.state, "UNKNOWN");
+
+    Ok(())
+}
+
+/// Poll the progress of the rollout `rollout_id` until it finished
+async fn wait_for_rollout(
+    env: &TestEnv,
+    rollout_id: &str,
+) -> Result<RackFirmwareBulkApplyResponse, Box<dyn std::error::Error>> {
+    for _ in 0..100 {
+        let status = env
+            .api
+            .get_rack_firmware_bulk_apply_status(tonic::Request::new(
+                RackFirmwareBulkApplyStatusRequest {
+                    rollout_id: rollout_id.to_string(),
+                },
+            ))
+            .await?
+            .into_inner();
+        if status.finished {
+            return Ok(status);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    Err(format!("Rollout {rollout_id} did not finish").into())
+}
+
+#[crate::sqlx_test()]
+async fn test_bulk_apply_rack_firmware_halts_on_failure(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "bulk-apply-test-001";
+    let first_rack = create_rack_and_firmware(&env, firmware_id).await?;
+    let last_rack = create_rack_with_power_shelf(&env).await?;
+
+    let mut txn = env.pool.begin().await?;
+    // A rack without devices fails to apply
+    let empty_rack = TestRackDbBuilder::new().persist(&mut txn).await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    txn.commit().await?;
+
+    let response = env
+        .api
+        .bulk_apply_rack_firmware(tonic::Request::new(RackFirmwareBulkApplyRequest {
+            firmware_id: firmware_id.to_string(),
+            firmware_type: "prod".to_string(),
+            rack_ids: vec![first_rack, empty_rack, last_rack],
+            max_concurrent_racks: 1,
+            max_failure_rate: 0.0,
+            job_timeout_seconds: 0,
+        }))
+        .await?
+        .into_inner();
+    assert!(!response.finished);
+    assert!(
+        response
+            .rack_results
+            .iter()
+            .all(|result| result.status() == RackFirmwareRackApplyStatus::RackApplyPending)
+    );
+
+    let response = wait_for_rollout(&env, &response.rollout_id).await?;
+    assert!(response.halted);
+    assert_eq!(
+        response
+            .rack_results
+            .iter()
+            .map(|result| (result.rack_id.unwrap(), result.status()))
+            .collect::<Vec<_>>(),
+        vec![
+            (first_rack, RackFirmwareRackApplyStatus::RackApplySucceeded),
+            (empty_rack, RackFirmwareRackApplyStatus::RackApplyFailed),
+            (last_rack, RackFirmwareRackApplyStatus::RackApplySkipped),
+        ]
+    );
+    assert_eq!(
+        response.rack_results[0]
+            .apply_response
+            .as_ref()
+            .unwrap()
+            .successful_updates,
+        1
+    );
+    assert!(
+        response.rack_results[1]
+            .message
+            .contains("contains no devices"),
+        "{}",
+        response.rack_results[1].message
+    );
+    // The rack after the failed one was never updated
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_bulk_apply_rack_firmware_counts_failed_jobs(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "bulk-apply-test-002";
+    let first_rack = create_rack_and_firmware(&env, firmware_id).await?;
+    let last_rack = create_rack_with_power_shelf(&env).await?;
+
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    txn.commit().await?;
+
+    // RMS accepts the update, but the job it starts fails
+    env.rms_sim
+        .set_firmware_job_state(FirmwareJobState::Failed as i32);
+
+    let response = env
+        .api
+        .bulk_apply_rack_firmware(tonic::Request::new(RackFirmwareBulkApplyRequest {
+            firmware_id: firmware_id.to_string(),
+            firmware_type: "prod".to_string(),
+            rack_ids: vec![first_rack, last_rack],
+            max_concurrent_racks: 1,
+            max_failure_rate: 0.0,
+            job_timeout_seconds: 0,
+        }))
+        .await?
+        .into_inner();
+    let response = wait_for_rollout(&env, &response.rollout_id).await?;
+
+    assert!(response.halted);
+    assert_eq!(
+        response
+            .rack_results
+            .iter()
+            .map(|result| (result.rack_id.unwrap(), result.status()))
+            .collect::<Vec<_>>(),
+        vec![
+            (first_rack, RackFirmwareRackApplyStatus::RackApplyFailed),
+            (last_rack, RackFirmwareRackApplyStatus::RackApplySkipped),
+        ]
+    );
+    assert!(
+        response.rack_results[0].message.contains("failed"),
+        "{}",
+        response.rack_results[0].message
+    );
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    // Unknown rollouts are reported as not found
+    let err = env
+        .api
+        .get_rack_firmware_bulk_apply_status(tonic::Request::new(
+            RackFirmwareBulkApplyStatusRequest {
+                rollout_id: "unknown".to_string(),
+            },
+        ))
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), tonic::Code::NotFound);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_apply_rack_firmware_available_for_prod_only(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "apply-test-002";
+    let rack_id = create_rack_and_firmware(&env, firmware_id).await?;
+
+    // Prod downloads finished, Dev downloads did not
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::set_available_types(&mut txn, firmware_id, &["prod".to_string()]).await?;
+    txn.commit().await?;
+
+    let apply = |firmware_type: &str| {
+        env.api
+            .apply_rack_firmware(tonic::Request::new(RackFirmwareApplyRequest {
+                rack_id: Some(rack_id),
+                firmware_id: firmware_id.to_string(),
+                firmware_type: firmware_type.to_string(),
+                node_ids: vec![],
+            }))
+    };
+
+    let response = apply("Prod").await?.into_inner();
+    assert_eq!(response.successful_updates, 1);
+
+    let err = apply("Dev")
+        .await
+        .expect_err("Dev firmware is not available");
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+    let list = |firmware_type: Option<&str>| {
+        env.api
+            .list_rack_firmware(tonic::Request::new(RackFirmwareListRequest {
+                only_available: true,
+                firmware_type: firmware_type.map(str::to_string),
+                label_selector: HashMap::new(),
+            }))
+    };
+    let configs = list(Some("prod")).await?.into_inner().configs;
+    assert_eq!(configs.len(), 1);
+    assert_eq!(configs[0].available_types, vec!["prod".to_string()]);
+    assert!(!configs[0].available);
+    assert!(list(Some("dev")).await?.into_inner().configs.is_empty());
+    assert!(list(None).await?.into_inner().configs.is_empty());
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_apply_rack_firmware_with_missing_files(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "apply-test-003";
+    let rack_id = create_rack_and_firmware(&env, firmware_id).await?;
+
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    txn.commit().await?;
+
+    let apply = || {
+        env.api
+            .apply_rack_firmware(tonic::Request::new(RackFirmwareApplyRequest {
+                rack_id: Some(rack_id),
+                firmware_id: firmware_id.to_string(),
+                firmware_type: "prod".to_string(),
+                node_ids: vec![],
+            }))
+    };
+
+    // Neither a missing nor an empty file starts any job
+    let psu_file = firmware_dir(&env, firmware_id).join("psu_prod.fwpkg");
+    std::fs::remove_file(&psu_file)?;
+    let err = apply().await.expect_err("firmware file is missing");
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    assert!(
+        err.message().contains("psu_prod.fwpkg"),
+        "{}",
+        err.message()
+    );
+
+    std::fs::write(&psu_file, b"")?;
+    let err = apply().await.expect_err("firmware file is empty");
+    assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+    assert_eq!(env.rms_sim.firmware_update_calls(), 0);
+
+    std::fs::write(&psu_file, b"firmware")?;
+    let response = apply().await?.into_inner();
+    assert_eq!(response.successful_updates, 1);
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_apply_rack_firmware_missing_firmware_type(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "apply-test-004";
+    let rack_id = create_rack_and_firmware(&env, firmware_id).await?;
+
+    // The config only has Prod power shelf firmware
+    let mut txn = env.pool.begin().await?;
+    DbRackFirmware::set_parsed_components(
+        &mut txn,
+        firmware_id,
+        serde_json::json!({
+            "devices": {
+                "Power Shelf": {
+                    "PSU_prod": {
+                        "filename": "psu_prod.fwpkg",
+                        "target": "PSU",
+                        "component": "PSU",
+                        "bundle": "psu-bundle-v1.5",
+                        "firmware_type": "prod",
+                        "version": "1.5.0",
+                        "subcomponents": []
+                    }
+                }
+            }
+        }),
+    )
+    .await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    txn.commit().await?;
+
+    let response = env
+        .api
+        .apply_rack_firmware(tonic::Request::new(RackFirmwareApplyRequest {
+            rack_id: Some(rack_id),
+            firmware_id: firmware_id.to_string(),
+            firmware_type: "dev".to_string(),
+            node_ids: vec![],
+        }))
+        .await?
+        .into_inner();
+    assert_eq!(response.failed_updates, 1);
+    assert_eq!(
+        response.device_results[0].message,
+        "No dev firmware found in config for Power Shelf, only prod firmware"
+    );
+    assert_eq!(env.rms_sim.firmware_update_calls(), 0);
+
+    Ok(())
+}
+
+#[crate::sqlx_test()]
+async fn test_apply_rack_firmware_to_single_node(
+    pool: sqlx::PgPool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let env = create_test_env(pool).await;
+    let firmware_id = "apply-test-005";
+    let rack_id = create_rack_and_firmware(&env, firmware_id).await?;
+
+    // A second power shelf that is left alone
+    let target_shelf = PowerShelfId::from(uuid::Uuid::new_v4());
+    let mut config = db::rack::get(&env.pool, rack_id).await?.config;
+    config.power_shelves.push(target_shelf);
+    let mut txn = env.pool.begin().await?;
+    db::rack::update(&mut txn, rack_id, &config).await?;
+    DbRackFirmware::set_available(&mut txn, firmware_id, true).await?;
+    txn.commit().await?;
+
+    let apply = |node_id: String| {
+        env.api
+            .apply_rack_firmware(tonic::Request::new(RackFirmwareApplyRequest {
+                rack_id: Some(rack_id),
+                firmware_id: firmware_id.to_string(),
+                firmware_type: "prod".to_string(),
+                node_ids: vec![node_id],
+            }))
+    };
+
+    let response = apply(target_shelf.to_string()).await?.into_inner();
+    assert_eq!(response.successful_updates, 1);
+    assert_eq!(response.device_results.len(), 1);
+    let result = &response.device_results[0];
+    assert_eq!(result.device_id, target_shelf.to_string());
+    assert_eq!(result.node_jobs.len(), 1);
+    assert_eq!(result.node_jobs[0].node_id, target_shelf.to_string());
+    assert_eq!(result.node_jobs[0].job_id, result.job_id);
+    assert_eq!(
+        env.rms_sim.node_firmware_updates().await,
+        [target_shelf.to_string()]
+    );
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    // Nodes outside the rack are rejected before any job is started
+    let err = apply(PowerShelfId::from(uuid::Uuid::new_v4()).to_string())
+        .await
+        .expect_err("node is not in the rack");
+    assert_eq!(err.code(), tonic::Code::InvalidArgument);
+    assert_eq!(env.rms_sim.firmware_update_calls(), 1);
+
+    Ok(())
+}
+
+/// Serves `router` on a local port, standing in for Artifactory
+async fn serve_artifactory(router: axum::Router) -> SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    address
+}
+
+/// Artifactory location of the firmware file served from `/fw/{filename}` on `address`
+fn artifactory_location(address: SocketAddr, filename: &str) -> FirmwareLocation {
+    FirmwareLocation {
+        location: format!("http://{address}/fw/{filename}"),
+        location_type: "Artifactory".to_string(),
+        firmware_type: Some("Firmware".to_string()),
+        sha256: None,
+        size: None,
+    }
+}
+
+/// How `download_firmware` downloads the files of a config
+struct DownloadOptions<'a> {
+    preflight: bool,
+    max_concurrent: usize,
+    /// Progress of the download. A new download of `fw-1` is tracked if not set.
+    progress: Option<&'a DownloadProgress>,
+}
+
+impl Default for DownloadOptions<'_> {
+    fn default() -> Self {
+        Self {
+            preflight: false,
+            max_concurrent: 4,
+            progress: None,
+        }
+    }
+}
+
+/// Downloads the files of `parsed_components` into `dest_dir` as firmware `fw-1`
+async fn download_firmware(
+    pool: &sqlx::PgPool,
+    parsed_components: &ParsedFirmwareComponents,
+    dest_dir: &Path,
+    options: DownloadOptions<'_>,
+) -> Result<Vec<FileDownloadReport>, String> {
+    let tracker = RackFirmwareDownloadTracker::default();
+    let progress = options
+        .progress
+        .cloned()
+        .unwrap_or_else(|| tracker.start("fw-1"));
+    download_firmware_files(
+        "fw-1",
+        parsed_components,
+        &forge_secrets::credentials::TestCredentialManager::default(),
+        pool,
+        dest_dir,
+        &HashMap::new(),
+        options.preflight,
+        options.max_concurrent,
+        &progress,
+    )
+    .await
+}
+
+#[tokio::test]
+async fn test_sha256_file_spans_chunks() {
+    let dir = temp_dir::TempDir::new().unwrap();
+    let path = dir.path().join("image.bin");
+    let content: Vec<u8> = (0..HASH_CHUNK_SIZE * 3 + 17)
+        .map(|i| (i % 251) as u8)
+        .collect();
+    tokio::fs::write(&path, &content).await.unwrap();
+
+    assert_eq!(
+        sha256_file(&path).await.unwrap(),
+        hex::encode(Sha256::digest(&content))
+    );
+}
+
+#[tokio::test]
+async fn test_download_replaces_corrupt_cached_file() {
+    const CONTENT: &[u8] = b"complete firmware image";
+    let downloads = Arc::new(AtomicUsize::new(0));
+    let router = axum::Router::new().route(
+        "/fw/image.bin",
+        axum::routing::get({
+            let downloads = downloads.clone();
+            move || async move {
+                downloads.fetch_add(1, Ordering::Relaxed);
+                CONTENT
+            }
+        }),
+    );
+    let address = serve_artifactory(router).await;
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    let progress = RackFirmwareDownloadTracker::default().start("fw-1");
+    let dest_path = dest_dir.path().join("image.bin");
+    // Left behind by an interrupted download
+    tokio::fs::write(&dest_path, &CONTENT[..8]).await.unwrap();
+
+    let location = FirmwareLocation {
+        sha256: Some(hex::encode(Sha256::digest(CONTENT))),
+        size: Some(CONTENT.len() as u64),
+        ..artifactory_location(address, "image.bin")
+    };
+    let download = || {
+        download_single_file(
+            location.clone(),
+            "BMC".to_string(),
+            String::new(),
+            dest_dir.path().to_path_buf(),
+            &progress,
+        )
+    };
+
+    let expected = CachedFileChecksum {
+        sha256: hex::encode(Sha256::digest(CONTENT)),
+        size: CONTENT.len() as u64,
+    };
+    assert_eq!(download().await.unwrap(), expected);
+    assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), CONTENT);
+    assert_eq!(downloads.load(Ordering::Relaxed), 1);
+
+    // Now that the cached file verifies, it is not downloaded again
+    assert_eq!(download().await.unwrap(), expected);
+    assert_eq!(downloads.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn test_partial_download_is_not_treated_as_complete() {
+    const CONTENT: &[u8] = b"complete firmware image";
+    let downloads = Arc::new(AtomicUsize::new(0));
+    let router = axum::Router::new().route(
+        "/fw/image.bin",
+        axum::routing::get({
+            let downloads = downloads.clone();
+            move || async move {
+                downloads.fetch_add(1, Ordering::Relaxed);
+                CONTENT
+            }
+        }),
+    );
+    let address = serve_artifactory(router).await;
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    let progress = RackFirmwareDownloadTracker::default().start("fw-1");
+    let dest_path = dest_dir.path().join("image.bin");
+    let part_path = part_file_path(dest_dir.path(), "image.bin");
+    // Left behind by a download that was interrupted while writing
+    tokio::fs::write(&part_path, &CONTENT[..8]).await.unwrap();
+
+    remove_stale_part_files(dest_dir.path()).await;
+    assert!(!part_path.exists());
+
+    // Without a checksum in the config, only a complete file may exist under the final name
+    tokio::fs::write(&part_path, &CONTENT[..8]).await.unwrap();
+    download_single_file(
+        artifactory_location(address, "image.bin"),
+        "BMC".to_string(),
+        String::new(),
+        dest_dir.path().to_path_buf(),
+        &progress,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(downloads.load(Ordering::Relaxed), 1);
+    assert_eq!(tokio::fs::read(&dest_path).await.unwrap(), CONTENT);
+    assert!(!part_path.exists());
+}
+
+#[tokio::test]
+async fn test_download_verifies_artifactory_checksum() {
+    const CONTENT: &[u8] = b"complete firmware image";
+    let sha256 = hex::encode(Sha256::digest(CONTENT));
+    let router = axum::Router::new()
+        .route(
+            "/fw/good.bin",
+            axum::routing::get(
+                move || async move { ([(ARTIFACTORY_SHA256_HEADER, sha256)], CONTENT) },
+            ),
+        )
+        .route(
+            "/fw/bad.bin",
+            axum::routing::get(|| async {
+                (
+                    [(
+                        ARTIFACTORY_SHA256_HEADER,
+                        hex::encode(Sha256::digest(b"other")),
+                    )],
+                    CONTENT,
+                )
+            }),
+        );
+    let address = serve_artifactory(router).await;
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    let progress = RackFirmwareDownloadTracker::default().start("fw-1");
+    let download = |filename: &str| {
+        download_single_file(
+            artifactory_location(address, filename),
+            "BMC".to_string(),
+            String::new(),
+            dest_dir.path().to_path_buf(),
+            &progress,
+        )
+    };
+
+    download("good.bin").await.unwrap();
+    assert_eq!(
+        tokio::fs::read(dest_dir.path().join("good.bin"))
+            .await
+            .unwrap(),
+        CONTENT
+    );
+
+    let err = download("bad.bin").await.unwrap_err();
+    assert!(err.message.contains("SHA-256"), "{err}");
+    assert!(!err.retryable);
+    assert!(!dest_dir.path().join("bad.bin").exists());
+    assert!(!dest_dir.path().join("bad.bin.part").exists());
+}
+
+#[tokio::test]
+async fn test_watch_download_progress() {
+    const CONTENT: &[u8] = b"small firmware image";
+    let router =
+        axum::Router::new().route("/fw/image.bin", axum::routing::get(|| async { CONTENT }));
+    let address = serve_artifactory(router).await;
+
+    let tracker = RackFirmwareDownloadTracker::default();
+    assert!(tracker.subscribe("fw-1").is_none());
+    let progress = tracker.start("fw-1");
+    let events = BroadcastStream::new(tracker.subscribe("fw-1").unwrap());
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    download_single_file(
+        artifactory_location(address, "image.bin"),
+        "BMC".to_string(),
+        String::new(),
+        dest_dir.path().to_path_buf(),
+        &progress,
+    )
+    .await
+    .unwrap();
+    progress.finished("done".to_string());
+    tracker.finish(progress);
+    assert!(tracker.subscribe("fw-1").is_none());
+
+    // The stream ends once the finished download is unregistered
+    let events: Vec<RackFirmwareDownloadEvent> = events.map(Result::unwrap).collect().await;
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| event.event_type())
+            .collect::<Vec<_>>(),
+        [
+            RackFirmwareDownloadEventType::DownloadFileStarted,
+            RackFirmwareDownloadEventType::DownloadFileCompleted,
+            RackFirmwareDownloadEventType::DownloadFinished,
+        ]
+    );
+    assert!(events.iter().all(|event| event.firmware_id == "fw-1"));
+    assert_eq!(events[0].filename, "image.bin");
+    assert_eq!(events[0].total_bytes, Some(CONTENT.len() as u64));
+    assert_eq!(events[1].filename, "image.bin");
+    assert_eq!(events[1].bytes_downloaded, CONTENT.len() as u64);
+}
+
+/// Parsed components of a config with one firmware component per file of `filenames`, each
+/// downloaded from `/fw/{filename}` on `address`
+fn parsed_components_served_by(
+    address: std::net::SocketAddr,
+    filenames: &[&str],
+) -> ParsedFirmwareComponents {
+    let firmware: Vec<serde_json::Value> = filenames
+        .iter()
+        .map(|filename| {
+            serde_json::json!({
+                "Component": filename,
+                "Version": "1.0",
+                "Locations": [{
+                    "Location": format!("http://{address}/fw/{filename}"),
+                    "LocationType": "Artifactory",
+                    "Type": "Firmware"
+                }]
+            })
+        })
+        .collect();
+    parse_rack_firmware_json(&serde_json::json!({
+        "BoardSKUs": [{
+            "SKUID": "sku-001",
+            "Name": "Compute Tray",
+            "Type": "ComputeTray",
+            "Components": {"Firmware": firmware}
+        }]
+    }))
+    .unwrap()
+}
+
+#[crate::sqlx_test()]
+async fn test_download_writes_checksum_index(pool: sqlx::PgPool) {
+    const BMC: &[u8] = b"bmc firmware image";
+    const BIOS: &[u8] = b"bios firmware image";
+    let router = axum::Router::new()
+        .route("/fw/bmc.fwpkg", axum::routing::get(|| async { BMC }))
+        .route("/fw/bios.fwpkg", axum::routing::get(|| async { BIOS }));
+    let address = serve_artifactory(router).await;
+
+    let parsed_components = parsed_components_served_by(address, &["bmc.fwpkg", "bios.fwpkg"]);
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    download_firmware(
+        &pool,
+        &parsed_components,
+        dest_dir.path(),
+        DownloadOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    let index = std::fs::read(dest_dir.path().join(CHECKSUM_INDEX_FILENAME)).unwrap();
+    let index: HashMap<String, CachedFileChecksum> = serde_json::from_slice(&index).unwrap();
+    assert_eq!(
+        index,
+        HashMap::from([
+            (
+                "bmc.fwpkg".to_string(),
+                CachedFileChecksum {
+                    sha256: hex::encode(Sha256::digest(BMC)),
+                    size: BMC.len() as u64,
+                }
+            ),
+            (
+                "bios.fwpkg".to_string(),
+                CachedFileChecksum {
+                    sha256: hex::encode(Sha256::digest(BIOS)),
+                    size: BIOS.len() as u64,
+                }
+            ),
+        ])
+    );
+}
+
+#[crate::sqlx_test()]
+async fn test_download_preflight_aborts_on_unreachable_url(pool: sqlx::PgPool) {
+    let downloads = Arc::new(AtomicUsize::new(0));
+    let router = axum::Router::new().route(
+        "/fw/bmc.fwpkg",
+        axum::routing::get({
+            let downloads = downloads.clone();
+            move |method: axum::http::Method| {
+                let downloads = downloads.clone();
+                async move {
+                    if method == axum::http::Method::GET {
+                        downloads.fetch_add(1, Ordering::Relaxed);
+                    }
+                    &b"bmc firmware image"[..]
+                }
+            }
+        }),
+    );
+    let address = serve_artifactory(router).await;
+
+    let parsed_components = parsed_components_served_by(address, &["bmc.fwpkg", "missing.fwpkg"]);
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    let err = download_firmware(
+        &pool,
+        &parsed_components,
+        dest_dir.path(),
+        DownloadOptions {
+            preflight: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .expect_err("missing.fwpkg is unreachable");
+
+    assert!(err.contains("missing.fwpkg"), "{err}");
+    assert!(!err.contains("bmc.fwpkg"), "{err}");
+    assert_eq!(downloads.load(Ordering::Relaxed), 0);
+    assert!(!dest_dir.path().join("bmc.fwpkg").exists());
+}
+
+#[crate::sqlx_test()]
+async fn test_download_preflight_honors_concurrency_limit(pool: sqlx::PgPool) {
+    #[derive(Default)]
+    struct Preflight {
+        requests: AtomicUsize,
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+    let preflight = Arc::new(Preflight::default());
+    let router = axum::Router::new().route(
+        "/fw/{filename}",
+        axum::routing::get({
+            let preflight = preflight.clone();
+            move |method: axum::http::Method| {
+                let preflight = preflight.clone();
+                async move {
+                    if method == axum::http::Method::HEAD {
+                        preflight.requests.fetch_add(1, Ordering::SeqCst);
+                        let current = preflight.current.fetch_add(1, Ordering::SeqCst) + 1;
+                        preflight.max.fetch_max(current, Ordering::SeqCst);
+                        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                        preflight.current.fetch_sub(1, Ordering::SeqCst);
+                    }
+                    &b"firmware image"[..]
+                }
+            }
+        }),
+    );
+    let address = serve_artifactory(router).await;
+
+    // Both board SKUs share all of their files, which are only checked once
+    let filenames = ["a.fwpkg", "b.fwpkg", "c.fwpkg", "d.fwpkg", "e.fwpkg"];
+    let board_sku = |sku_id: &str| {
+        let firmware: Vec<serde_json::Value> = filenames
+            .iter()
+            .map(|filename| {
+                serde_json::json!({
+                    "Component": filename,
+                    "Version": "1.0",
+                    "Locations": [{
+                        "Location": format!("http://{address}/fw/{filename}"),
+                        "LocationType": "Artifactory",
+                        "Type": "Firmware"
+                    }]
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "SKUID": sku_id,
+            "Name": "Compute Tray",
+            "Type": "ComputeTray",
+            "Components": {"Firmware": firmware}
+        })
+    };
+    let parsed_components = parse_rack_firmware_json(&serde_json::json!({
+        "BoardSKUs": [board_sku("sku-001"), board_sku("sku-002")]
+    }))
+    .unwrap();
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    download_firmware(
+        &pool,
+        &parsed_components,
+        dest_dir.path(),
+        DownloadOptions {
+            preflight: true,
+            max_concurrent: 2,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(preflight.requests.load(Ordering::SeqCst), filenames.len());
+    assert_eq!(preflight.max.load(Ordering::SeqCst), 2);
+}
+
+#[crate::sqlx_test()]
+async fn test_downloads_honor_concurrency_limit(pool: sqlx::PgPool) {
+    #[derive(Default)]
+    struct InFlight {
+        current: AtomicUsize,
+        max: AtomicUsize,
+    }
+    let in_flight = Arc::new(InFlight::default());
+    let router = axum::Router::new().route(
+        "/fw/{filename}",
+        axum::routing::get({
+            let in_flight = in_flight.clone();
+            move || {
+                let in_flight = in_flight.clone();
+                async move {
+                    let current = in_flight.current.fetch_add(1, Ordering::SeqCst) + 1;
+                    in_flight.max.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                    in_flight.current.fetch_sub(1, Ordering::SeqCst);
+                    &b"firmware image"[..]
+                }
+            }
+        }),
+    );
+    let address = serve_artifactory(router).await;
+
+    let filenames = ["a.fwpkg", "b.fwpkg", "c.fwpkg", "d.fwpkg", "e.fwpkg"];
+    let parsed_components = parsed_components_served_by(address, &filenames);
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    download_firmware(
+        &pool,
+        &parsed_components,
+        dest_dir.path(),
+        DownloadOptions {
+            max_concurrent: 2,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(in_flight.max.load(Ordering::SeqCst), 2);
+    for filename in filenames {
+        assert!(dest_dir.path().join(filename).exists(), "{filename}");
+    }
+    let index = std::fs::read(dest_dir.path().join(CHECKSUM_INDEX_FILENAME)).unwrap();
+    let index: HashMap<String, CachedFileChecksum> = serde_json::from_slice(&index).unwrap();
+    assert_eq!(index.len(), filenames.len());
+}
+
+#[crate::sqlx_test()]
+async fn test_file_shared_by_board_skus_is_downloaded_once(pool: sqlx::PgPool) {
+    let downloads = Arc::new(AtomicUsize::new(0));
+    let router = axum::Router::new().route(
+        "/fw/bmc.fwpkg",
+        axum::routing::get({
+            let downloads = downloads.clone();
+            move || async move {
+                downloads.fetch_add(1, Ordering::Relaxed);
+                &b"bmc firmware image"[..]
+            }
+        }),
+    );
+    let address = serve_artifactory(router).await;
+
+    let board_sku = |sku_id: &str| {
+        serde_json::json!({
+            "SKUID": sku_id,
+            "Name": "Compute Tray",
+            "Type": "ComputeTray",
+            "Components": {"Firmware": [{
+                "Component": "BMC",
+                "Version": "1.0",
+                "Locations": [{
+                    "Location": format!("http://{address}/fw/bmc.fwpkg"),
+                    "LocationType": "Artifactory",
+                    "Type": "Firmware"
+                }]
+            }]}
+        })
+    };
+    let parsed_components = parse_rack_firmware_json(&serde_json::json!({
+        "BoardSKUs": [board_sku("sku-001"), board_sku("sku-002")]
+    }))
+    .unwrap();
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    let reports = download_firmware(
+        &pool,
+        &parsed_components,
+        dest_dir.path(),
+        DownloadOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        reports,
+        [FileDownloadReport {
+            filename: "bmc.fwpkg".to_string(),
+            attempts: 1,
+            error: None,
+        }]
+    );
+    assert_eq!(downloads.load(Ordering::Relaxed), 1);
+    assert!(dest_dir.path().join("bmc.fwpkg").exists());
+}
+
+#[crate::sqlx_test()]
+async fn test_download_retries_failed_attempts(pool: sqlx::PgPool) {
+    const CONTENT: &[u8] = b"flaky firmware image";
+    let requests = Arc::new(AtomicUsize::new(0));
+    let router = axum::Router::new()
+        .route(
+            "/fw/flaky.fwpkg",
+            axum::routing::get({
+                let requests = requests.clone();
+                move || {
+                    let requests = requests.clone();
+                    async move {
+                        // The first request fails
+                        if requests.fetch_add(1, Ordering::Relaxed) == 0 {
+                            Err(axum::http::StatusCode::INTERNAL_SERVER_ERROR)
+                        } else {
+                            Ok(CONTENT)
+                        }
+                    }
+                }
+            }),
+        )
+        .route("/fw/stable.fwpkg", axum::routing::get(|| async { CONTENT }));
+    let address = serve_artifactory(router).await;
+
+    let parsed_components = parsed_components_served_by(address, &["flaky.fwpkg", "stable.fwpkg"]);
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    let tracker = RackFirmwareDownloadTracker::default();
+    let progress = tracker.start("fw-1");
+    let mut events = tracker.subscribe("fw-1").unwrap();
+    let reports = download_firmware(
+        &pool,
+        &parsed_components,
+        dest_dir.path(),
+        DownloadOptions {
+            progress: Some(&progress),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        reports,
+        [
+            FileDownloadReport {
+                filename: "flaky.fwpkg".to_string(),
+                attempts: 2,
+                error: None,
+            },
+            FileDownloadReport {
+                filename: "stable.fwpkg".to_string(),
+                attempts: 1,
+                error: None,
+            },
+        ]
+    );
+    assert_eq!(
+        reports[0].to_string(),
+        "flaky.fwpkg: succeeded after 2 attempts"
+    );
+    assert_eq!(
+        tokio::fs::read(dest_dir.path().join("flaky.fwpkg"))
+            .await
+            .unwrap(),
+        CONTENT
+    );
+
+    let mut completed = HashMap::new();
+    while let Ok(event) = events.try_recv() {
+        if event.event_type() == RackFirmwareDownloadEventType::DownloadFileCompleted {
+            completed.insert(event.filename, event.attempt);
+        }
+    }
+    assert_eq!(
+        completed,
+        HashMap::from([
+            ("flaky.fwpkg".to_string(), 2),
+            ("stable.fwpkg".to_string(), 1)
+        ])
+    );
+}
+
+#[crate::sqlx_test()]
+async fn test_download_retries_rate_limited_attempts(pool: sqlx::PgPool) {
+    const CONTENT: &[u8] = b"rate limited firmware image";
+    let requests = Arc::new(AtomicUsize::new(0));
+    let router = axum::Router::new().route(
+        "/fw/limited.fwpkg",
+        axum::routing::get({
+            let requests = requests.clone();
+            move || {
+                let requests = requests.clone();
+                async move {
+                    // Artifactory rate limits the first request
+                    if requests.fetch_add(1, Ordering::Relaxed) == 0 {
+                        Err((
+                            axum::http::StatusCode::TOO_MANY_REQUESTS,
+                            [(axum::http::header::RETRY_AFTER, "2")],
+                        ))
+                    } else {
+                        Ok(CONTENT)
+                    }
+                }
+            }
+        }),
+    );
+    let address = serve_artifactory(router).await;
+
+    let parsed_components = parsed_components_served_by(address, &["limited.fwpkg"]);
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    let started = std::time::Instant::now();
+    let reports = download_firmware(
+        &pool,
+        &parsed_components,
+        dest_dir.path(),
+        DownloadOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        reports,
+        [FileDownloadReport {
+            filename: "limited.fwpkg".to_string(),
+            attempts: 2,
+            error: None,
+        }]
+    );
+    assert_eq!(requests.load(Ordering::Relaxed), 2);
+    // The retry waited as long as the response asked for
+    assert!(started.elapsed() >= std::time::Duration::from_secs(2));
+    assert_eq!(
+        tokio::fs::read(dest_dir.path().join("limited.fwpkg"))
+            .await
+            .unwrap(),
+        CONTENT
+    );
+}
+
+#[crate::sqlx_test()]
+async fn test_download_does_not_retry_permanent_errors(pool: sqlx::PgPool) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let router = axum::Router::new().route(
+        "/fw/missing.fwpkg",
+        axum::routing::get({
+            let requests = requests.clone();
+            move || async move {
+                requests.fetch_add(1, Ordering::Relaxed);
+                axum::http::StatusCode::NOT_FOUND
+            }
+        }),
+    );
+    let address = serve_artifactory(router).await;
+
+    let parsed_components = parsed_components_served_by(address, &["missing.fwpkg"]);
+
+    let dest_dir = temp_dir::TempDir::new().unwrap();
+    let reports = download_firmware(
+        &pool,
+        &parsed_components,
+        dest_dir.path(),
+        DownloadOptions::default(),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].attempts, 1);
+    let error = reports[0].error.as_ref().unwrap();
+    assert!(error.contains("404"), "{error}");
+    assert_eq!(requests.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn test_power_shelf_subcomponent_firmware() {
+    let config = serde_json::json!({
+        "BoardSKUs": [
+            {
+                "SKUID": "699-24764-0001-TS1",
+                "Name": "GB200 Compute Tray",
+                "Type": "ComputeTray",
+                "Components": {
+                    "Firmware": [
+                        {
+                            "Component": "Power Shelf FW",
+                            "Bundle": "P4972",
+                            "Version": "1.0",
+                            "Type": "Prod",
+                            "Locations": [
+                                {
+                                    "Location": "artifactory.example.com/ps/powershelf_1.0.fwpkg",
+                                    "LocationType": "Artifactory",
+                                    "Type": "Firmware"
+                                },
+                                {
+                                    "Location": "artifactory.example.com/ps/powershelf_psu_1.2.bin",
+                                    "LocationType": "Artifactory",
+                                    "Type": "Firmware"
+                                }
+                            ],
+                            "SubComponents": [
+                                {"Component": "PSU", "Version": "1.2", "Filename": "powershelf_psu_1.2.bin"},
+                                {"Component": "PMC", "Version": "3.4", "Filename": "powershelf_1.0.fwpkg"},
+                                {"Component": "FAN", "Version": "0.9", "Filename": "powershelf_1.0.fwpkg"},
+                                {"Component": "HSC", "Version": "2.0"}
+                            ]
+                        }
+                    ]
+                }
+            }
+        ]
+    });
+    let parsed = parse_rack_firmware_json(&config).unwrap();
+    let targets = HashMap::from([
+        ("PSU".to_string(), "psu_target".to_string()),
+        ("PMC".to_string(), "pmc_target".to_string()),
+        ("HSC".to_string(), "hsc_target".to_string()),
+    ]);
+
+    let lookup = build_firmware_lookup_table(&parsed, &targets);
+    let power_shelf = &lookup.devices["Power Shelf"];
+    // FAN has no configured target, and HSC names no file
+    assert_eq!(power_shelf.len(), 2);
+
+    let psu = &power_shelf["PSU_prod"];
+    assert_eq!(psu.filename, "powershelf_psu_1.2.bin");
+    assert_eq!(psu.target, "psu_target");
+    assert_eq!(psu.version.as_deref(), Some("1.2"));
+    assert_eq!(psu.bundle, "P4972");
+
+    let pmc = &power_shelf["PMC_prod"];
+    assert_eq!(pmc.filename, "powershelf_1.0.fwpkg");
+    assert_eq!(pmc.target, "pmc_target");
+
+    let components = find_firmware_components_for_device(
+        &serde_json::to_value(&lookup).unwrap(),
+        "Power Shelf",
+        "prod",
+    );
+    assert_eq!(components.len(), 2);
+    assert!(
+        components
+            .iter()
+            .all(|(_, filename, target)| !filename.is_empty() && !target.is_empty())
+    );
+
+    // Nothing is flashed without configured targets
+    let lookup = build_firmware_lookup_table(&parsed, &HashMap::new());
+    assert!(!lookup.devices.contains_key("Power Shelf"));
+}
+
+#[test]
+fn test_bundle_file_flashes_multiple_targets() {
+    let config = serde_json::json!({
+        "BoardSKUs": [
+            {
+                "SKUID": "920-9K36F-00MV-QS1",
+                "Name": "Juliet Switch",
+                "Type": "SwitchTray",
+                "Components": {
+                    "Firmware": [
+                        {
+                            "Component": "BMC+FPGA+EROT",
+                            "Bundle": "P4978",
+                            "Version": "1.0",
+                            "Locations": [
+                                {
+                                    "Location": "artifactory.example.com/switch/bmc_fpga_erot.fwpkg",
+                                    "LocationType": "Artifactory",
+                                    "Type": "Firmware"
+                                }
+                            ]
+                        }
+                    ]
+                }
+            }
+        ]
+    });
+    let parsed = parse_rack_firmware_json(&config).unwrap();
+    let lookup = build_firmware_lookup_table(&parsed, &HashMap::new());
+
+    // One file is downloaded for the bundle
+    assert_eq!(download_files(&parsed).len(), 1);
+
+    let components = order_firmware_components_for_flashing(
+        "Switch Tray",
+        find_firmware_components_for_device(
+            &serde_json::to_value(&lookup).unwrap(),
+            "Switch Tray",
+            "prod",
+        ),
+    )
+    .unwrap();
+    let targets: Vec<(&str, &str)> = components
+        .iter()
+        .map(|(_, filename, target)| (filename.as_str(), target.as_str()))
+        .collect();
+    assert_eq!(
+        targets,
+        [
+            ("bmc_fpga_erot.fwpkg", "bmc"),
+            ("bmc_fpga_erot.fwpkg", "fpga"),
+            ("bmc_fpga_erot.fwpkg", "erot"),
+        ]
+    );
+
+    // A target listed twice is only flashed once
+    let mut duplicated = components.clone();
+    duplicated.push(components[1].clone());
+    assert_eq!(
+        order_firmware_components_for_flashing("Switch Tray", duplicated).unwrap(),
+        components
+    );
+
+    // A different file for a target that already has one is not dropped silently
+    let mut colliding = components.clone();
+    colliding.push((
+        "BMC".to_string(),
+        "bmc_only.fwpkg".to_string(),
+        "bmc".to_string(),
+    ));
+    let err = order_firmware_components_for_flashing("Switch Tray", colliding).unwrap_err();
+    assert!(err.contains("bmc_fpga_erot.fwpkg"), "{err}");
+    assert!(err.contains("bmc_only.fwpkg"), "{err}");
+}
+
+#[test]
+fn test_download_files_are_keyed_by_filename() {
+    let component = |name: &str, url: &str, firmware_type: &str| {
+        serde_json::json!({
+            "Component": name,
+            "Version": "1.0",
+            "Type": firmware_type,
+            "Locations": [{
+                "Location": url,
+                "LocationType": "Artifactory",
+                "Type": "Firmware"
+            }]
+        })
+    };
+    let parsed = parse_rack_firmware_json(&serde_json::json!({
+        "BoardSKUs": [
+            {
+                "SKUID": "sku-001",
+                "Name": "Compute Tray",
+                "Type": "ComputeTray",
+                "Components": {"Firmware": [
+                    component("BMC", "https://mirror-a.example.com/fw/bmc.fwpkg", "Prod"),
+                    component("BIOS", "https://mirror-a.example.com/fw/bios.fwpkg", "Prod"),
+                ]}
+            },
+            {
+                "SKUID": "sku-002",
+                "Name": "Compute Tray",
+                "Type": "ComputeTray",
+                "Components": {"Firmware": [
+                    component("BMC", "https://mirror-b.example.com/fw/bmc.fwpkg", "Dev"),
+                ]}
+            }
+        ]
+    }))
+    .unwrap();
+
+    let files = download_files(&parsed);
+    let filenames: Vec<&str> = files.iter().map(|file| file.filename.as_str()).collect();
+    assert_eq!(filenames, ["bmc.fwpkg", "bios.fwpkg"]);
+    // The first URL of a filename is downloaded for every component sharing it
+    assert_eq!(files[0].url, "https://mirror-a.example.com/fw/bmc.fwpkg");
+    assert_eq!(files[0].components, ["BMC"]);
+    assert_eq!(files[0].firmware_types, ["prod", "dev"]);
+}
+
+#[test]
+fn test_parse_firmware_job_result() {
+    let result_json = r#"{
+        "rack_id": "rack-1",
+        "nodes": [
+            {
+                "node_id": "compute-0",
+                "status": "success",
+                "message": "Firmware updated",
+                "components": [
+                    {"target": "FW_BMC_0", "status": "success", "version": "1.2.3"},
+                    {"target": "/redfish/v1/Chassis/HGX_Chassis_0", "status": "success", "version": "2.0"}
+                ]
+            },
+            {
+                "node_id": "compute-1",
+                "status": "failed",
+                "message": "Flash timed out",
+                "components": [
+                    {"target": "FW_BMC_0", "status": "failed", "message": "timeout"}
+                ]
+            }
+        ]
+    }"#;
+
+    let result = parse_firmware_job_result(result_json).unwrap();
+    assert_eq!(result.nodes.len(), 2);
+    assert_eq!(result.nodes[0].node_id, "compute-0");
+    assert_eq!(result.nodes[0].components.len(), 2);
+    assert_eq!(
+        result.nodes[0].components[0].version.as_deref(),
+        Some("1.2.3")
+    );
+    assert_eq!(result.nodes[1].status, "failed");
+    assert_eq!(result.nodes[1].components[0].message, "timeout");
+    assert_eq!(result.nodes[1].components[0].version, None);
+
+    let node: FirmwareJobNodeResult = result.nodes[1].clone().into();
+    assert_eq!(node.message, "Flash timed out");
+    assert_eq!(node.component_results[0].target, "FW_BMC_0");
+    assert_eq!(node.component_results[0].version, "");
+}
+
+#[test]
+fn test_parse_firmware_job_result_empty_or_unknown() {
+    assert_eq!(parse_firmware_job_result(""), None);
+    assert_eq!(parse_firmware_job_result("not json"), None);
+    assert_eq!(
+        parse_firmware_job_result(r#"{"summary": "done"}"#),
+        Some(RmsFirmwareJobResult::default())
+    );
+}
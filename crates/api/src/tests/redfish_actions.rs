@@ -17,12 +17,17 @@
 use std::time::Duration;
 
 use ::rpc::forge::forge_server::Forge;
+use bmc_mock::test_support::harness::BmcMockHarness;
+use http::{HeaderMap, Uri};
 use rpc::forge::{RedfishAction, RedfishActionResult};
 use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
 use tokio::time::Instant;
 
 use crate::auth::{AuthContext, ExternalUserInfo};
-use crate::handlers::redfish::TestBehavior;
+use crate::handlers::redfish::{
+    BOOT_ORDER_ACTION, JobPollOptions, PATCH_AND_WAIT_ACTION, RedfishPatch, TestBehavior,
+    patch_and_wait_for_job, plan_boot_order_remediation, send_request,
+};
 use crate::tests::common::api_fixtures::{TestEnv, create_managed_host, create_test_env};
 
 #[crate::sqlx_test]
@@ -283,6 +288,313 @@ async fn test_action_failure_at_client_creation(_: PgPoolOptions, options: PgCon
     }
 }
 
+#[tokio::test]
+async fn test_bmc_mock_harness_serves_service_root() {
+    let bmc = BmcMockHarness::dell_poweredge_r750().await.unwrap();
+    let http_client = reqwest::Client::new();
+
+    let response = http_client
+        .get(bmc.url("/redfish/v1"))
+        .send()
+        .await
+        .unwrap()
+        .error_for_status()
+        .unwrap();
+    let service_root: serde_json::Value =
+        serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(service_root["@odata.id"], "/redfish/v1");
+    assert_eq!(service_root["Systems"]["@odata.id"], "/redfish/v1/Systems");
+
+    // Changes to the harness state apply to the running mock.
+    bmc.state
+        .injected_bugs
+        .update(serde_json::json!({"require_session_auth": true}))
+        .unwrap();
+    let response = http_client
+        .get(bmc.url("/redfish/v1/Systems"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn test_patch_and_wait_for_job() {
+    let bmc = BmcMockHarness::dell_poweredge_r750().await.unwrap();
+    let bmc_address = bmc.address;
+
+    let http_client = reqwest::Client::new();
+    let metadata = rpc::forge::BmcMetaDataGetResponse {
+        user: "root".to_string(),
+        password: "password".to_string(),
+        ..Default::default()
+    };
+    let bios_settings_uri: Uri =
+        format!("http://{bmc_address}/redfish/v1/Systems/System.Embedded.1/Bios/Settings")
+            .parse()
+            .unwrap();
+    let body = serde_json::json!({"Attributes": {"SerialComm": "OnConRedirCom1"}});
+
+    // iDRAC only runs BIOS configuration jobs on the next reboot
+    let err = patch_and_wait_for_job(
+        &http_client,
+        &metadata,
+        bios_settings_uri.clone(),
+        HeaderMap::new(),
+        body.clone(),
+        JobPollOptions {
+            timeout: Duration::ZERO,
+            interval: Duration::from_millis(10),
+        },
+    )
+    .await
+    .unwrap_err();
+    assert!(err.to_string().contains("Last state: Scheduled"), "{err}");
+
+    let reset_uri = format!(
+        "http://{bmc_address}/redfish/v1/Systems/System.Embedded.1/Actions/ComputerSystem.Reset"
+    );
+    tokio::spawn({
+        let http_client = http_client.clone();
+        async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            http_client
+                .post(reset_uri)
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(serde_json::json!({"ResetType": "ForceRestart"}).to_string())
+                .send()
+                .await
+                .unwrap()
+                .error_for_status()
+                .unwrap();
+        }
+    });
+
+    let job_state = patch_and_wait_for_job(
+        &http_client,
+        &metadata,
+        bios_settings_uri,
+        HeaderMap::new(),
+        body,
+        JobPollOptions {
+            timeout: Duration::from_secs(10),
+            interval: Duration::from_millis(10),
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(job_state, "Completed");
+}
+
+#[tokio::test]
+async fn test_patch_and_wait_for_failed_job() {
+    let router = axum::Router::new()
+        .route(
+            "/redfish/v1/Managers/iDRAC.Embedded.1/Attributes",
+            axum::routing::patch(|| async {
+                (
+                    http::StatusCode::ACCEPTED,
+                    [(
+                        http::header::LOCATION,
+                        "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_001",
+                    )],
+                )
+            }),
+        )
+        .route(
+            "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_001",
+            axum::routing::get(|| async {
+                axum::Json(serde_json::json!({
+                    "Id": "JID_001",
+                    "JobState": "Failed",
+                    "Message": "Unable to apply the configuration changes",
+                }))
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let err = patch_and_wait_for_job(
+        &reqwest::Client::new(),
+        &rpc::forge::BmcMetaDataGetResponse::default(),
+        format!("http://{address}/redfish/v1/Managers/iDRAC.Embedded.1/Attributes")
+            .parse()
+            .unwrap(),
+        HeaderMap::new(),
+        serde_json::json!({"Attributes": {"SerialRedirection.1.Enable": "Enabled"}}),
+        JobPollOptions {
+            timeout: Duration::from_secs(10),
+            interval: Duration::from_millis(10),
+        },
+    )
+    .await
+    .unwrap_err();
+    let err = err.to_string();
+    assert!(err.contains("finished in state Failed"), "{err}");
+    assert!(
+        err.contains("Unable to apply the configuration changes"),
+        "{err}"
+    );
+}
+
+#[tokio::test]
+async fn test_patch_action_waits_for_job() {
+    let bmc = BmcMockHarness::dell_poweredge_r750().await.unwrap();
+    let metadata = rpc::forge::BmcMetaDataGetResponse {
+        user: "root".to_string(),
+        password: "password".to_string(),
+        ..Default::default()
+    };
+
+    // iDRAC only runs BIOS configuration jobs on the next reboot
+    let Err(err) = send_request(
+        PATCH_AND_WAIT_ACTION,
+        serde_json::json!({"Attributes": {"SerialComm": "OnConRedirCom1"}}).to_string(),
+        bmc.url("/redfish/v1/Systems/System.Embedded.1/Bios/Settings")
+            .as_str()
+            .parse()
+            .unwrap(),
+        HeaderMap::new(),
+        &metadata,
+        &reqwest::Client::new(),
+        JobPollOptions {
+            timeout: Duration::ZERO,
+            interval: Duration::from_millis(10),
+        },
+    )
+    .await
+    else {
+        panic!("PATCH action should time out waiting for the BIOS job");
+    };
+    assert!(
+        err.description.contains("Last state: Scheduled"),
+        "{}",
+        err.description
+    );
+}
+
+#[tokio::test]
+async fn test_plan_boot_order_remediation() {
+    let bmc = BmcMockHarness::dell_poweredge_r750().await.unwrap();
+    let http_client = reqwest::Client::new();
+    let system_path = "/redfish/v1/Systems/System.Embedded.1";
+
+    let get_boot_order = || async {
+        let response = http_client
+            .get(bmc.url(system_path))
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+        let system: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        serde_json::from_value::<Vec<String>>(system["Boot"]["BootOrder"].clone()).unwrap()
+    };
+    let apply = |patch: RedfishPatch| {
+        http_client
+            .patch(bmc.url(&patch.path))
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(patch.body.to_string())
+            .send()
+    };
+
+    let desired = get_boot_order().await;
+    assert!(desired.len() > 1, "{desired:?}");
+    assert!(
+        plan_boot_order_remediation(system_path, &desired, &desired)
+            .unwrap()
+            .is_empty()
+    );
+
+    // Scramble the boot order on the BMC
+    let mut scrambled = desired.clone();
+    scrambled.reverse();
+    apply(RedfishPatch {
+        path: system_path.to_string(),
+        body: serde_json::json!({"Boot": {"BootOrder": scrambled}}),
+    })
+    .await
+    .unwrap()
+    .error_for_status()
+    .unwrap();
+    let current = get_boot_order().await;
+    assert_eq!(current, scrambled);
+
+    let plan = plan_boot_order_remediation(system_path, &current, &desired).unwrap();
+    assert_eq!(plan.len(), 1);
+    for patch in plan {
+        apply(patch).await.unwrap().error_for_status().unwrap();
+    }
+    assert_eq!(get_boot_order().await, desired);
+
+    // Boot options can only be reordered
+    let mut unknown = desired.clone();
+    unknown[0] = "Boot9999".to_string();
+    assert!(plan_boot_order_remediation(system_path, &current, &unknown).is_err());
+    assert!(plan_boot_order_remediation(system_path, &current, &desired[1..]).is_err());
+}
+
+#[tokio::test]
+async fn test_boot_order_action() {
+    let bmc = BmcMockHarness::dell_poweredge_r750().await.unwrap();
+    let http_client = reqwest::Client::new();
+    let metadata = rpc::forge::BmcMetaDataGetResponse {
+        user: "root".to_string(),
+        password: "password".to_string(),
+        ..Default::default()
+    };
+    let system_url = bmc.url("/redfish/v1/Systems/System.Embedded.1");
+
+    let get_boot_order = || async {
+        let response = http_client
+            .get(system_url.clone())
+            .send()
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap();
+        let system: serde_json::Value =
+            serde_json::from_str(&response.text().await.unwrap()).unwrap();
+        serde_json::from_value::<Vec<String>>(system["Boot"]["BootOrder"].clone()).unwrap()
+    };
+    let remediate = |desired: Vec<String>| {
+        send_request(
+            BOOT_ORDER_ACTION,
+            serde_json::json!({"BootOrder": desired}).to_string(),
+            system_url.as_str().parse().unwrap(),
+            HeaderMap::new(),
+            &metadata,
+            &http_client,
+            JobPollOptions::default(),
+        )
+    };
+
+    let desired = get_boot_order().await;
+    let mut scrambled = desired.clone();
+    scrambled.reverse();
+    remediate(scrambled.clone()).await.unwrap();
+    assert_eq!(get_boot_order().await, scrambled);
+
+    let response = remediate(desired.clone()).await.unwrap();
+    assert_eq!(response.status, "200 OK");
+    assert_eq!(get_boot_order().await, desired);
+
+    let response = remediate(desired.clone()).await.unwrap();
+    assert_eq!(response.body, "Boot order already matches");
+
+    // Boot options can only be reordered
+    let Err(err) = remediate(desired[1..].to_vec()).await else {
+        panic!("Boot order action should not remove boot options");
+    };
+    assert!(
+        err.description.contains("missing boot options"),
+        "{}",
+        err.description
+    );
+}
+
 async fn wait_for_action_results(env: &TestEnv, bmc_ip: &str) -> Vec<RedfishActionResult> {
     let start = Instant::now();
     let mut retry_interval = tokio::time::interval(Duration::from_millis(100));
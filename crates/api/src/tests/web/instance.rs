@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use ::rpc::forge as rpc;
+use axum::body::Body;
+use http_body_util::BodyExt;
+use hyper::http::StatusCode;
+use tower::ServiceExt;
+
+use crate::tests::common::api_fixtures::{create_managed_host, create_test_env};
+use crate::tests::web::{authenticated_request_builder, make_test_app};
+
+#[crate::sqlx_test]
+async fn test_filter_by_tag(pool: sqlx::PgPool) {
+    let env = create_test_env(pool).await;
+    let segment_id = env.create_vpc_and_tenant_segment().await;
+
+    for (i, color) in [Some("blue"), Some("red"), None].into_iter().enumerate() {
+        let mh = create_managed_host(&env).await;
+        mh.instance_builer(&env)
+            .single_interface_network_config(segment_id)
+            .metadata(rpc::Metadata {
+                name: format!("instance_{i}"),
+                description: String::new(),
+                labels: color
+                    .map(|color| rpc::Label {
+                        key: "color".to_string(),
+                        value: Some(color.to_string()),
+                    })
+                    .into_iter()
+                    .collect(),
+            })
+            .build()
+            .await;
+    }
+
+    let app = make_test_app(&env);
+    for (uri, expected_names) in [
+        (
+            "/admin/instance.json",
+            vec!["instance_0", "instance_1", "instance_2"],
+        ),
+        (
+            "/admin/instance.json?tag=color",
+            vec!["instance_0", "instance_1"],
+        ),
+        ("/admin/instance.json?tag=color:blue", vec!["instance_0"]),
+        ("/admin/instance.json?tag=color:green", vec![]),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(
+                authenticated_request_builder()
+                    .uri(uri)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+
+        let body_bytes = response
+            .into_body()
+            .collect()
+            .await
+            .expect("Empty response body?")
+            .to_bytes();
+        let body_str = std::str::from_utf8(&body_bytes).expect("Invalid UTF-8 in body");
+        let instances: serde_json::Value =
+            serde_json::from_str(body_str).expect("Could not deserialize response");
+
+        let names: Vec<&str> = instances["instances"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["metadata"]["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, expected_names, "{uri}");
+    }
+
+    let response = app
+        .oneshot(
+            authenticated_request_builder()
+                .uri("/admin/instance.json?tag=:blue")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
@@ -21,6 +21,7 @@ use hyper::http::request::Builder;
 
 use crate::tests::common;
 use crate::web::routes;
+mod instance;
 mod machine_health;
 mod managed_host;
 
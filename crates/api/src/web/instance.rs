@@ -20,7 +20,7 @@ use std::sync::Arc;
 
 use askama::Template;
 use axum::Json;
-use axum::extract::{Path as AxumPath, State as AxumState};
+use axum::extract::{Path as AxumPath, Query, State as AxumState};
 use axum::response::{Html, IntoResponse, Response};
 use carbide_uuid::network::NetworkSegmentId;
 use carbide_uuid::vpc::VpcId;
@@ -36,6 +36,7 @@ use crate::api::Api;
 #[template(path = "instance_show.html")]
 struct InstanceShow {
     instances: Vec<InstanceDisplay>,
+    active_tag: String,
 }
 
 struct InstanceDisplay {
@@ -132,8 +133,20 @@ impl From<forgerpc::Instance> for InstanceDisplay {
 }
 
 /// List instances
-pub async fn show_html(AxumState(state): AxumState<Arc<Api>>) -> Response {
-    let out = match fetch_instances(state).await {
+///
+/// Accepts an optional `tag=key:value` (or `tag=key`) query parameter which
+/// limits the list to instances carrying that label.
+pub async fn show_html(
+    AxumState(state): AxumState<Arc<Api>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let active_tag = params.get("tag").cloned().unwrap_or_default();
+    let label = match parse_tag_filter(&active_tag) {
+        Ok(label) => label,
+        Err(err) => return (StatusCode::BAD_REQUEST, err).into_response(),
+    };
+
+    let out = match fetch_instances(state, label).await {
         Ok(m) => m,
         Err(err) => {
             tracing::error!(%err, "fetch_instances");
@@ -142,12 +155,23 @@ pub async fn show_html(AxumState(state): AxumState<Arc<Api>>) -> Response {
     };
 
     let instances: Vec<InstanceDisplay> = out.instances.into_iter().map(Into::into).collect();
-    let tmpl = InstanceShow { instances };
+    let tmpl = InstanceShow {
+        instances,
+        active_tag,
+    };
     (StatusCode::OK, Html(tmpl.render().unwrap())).into_response()
 }
 
-pub async fn show_all_json(AxumState(state): AxumState<Arc<Api>>) -> Response {
-    let out = match fetch_instances(state).await {
+pub async fn show_all_json(
+    AxumState(state): AxumState<Arc<Api>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let label = match parse_tag_filter(params.get("tag").map(String::as_str).unwrap_or_default()) {
+        Ok(label) => label,
+        Err(err) => return (StatusCode::BAD_REQUEST, err).into_response(),
+    };
+
+    let out = match fetch_instances(state, label).await {
         Ok(m) => m,
         Err(err) => {
             tracing::error!(%err, "fetch_instances");
@@ -157,8 +181,38 @@ pub async fn show_all_json(AxumState(state): AxumState<Arc<Api>>) -> Response {
     (StatusCode::OK, Json(out)).into_response()
 }
 
-async fn fetch_instances(api: Arc<Api>) -> Result<forgerpc::InstanceList, tonic::Status> {
-    let request = tonic::Request::new(forgerpc::InstanceSearchFilter::default());
+/// Turns a `key:value` or `key` tag filter into the label used for searching.
+/// An empty filter means no filtering.
+fn parse_tag_filter(tag: &str) -> Result<Option<forgerpc::Label>, String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Ok(None);
+    }
+
+    let (key, value) = match tag.split_once(':') {
+        Some((key, value)) => (key.trim(), Some(value.trim())),
+        None => (tag, None),
+    };
+    if key.is_empty() {
+        return Err(format!(
+            "Invalid tag filter \"{tag}\": the key must not be empty"
+        ));
+    }
+
+    Ok(Some(forgerpc::Label {
+        key: key.to_string(),
+        value: value.filter(|v| !v.is_empty()).map(str::to_string),
+    }))
+}
+
+async fn fetch_instances(
+    api: Arc<Api>,
+    label: Option<forgerpc::Label>,
+) -> Result<forgerpc::InstanceList, tonic::Status> {
+    let request = tonic::Request::new(forgerpc::InstanceSearchFilter {
+        label,
+        ..Default::default()
+    });
 
     let instance_ids = api
         .find_instance_ids(request)
@@ -442,60 +496,11 @@ impl From<forgerpc::Instance> for InstanceDetail {
     }
 }
 
-async fn get_network_segments_map_for_instance(
-    state: Arc<Api>,
-    if_configs: &[forgerpc::InstanceInterfaceConfig],
-) -> Result<HashMap<NetworkSegmentId, NetworkSegment>, tonic::Status> {
-    let network_segment_ids: Vec<NetworkSegmentId> = if_configs
-        .iter()
-        .filter_map(|iface| iface.network_segment_id)
-        .collect();
-
-    let ns_req = tonic::Request::new(forgerpc::NetworkSegmentsByIdsRequest {
-        network_segments_ids: network_segment_ids,
-        include_history: false,
-        include_num_free_ips: false,
-    });
-
-    let network_segments = state
-        .find_network_segments_by_ids(ns_req)
-        .await?
-        .into_inner()
-        .network_segments;
-
-    let network_segments_map: HashMap<NetworkSegmentId, NetworkSegment> = network_segments
-        .into_iter()
-        .filter_map(|ns| ns.id.map(|id| (id, ns)))
-        .collect();
-
-    Ok(network_segments_map)
-}
-
-async fn get_vpc_map_for_instance(
-    state: Arc<Api>,
-    network_segments_map: &HashMap<NetworkSegmentId, NetworkSegment>,
-) -> Result<HashMap<VpcId, forgerpc::Vpc>, tonic::Status> {
-    let vpc_ids: Vec<VpcId> = network_segments_map
-        .values()
-        .filter_map(|ns| ns.vpc_id)
-        .collect();
-
-    let vpc_req = tonic::Request::new(forgerpc::VpcsByIdsRequest { vpc_ids });
-
-    let vpcs = state.find_vpcs_by_ids(vpc_req).await?.into_inner().vpcs;
-
-    let vpc_map: HashMap<VpcId, forgerpc::Vpc> = vpcs
-        .into_iter()
-        .filter_map(|vpc| vpc.id.map(|id| (id, vpc)))
-        .collect();
-
-    Ok(vpc_map)
-}
-
-async fn get_interfaces_for_instance_detail(
-    state: Arc<Api>,
+fn get_interfaces_for_instance_detail(
     instance: &forgerpc::Instance,
-) -> Result<Vec<InstanceInterface>, tonic::Status> {
+    network_segments: Vec<NetworkSegment>,
+    vpcs: Vec<forgerpc::Vpc>,
+) -> Vec<InstanceInterface> {
     let mut interfaces = Vec::new();
     let if_configs = instance
         .config
@@ -511,13 +516,18 @@ async fn get_interfaces_for_instance_detail(
         .unwrap_or_default();
 
     if if_configs.len() != if_status.len() {
-        return Ok(interfaces);
+        return interfaces;
     }
 
-    let network_segments_map =
-        get_network_segments_map_for_instance(state.clone(), if_configs).await?;
+    let network_segments_map: HashMap<NetworkSegmentId, NetworkSegment> = network_segments
+        .into_iter()
+        .filter_map(|ns| ns.id.map(|id| (id, ns)))
+        .collect();
 
-    let vpc_map = get_vpc_map_for_instance(state, &network_segments_map).await?;
+    let vpc_map: HashMap<VpcId, forgerpc::Vpc> = vpcs
+        .into_iter()
+        .filter_map(|vpc| vpc.id.map(|id| (id, vpc)))
+        .collect();
 
     for (i, interface) in if_configs.iter().enumerate() {
         let mut vpc_id = "".to_string();
@@ -554,7 +564,7 @@ async fn get_interfaces_for_instance_detail(
             vpc_name,
         });
     }
-    Ok(interfaces)
+    interfaces
 }
 
 /// View instance
@@ -578,44 +588,30 @@ pub async fn detail(
         }
     };
 
-    let request = tonic::Request::new(forgerpc::InstancesByIdsRequest {
-        instance_ids: vec![instance_id],
-    });
-    let instance = match state
-        .find_instances_by_ids(request)
+    let detail = match state
+        .get_instance_detail(tonic::Request::new(instance_id))
         .await
         .map(|response| response.into_inner())
     {
-        Ok(x) if x.instances.is_empty() => {
-            return super::not_found_response(instance_id_string);
-        }
-        Ok(x) if x.instances.len() != 1 => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!(
-                    "Instance list for {instance_id} returned {} instances",
-                    x.instances.len()
-                ),
-            )
-                .into_response();
-        }
-        Ok(mut x) => x.instances.remove(0),
+        Ok(detail) => detail,
         Err(err) if err.code() == tonic::Code::NotFound => {
             return super::not_found_response(instance_id_string);
         }
         Err(err) => {
-            tracing::error!(%err, %instance_id, "find_instances");
+            tracing::error!(%err, %instance_id, "get_instance_detail");
             return (StatusCode::INTERNAL_SERVER_ERROR, "Error loading instances").into_response();
         }
     };
+    let Some(instance) = detail.instance else {
+        return super::not_found_response(instance_id_string);
+    };
 
     if show_json {
         return (StatusCode::OK, Json(instance)).into_response();
     }
 
-    let instance_detail_interfaces = get_interfaces_for_instance_detail(state.clone(), &instance)
-        .await
-        .unwrap_or_else(|_| Vec::new());
+    let instance_detail_interfaces =
+        get_interfaces_for_instance_detail(&instance, detail.network_segments, detail.vpcs);
     let mut instance_detail: InstanceDetail = instance.into();
     instance_detail.interfaces = instance_detail_interfaces;
     (StatusCode::OK, Html(instance_detail.render().unwrap())).into_response()
@@ -21,6 +21,7 @@ use crate::redfish;
 use crate::redfish::chassis::ChassisState;
 use crate::redfish::computer_system::SystemState;
 use crate::redfish::manager::ManagerState;
+use crate::redfish::session_service::SessionState;
 use crate::redfish::update_service::UpdateServiceState;
 
 #[derive(Clone)]
@@ -32,6 +33,7 @@ pub struct BmcState {
     pub system_state: Arc<SystemState>,
     pub chassis_state: Arc<ChassisState>,
     pub update_service_state: Arc<UpdateServiceState>,
+    pub session_state: Arc<SessionState>,
     pub injected_bugs: Arc<InjectedBugs>,
 }
 
@@ -15,12 +15,15 @@
  * limitations under the License.
  */
 
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use arc_swap::ArcSwap;
-use duration_str::deserialize_option_duration;
+use duration_str::{deserialize_duration, deserialize_option_duration};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::redfish;
@@ -29,12 +32,28 @@ use crate::redfish;
 pub struct InjectedBugs {
     all_dpu_lost_on_host: Arc<AtomicBool>,
     long_response: Arc<ArcSwap<Option<LongResponse>>>,
+    manager_reset_downtime: Arc<ArcSwap<Option<Duration>>>,
+    require_session_auth: Arc<AtomicBool>,
+    staged_dell_attributes: Arc<AtomicBool>,
+    latency_profile: Arc<ArcSwap<Option<LatencyProfile>>>,
 }
 
 #[derive(Deserialize, Serialize)]
 struct Args {
     all_dpu_lost_on_host: Option<bool>,
     long_response: Option<LongResponse>,
+    /// How long the BMC stays unavailable after a Manager.Reset.
+    #[serde(default, deserialize_with = "deserialize_option_duration")]
+    manager_reset_downtime: Option<Duration>,
+    /// Reject requests without a valid X-Auth-Token session header.
+    require_session_auth: Option<bool>,
+    /// Stage iDRAC attribute and BIOS settings PATCHes in a job, like a real
+    /// iDRAC does, instead of applying them right away.
+    staged_dell_attributes: Option<bool>,
+    /// Delays matching requests by a random duration.
+    latency_profile: Option<LatencyProfile>,
+    /// Loads `latency_profile` from a JSON file instead.
+    latency_profile_file: Option<PathBuf>,
 }
 
 #[derive(Clone, Deserialize, Serialize)]
@@ -44,17 +63,88 @@ struct LongResponse {
     timeout: Option<Duration>,
 }
 
+/// Maps odata path globs onto the latency of matching requests, e.g.
+/// `{"/redfish/v1/Managers/*/Attributes": {"min": "100ms", "max": "300ms"}}`.
+///
+/// `*` matches any sequence of characters. If several globs match a path, the
+/// most specific one is used: the one with the longest literal prefix, then
+/// the one with the most literal characters.
+#[derive(Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct LatencyProfile(BTreeMap<String, LatencyRange>);
+
+#[derive(Clone, Deserialize, Serialize)]
+struct LatencyRange {
+    #[serde(deserialize_with = "deserialize_duration")]
+    min: Duration,
+    #[serde(deserialize_with = "deserialize_duration")]
+    max: Duration,
+}
+
+impl LatencyProfile {
+    pub fn from_file(path: &Path) -> Result<Self, serde_json::Error> {
+        let data = std::fs::read(path).map_err(serde_json::Error::io)?;
+        serde_json::from_slice(&data)
+    }
+
+    /// Returns a random delay for a request to `path`, if any glob matches it.
+    fn delay(&self, path: &str) -> Option<Duration> {
+        let range = self.range(path)?;
+        if range.max <= range.min {
+            return Some(range.min);
+        }
+        Some(rand::rng().random_range(range.min..=range.max))
+    }
+
+    /// The latency range of the most specific glob matching `path`
+    fn range(&self, path: &str) -> Option<&LatencyRange> {
+        self.0
+            .iter()
+            .filter(|(glob, _)| glob_matches(glob, path))
+            .max_by_key(|(glob, _)| glob_specificity(glob))
+            .map(|(_, range)| range)
+    }
+}
+
+/// Orders globs from least to most specific: by the length of their literal
+/// prefix, then by their number of literal characters
+fn glob_specificity(glob: &str) -> (usize, usize) {
+    let prefix = glob.find('*').unwrap_or(glob.len());
+    (prefix, glob.len() - glob.matches('*').count())
+}
+
+fn glob_matches(glob: &str, path: &str) -> bool {
+    let Some((prefix, rest)) = glob.split_once('*') else {
+        return glob == path;
+    };
+    let Some(path) = path.strip_prefix(prefix) else {
+        return false;
+    };
+    (0..=path.len())
+        .filter(|i| path.is_char_boundary(*i))
+        .any(|i| glob_matches(rest, &path[i..]))
+}
+
 impl InjectedBugs {
     pub fn get(&self) -> serde_json::Value {
         let long_response = self.long_response.load();
         serde_json::json!(Args {
             all_dpu_lost_on_host: Some(self.all_dpu_lost_on_host().is_some()),
-            long_response: long_response.as_ref().clone()
+            long_response: long_response.as_ref().clone(),
+            manager_reset_downtime: self.manager_reset_downtime(),
+            require_session_auth: Some(self.require_session_auth()),
+            staged_dell_attributes: Some(self.staged_dell_attributes()),
+            latency_profile: self.latency_profile.load().as_ref().clone(),
+            latency_profile_file: None,
         })
     }
 
     pub fn update(&self, v: serde_json::Value) -> Result<(), serde_json::Error> {
         let args = serde_json::from_value::<Args>(v)?;
+        let latency_profile = match args.latency_profile_file {
+            Some(path) => Some(LatencyProfile::from_file(&path)?),
+            None => args.latency_profile,
+        };
 
         self.all_dpu_lost_on_host.store(
             args.all_dpu_lost_on_host.unwrap_or(false),
@@ -62,6 +152,17 @@ impl InjectedBugs {
         );
 
         self.long_response.store(args.long_response.into());
+        self.require_session_auth.store(
+            args.require_session_auth.unwrap_or(false),
+            Ordering::Relaxed,
+        );
+        self.staged_dell_attributes.store(
+            args.staged_dell_attributes.unwrap_or(false),
+            Ordering::Relaxed,
+        );
+        self.manager_reset_downtime
+            .store(args.manager_reset_downtime.into());
+        self.latency_profile.store(latency_profile.into());
         Ok(())
     }
 
@@ -71,6 +172,26 @@ impl InjectedBugs {
             .then_some(AllDpuLostOnHost {})
     }
 
+    pub fn require_session_auth(&self) -> bool {
+        self.require_session_auth.load(Ordering::Relaxed)
+    }
+
+    pub fn staged_dell_attributes(&self) -> bool {
+        self.staged_dell_attributes.load(Ordering::Relaxed)
+    }
+
+    pub fn manager_reset_downtime(&self) -> Option<Duration> {
+        *self.manager_reset_downtime.load().as_ref()
+    }
+
+    pub fn latency(&self, path: &str) -> Option<Duration> {
+        self.latency_profile
+            .load()
+            .as_ref()
+            .as_ref()
+            .and_then(|profile| profile.delay(path))
+    }
+
     pub fn long_response(&self, path: &str) -> Option<Duration> {
         self.long_response.load().as_ref().as_ref().and_then(|v| {
             if v.path.as_ref().is_none_or(|v| v == path) {
@@ -106,3 +227,109 @@ impl AllDpuLostOnHost {
             .to_json()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use axum::http::{Method, StatusCode};
+
+    use super::*;
+    use crate::test_support::{call_router, dell_poweredge_r750_router_with_state};
+
+    fn profile(json: serde_json::Value) -> LatencyProfile {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("/redfish/v1", "/redfish/v1"));
+        assert!(!glob_matches("/redfish/v1", "/redfish/v1/Systems"));
+        assert!(glob_matches(
+            "/redfish/v1/Managers/*/Attributes",
+            "/redfish/v1/Managers/iDRAC.Embedded.1/Attributes"
+        ));
+        assert!(!glob_matches(
+            "/redfish/v1/Managers/*/Attributes",
+            "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs"
+        ));
+        assert!(glob_matches("/redfish/v1/*", "/redfish/v1/Systems/1"));
+        assert!(glob_matches("*", ""));
+    }
+
+    #[test]
+    fn test_delay_within_range() {
+        let profile = profile(serde_json::json!({
+            "/redfish/v1/Managers/*/Attributes": {"min": "200ms", "max": "400ms"},
+            "/redfish/v1/Systems/*": {"min": "50ms", "max": "50ms"},
+        }));
+
+        for _ in 0..20 {
+            let delay = profile
+                .delay("/redfish/v1/Managers/iDRAC.Embedded.1/Attributes")
+                .unwrap();
+            assert!(
+                delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400),
+                "{delay:?}"
+            );
+        }
+        assert_eq!(
+            profile.delay("/redfish/v1/Systems/System.Embedded.1"),
+            Some(Duration::from_millis(50))
+        );
+        assert_eq!(profile.delay("/redfish/v1"), None);
+    }
+
+    #[test]
+    fn test_delay_uses_most_specific_glob() {
+        let profile = profile(serde_json::json!({
+            "*": {"min": "1ms", "max": "1ms"},
+            "/redfish/v1/*": {"min": "2ms", "max": "2ms"},
+            "/redfish/v1/Managers/*": {"min": "3ms", "max": "3ms"},
+            "/redfish/v1/Managers/*/Attributes": {"min": "4ms", "max": "4ms"},
+            "/redfish/v1/Managers/iDRAC.Embedded.1/Attributes": {"min": "5ms", "max": "5ms"},
+        }));
+        let delay = |path| profile.delay(path).unwrap().as_millis();
+
+        assert_eq!(delay("/redfish/v1/Managers/iDRAC.Embedded.1/Attributes"), 5);
+        assert_eq!(delay("/redfish/v1/Managers/BMC_0/Attributes"), 4);
+        assert_eq!(delay("/redfish/v1/Managers/BMC_0"), 3);
+        assert_eq!(delay("/redfish/v1/Systems"), 2);
+        assert_eq!(delay("/InjectedBugs"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_latency_profile_from_file() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let profile_path = dir.path().join("latency-profile.json");
+        std::fs::write(
+            &profile_path,
+            r#"{"/redfish/v1/Managers/*/Attributes": {"min": "200ms", "max": "200ms"}}"#,
+        )
+        .unwrap();
+
+        let (router, state) = dell_poweredge_r750_router_with_state();
+        let response = call_router(
+            &router,
+            Method::POST,
+            "/InjectedBugs",
+            &[],
+            serde_json::json!({"latency_profile_file": profile_path}),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let attributes_uri = "/redfish/v1/Managers/iDRAC.Embedded.1/Attributes";
+        assert_eq!(
+            state.injected_bugs.latency(attributes_uri),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(state.injected_bugs.latency("/redfish/v1"), None);
+
+        // Matching requests are held back by the delay
+        let start = Instant::now();
+        let response = call_router(&router, Method::GET, attributes_uri, &[], "").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
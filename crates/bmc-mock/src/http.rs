@@ -28,6 +28,30 @@ pub(crate) fn not_found() -> Response {
     json!("").into_response(StatusCode::NOT_FOUND)
 }
 
+/// Builds a Redfish error response: an `error` object with `code`, `message`
+/// and a single `@Message.ExtendedInfo` entry describing what went wrong.
+pub(crate) fn redfish_error(
+    status: StatusCode,
+    message_id: &str,
+    message: &str,
+    resolution: &str,
+) -> Response {
+    json!({
+        "error": {
+            "code": "Base.1.12.GeneralError",
+            "message": "A general error has occurred. See ExtendedInfo for more information.",
+            "@Message.ExtendedInfo": [{
+                "@odata.type": "#Message.v1_1_1.Message",
+                "MessageId": message_id,
+                "Message": message,
+                "Severity": "Critical",
+                "Resolution": resolution,
+            }]
+        }
+    })
+    .into_response(status)
+}
+
 /// Wrapper arond axum::Router::call which constructs a new request object. This works
 /// around an issue where if you just call inner_router.call(request) when that request's
 /// Path<> is parameterized (ie. /:system_id, etc) it fails if the inner router doesn't have
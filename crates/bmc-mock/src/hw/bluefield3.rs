@@ -253,6 +253,7 @@ impl Bluefield3<'_> {
             part_number: Some(self.part_number().into()),
             firmware_version: Some(self.firmware_versions.dpu_nic.clone().into()),
             is_mat_dpu: true,
+            status: None,
         }
     }
 
@@ -191,7 +191,6 @@ impl DellPowerEdgeR750<'_> {
                 ),
                 vec![function],
             )
-            .status(redfish::resource::Status::Ok)
             .build()
         }))
         .collect();
@@ -205,7 +204,6 @@ impl DellPowerEdgeR750<'_> {
                     &redfish::pcie_device::chassis_resource(chassis_id, &pcie_device_id),
                     nic,
                 )
-                .status(redfish::resource::Status::Ok)
                 .build()
             })
             .collect();
@@ -19,6 +19,8 @@ use std::borrow::Cow;
 
 use mac_address::MacAddress;
 
+use crate::redfish;
+
 pub struct Nic {
     pub mac_address: MacAddress,
     pub serial_number: String,
@@ -28,6 +30,8 @@ pub struct Nic {
     pub part_number: Option<Cow<'static, str>>,
     pub firmware_version: Option<Cow<'static, str>>,
     pub is_mat_dpu: bool,
+    /// Health reported for the NIC. `None` is reported as healthy.
+    pub status: Option<redfish::resource::Status>,
 }
 
 impl Nic {
@@ -42,6 +46,7 @@ impl Nic {
             firmware_version: None,
             mac_address: mac,
             is_mat_dpu: false,
+            status: None,
         }
     }
 }
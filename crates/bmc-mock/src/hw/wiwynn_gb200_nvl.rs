@@ -121,7 +121,6 @@ impl WiwynnGB200Nvl<'_> {
                     &redfish::network_adapter::chassis_resource(chassis_id, chassis_id),
                     &nic,
                 )
-                .status(redfish::resource::Status::Ok)
                 .build(),
             ]);
 
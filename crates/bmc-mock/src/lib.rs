@@ -35,10 +35,12 @@ mod redfish;
 pub mod test_support;
 pub mod tls;
 
+pub use bmc_state::BmcState;
 pub use combined_server::{CombinedServer, ListenerOrAddress};
 pub use machine_info::{DpuFirmwareVersions, DpuMachineInfo, HostMachineInfo, MachineInfo};
 pub use mock_machine_router::{
     BmcCommand, SetSystemPowerError, SetSystemPowerResult, machine_router,
+    machine_router_with_state,
 };
 
 #[derive(Default, Debug, Clone, Copy, Serialize, Deserialize, Eq, PartialEq)]
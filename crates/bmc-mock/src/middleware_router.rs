@@ -20,20 +20,31 @@ use std::sync::Arc;
 use axum::Router;
 use axum::body::Body;
 use axum::extract::{Request, State};
-use axum::response::Response;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum::routing::any;
 use tracing::instrument;
 
 use crate::bug::InjectedBugs;
 use crate::http::call_router_with_new_request;
+use crate::redfish::manager::ManagerState;
+use crate::redfish::session_service::{self, SessionState};
 
-pub fn append(mat_host_id: String, router: Router, injected_bugs: Arc<InjectedBugs>) -> Router {
+pub fn append(
+    mat_host_id: String,
+    router: Router,
+    injected_bugs: Arc<InjectedBugs>,
+    manager: Arc<ManagerState>,
+    sessions: Arc<SessionState>,
+) -> Router {
     Router::new()
         .route("/{*all}", any(process))
         .with_state(Middleware {
             mat_host_id,
             inner: router,
             injected_bugs,
+            manager,
+            sessions,
         })
 }
 
@@ -41,6 +52,24 @@ pub fn append(mat_host_id: String, router: Router, injected_bugs: Arc<InjectedBu
 async fn process(State(mut state): State<Middleware>, request: Request<Body>) -> Response {
     let method = request.method().to_string();
     let path = request.uri().path().to_string();
+    if state.manager.is_resetting() {
+        tracing::warn!(method, path, "BMC is rebooting after Manager.Reset");
+        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+    }
+    if state.injected_bugs.require_session_auth()
+        && path != "/InjectedBugs"
+        && !session_service::is_unauthenticated_path(request.method(), &path)
+    {
+        let authorized = request
+            .headers()
+            .get(session_service::AUTH_TOKEN_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|token| state.sessions.is_valid_token(token));
+        if !authorized {
+            tracing::warn!(method, path, "Request without a valid session token");
+            return StatusCode::UNAUTHORIZED.into_response();
+        }
+    }
     if let Some(delay) = state.injected_bugs.long_response(&path) {
         tracing::warn!(
             method,
@@ -49,6 +78,10 @@ async fn process(State(mut state): State<Middleware>, request: Request<Body>) ->
         );
         tokio::time::sleep(delay).await;
     }
+    if let Some(delay) = state.injected_bugs.latency(&path) {
+        tracing::debug!(method, path, "Delaying request by {delay:?}");
+        tokio::time::sleep(delay).await;
+    }
     let response = state.call_inner_router(request).await;
     if !response.status().is_success() {
         tracing::warn!(method, path, status = response.status().to_string());
@@ -61,6 +94,8 @@ struct Middleware {
     mat_host_id: String,
     inner: Router,
     injected_bugs: Arc<InjectedBugs>,
+    manager: Arc<ManagerState>,
+    sessions: Arc<SessionState>,
 }
 
 impl Middleware {
@@ -66,6 +66,17 @@ pub fn machine_router(
     power_control: Arc<dyn PowerControl>,
     mat_host_id: String,
 ) -> Router {
+    machine_router_with_state(machine_info, power_control, mat_host_id).0
+}
+
+/// Same as [`machine_router`], but also returns the [`BmcState`] backing the
+/// router so callers (mostly tests) can inspect or mutate the mock while it
+/// is being served.
+pub fn machine_router_with_state(
+    machine_info: MachineInfo,
+    power_control: Arc<dyn PowerControl>,
+    mat_host_id: String,
+) -> (Router, BmcState) {
     let system_config = machine_info.system_config(power_control);
     let chassis_config = machine_info.chassis_config();
     let update_service_config = machine_info.update_service_config();
@@ -84,6 +95,7 @@ pub fn machine_router(
         .add_routes(crate::redfish::update_service::add_routes)
         .add_routes(crate::redfish::task_service::add_routes)
         .add_routes(crate::redfish::account_service::add_routes)
+        .add_routes(crate::redfish::session_service::add_routes)
         .add_routes(|routes| crate::redfish::computer_system::add_routes(routes, bmc_vendor));
     let router = match &machine_info {
         MachineInfo::Dpu(_) => {
@@ -101,19 +113,29 @@ pub fn machine_router(
     let update_service_state = Arc::new(
         crate::redfish::update_service::UpdateServiceState::from_config(update_service_config),
     );
+    let session_state = Arc::new(crate::redfish::session_service::SessionState::default());
     let injected_bugs = Arc::new(InjectedBugs::default());
-    let router = router.with_state(BmcState {
+    let state = BmcState {
         bmc_vendor,
         bmc_product,
         oem_state,
-        manager,
+        manager: manager.clone(),
         system_state,
         chassis_state,
         update_service_state,
+        session_state: session_state.clone(),
         injected_bugs: injected_bugs.clone(),
-    });
+    };
+    let router = router.with_state(state.clone());
     let router_with_expansion = redfish::expander_router::append(router);
-    middleware_router::append(mat_host_id, router_with_expansion, injected_bugs)
+    let router = middleware_router::append(
+        mat_host_id,
+        router_with_expansion,
+        injected_bugs,
+        manager,
+        session_state,
+    );
+    (router, state)
 }
 
 async fn get_injected_bugs(State(state): State<BmcState>) -> Response {
@@ -34,6 +34,17 @@ pub fn resource<'a>(system_id: &str) -> redfish::Resource<'a> {
     }
 }
 
+/// Pending BIOS settings, applied to the BIOS once the job staging them has run
+pub fn settings_resource<'a>(system_id: &str) -> redfish::Resource<'a> {
+    let odata_id = format!("{}/Settings", resource(system_id).odata_id);
+    redfish::Resource {
+        odata_id: Cow::Owned(odata_id),
+        odata_type: Cow::Borrowed("#Bios.v1_2_0.Bios"),
+        name: Cow::Borrowed("BIOS Pending Settings"),
+        id: Cow::Borrowed("Settings"),
+    }
+}
+
 pub fn change_password_target(resource: &redfish::Resource<'_>) -> String {
     format!("{}/Actions/Bios.ChangePassword", resource.odata_id)
 }
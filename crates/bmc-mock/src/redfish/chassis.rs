@@ -465,3 +465,63 @@ impl ChassisBuilder {
         self.value
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::{Method, StatusCode};
+
+    use super::*;
+    use crate::test_support::{call_router, response_json, wiwynn_gb200_router};
+
+    async fn get_json(router: &Router, uri: &str) -> serde_json::Value {
+        let (status, body) =
+            response_json(call_router(router, Method::GET, uri, &[], "").await).await;
+        assert_eq!(status, StatusCode::OK, "GET {uri}");
+        body
+    }
+
+    fn member_ids(collection: &serde_json::Value) -> Vec<String> {
+        collection["Members"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["@odata.id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_multiple_chassis_have_distinct_device_collections() {
+        let router = wiwynn_gb200_router();
+        let chassis_ids = [
+            "Riser_Slot1_BlueField_3_Card",
+            "Riser_Slot2_BlueField_3_Card",
+        ];
+
+        let chassis_members = member_ids(&get_json(&router, &collection().odata_id).await);
+        for chassis_id in chassis_ids {
+            assert!(chassis_members.contains(&resource(chassis_id).odata_id.to_string()));
+        }
+
+        let mut adapters = Vec::new();
+        for chassis_id in chassis_ids {
+            let chassis = get_json(&router, &resource(chassis_id).odata_id).await;
+            let adapters_uri = chassis["NetworkAdapters"]["@odata.id"].as_str().unwrap();
+            let pcie_uri = chassis["PCIeDevices"]["@odata.id"].as_str().unwrap();
+            assert_eq!(
+                adapters_uri,
+                redfish::network_adapter::chassis_collection(chassis_id).odata_id
+            );
+            assert_eq!(
+                pcie_uri,
+                redfish::pcie_device::chassis_collection(chassis_id).odata_id
+            );
+
+            let members = member_ids(&get_json(&router, adapters_uri).await);
+            assert_eq!(members.len(), 1);
+            assert!(members[0].starts_with(adapters_uri));
+            get_json(&router, &members[0]).await;
+            adapters.extend(members);
+        }
+        assert_ne!(adapters[0], adapters[1]);
+    }
+}
@@ -110,7 +110,7 @@ pub fn add_routes(r: Router<BmcState>, bmc_vendor: redfish::oem::BmcVendor) -> R
         )
         .route(
             &bmc_vendor.make_settings_odata_id(&bios),
-            patch(patch_bios_settings),
+            get(get_bios_settings).patch(patch_bios_settings),
         )
         .route(
             &redfish::bios::change_password_target(&bios),
@@ -596,6 +596,30 @@ async fn get_bios(State(state): State<BmcState>, Path(system_id): Path<String>)
         .unwrap_or_else(http::not_found)
 }
 
+/// BIOS settings that have been PATCHed but not applied yet. Only Dell systems
+/// stage BIOS settings, other systems apply them right away.
+async fn get_bios_settings(
+    State(state): State<BmcState>,
+    Path(system_id): Path<String>,
+) -> Response {
+    let Some(system_state) = state.system_state.find(&system_id) else {
+        return http::not_found();
+    };
+    if system_state.config.base_bios.is_none() {
+        return http::not_found();
+    }
+    let pending = match &state.oem_state {
+        redfish::oem::State::DellIdrac(idrac_state) => {
+            idrac_state.pending_bios_settings(&system_state.bios_overrides)
+        }
+        _ => json!({"Attributes": {}}),
+    };
+    redfish::bios::settings_resource(&system_id)
+        .json_patch()
+        .patch(pending)
+        .into_ok_response()
+}
+
 async fn patch_bios_settings(
     State(state): State<BmcState>,
     Path(system_id): Path<String>,
@@ -624,6 +648,15 @@ async fn patch_bios_settings(
             } else {
                 patch_bios_request
             };
+            if state.injected_bugs.staged_dell_attributes() {
+                // Real iDRACs only apply BIOS settings once their job has run
+                let bios = system_state.bios_overrides.clone();
+                return redfish::oem::dell::idrac::create_bios_settings_job_with_location(
+                    state,
+                    bios,
+                    patch_bios_request,
+                );
+            }
             json_patch(
                 &mut system_state.bios_overrides.lock().expect("mutex poisoned"),
                 patch_bios_request,
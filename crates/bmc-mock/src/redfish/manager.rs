@@ -16,12 +16,13 @@
  */
 
 use std::borrow::Cow;
-use std::sync::{Arc, atomic};
+use std::sync::{Arc, Mutex, atomic};
+use std::time::{Duration, Instant};
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum::response::Response;
-use axum::routing::get;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
 use axum::{Json, Router};
 use chrono::{DateTime, Utc};
 use serde_json::json;
@@ -157,6 +158,7 @@ pub fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
     const ETH_ID: &str = "{ethernet_id}";
     r.route(&collection().odata_id, get(get_manager_collection))
         .route(&resource(MGR_ID).odata_id, get(get_manager))
+        .route(&reset_target(MGR_ID), post(post_reset_manager))
         .route(
             &redfish::ethernet_interface::manager_collection(MGR_ID).odata_id,
             get(get_ethernet_interface_collection),
@@ -200,6 +202,8 @@ pub struct SingleConfig {
 
 pub struct ManagerState {
     managers: Vec<SingleManagerState>,
+    // Set while the BMC is rebooting after a Manager.Reset.
+    unavailable_until: Mutex<Option<Instant>>,
 }
 
 impl ManagerState {
@@ -210,9 +214,27 @@ impl ManagerState {
                 .iter()
                 .map(SingleManagerState::new)
                 .collect(),
+            unavailable_until: Mutex::new(None),
         }
     }
 
+    /// Returns true while the BMC is rebooting after a Manager.Reset.
+    pub fn is_resetting(&self) -> bool {
+        let mut unavailable_until = self.unavailable_until.lock().unwrap();
+        match *unavailable_until {
+            Some(until) if Instant::now() < until => true,
+            Some(_) => {
+                *unavailable_until = None;
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn start_reset(&self, downtime: Duration) {
+        *self.unavailable_until.lock().unwrap() = Some(Instant::now() + downtime);
+    }
+
     pub fn find(&self, manager_id: &str) -> Option<&SingleManagerState> {
         self.managers.iter().find(|c| c.id == manager_id)
     }
@@ -269,6 +291,30 @@ async fn get_manager(State(state): State<BmcState>, Path(manager_id): Path<Strin
         .into_ok_response()
 }
 
+async fn post_reset_manager(
+    State(state): State<BmcState>,
+    Path(manager_id): Path<String>,
+    Json(reset_request): Json<serde_json::Value>,
+) -> Response {
+    if state.manager.find(&manager_id).is_none() {
+        return http::not_found();
+    }
+    match reset_request
+        .get("ResetType")
+        .and_then(serde_json::Value::as_str)
+    {
+        Some("GracefulRestart" | "ForceRestart") => {}
+        _ => {
+            return json!("Valid ResetType is expected field in Reset action")
+                .into_response(StatusCode::BAD_REQUEST);
+        }
+    }
+    if let Some(downtime) = state.injected_bugs.manager_reset_downtime() {
+        state.manager.start_reset(downtime);
+    }
+    StatusCode::NO_CONTENT.into_response()
+}
+
 async fn get_ethernet_interface_collection(
     State(state): State<BmcState>,
     Path(manager_id): Path<String>,
@@ -342,3 +388,64 @@ async fn get_log_services() -> Response {
 fn not_implemented() -> Response {
     json!("").into_response(StatusCode::NOT_IMPLEMENTED)
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+
+    use super::*;
+    use crate::test_support::{call_router, dell_poweredge_r750_router};
+
+    async fn call(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        body: serde_json::Value,
+    ) -> StatusCode {
+        call_router(router, method, uri, &[], body).await.status()
+    }
+
+    #[tokio::test]
+    async fn test_manager_reset_unavailable_then_recovers() {
+        let router = dell_poweredge_r750_router();
+        let manager_uri = resource("iDRAC.Embedded.1").odata_id.to_string();
+
+        let status = call(
+            &router,
+            Method::POST,
+            "/InjectedBugs",
+            json!({"manager_reset_downtime": "300ms"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let status = call(
+            &router,
+            Method::POST,
+            &reset_target("iDRAC.Embedded.1"),
+            json!({"ResetType": "GracefulRestart"}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let status = call(&router, Method::GET, &manager_uri, json!({})).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let status = call(&router, Method::GET, &manager_uri, json!({})).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_manager_reset_requires_reset_type() {
+        let router = dell_poweredge_r750_router();
+        let status = call(
+            &router,
+            Method::POST,
+            &reset_target("iDRAC.Embedded.1"),
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
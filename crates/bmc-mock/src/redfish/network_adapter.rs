@@ -73,7 +73,9 @@ pub fn builder(resource: &redfish::Resource) -> NetworkAdapterBuilder {
 }
 
 pub fn builder_from_nic(resource: &redfish::Resource, nic: &hw::nic::Nic) -> NetworkAdapterBuilder {
-    let b = builder(resource).serial_number(&nic.serial_number);
+    let b = builder(resource)
+        .serial_number(&nic.serial_number)
+        .status(nic.status.unwrap_or(redfish::resource::Status::Ok));
     b.maybe_with(NetworkAdapterBuilder::description, &nic.description)
         .maybe_with(NetworkAdapterBuilder::manufacturer, &nic.manufacturer)
         .maybe_with(NetworkAdapterBuilder::model, &nic.model)
@@ -145,3 +147,36 @@ impl NetworkAdapterBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_from_nic_reports_nic_status() {
+        let mac = "02:00:00:00:00:01".parse().unwrap();
+        let resource = chassis_resource("System.Embedded.1", "NIC.Slot.1");
+
+        let healthy = builder_from_nic(&resource, &hw::nic::Nic::rooftop(mac)).build();
+        assert_eq!(
+            healthy.to_json()["Status"],
+            json!({"State": "Enabled", "Health": "OK"})
+        );
+
+        let degraded_nic = hw::nic::Nic {
+            status: Some(redfish::resource::Status::Critical),
+            ..hw::nic::Nic::rooftop(mac)
+        };
+        let degraded = builder_from_nic(&resource, &degraded_nic).build();
+        assert_eq!(
+            degraded.to_json()["Status"],
+            json!({"State": "Enabled", "Health": "Critical"})
+        );
+        let pcie_device = redfish::pcie_device::builder_from_nic(
+            &redfish::pcie_device::chassis_resource("System.Embedded.1", "mat_1"),
+            &degraded_nic,
+        )
+        .build();
+        assert_eq!(pcie_device.to_json()["Status"]["Health"], "Critical");
+    }
+}
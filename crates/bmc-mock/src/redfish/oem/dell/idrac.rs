@@ -19,6 +19,7 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use axum::Router;
+use axum::body::Bytes;
 use axum::extract::{Json, Path, State};
 use axum::http::{HeaderValue, StatusCode};
 use axum::response::Response;
@@ -41,10 +42,10 @@ pub fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
         get(get_managers_oem_dell_attributes).patch(patch_managers_oem_dell_attributes),
     ).route(
         "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs",
-        post(post_dell_create_bios_job),
+        get(get_dell_jobs).post(post_dell_create_bios_job),
     ).route(
         "/redfish/v1/Managers/iDRAC.Embedded.1/Oem/Dell/Jobs",
-        post(post_dell_create_bios_job),
+        get(get_dell_jobs).post(post_dell_create_bios_job),
     ).route(
         "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/{job_id}",
         get(get_dell_job),
@@ -99,13 +100,25 @@ async fn patch_managers_oem_dell_attributes(
     State(state): State<BmcState>,
     Json(attrs): Json<serde_json::Value>,
 ) -> Response {
-    let redfish::oem::State::DellIdrac(state) = state.oem_state else {
+    let redfish::oem::State::DellIdrac(idrac_state) = &state.oem_state else {
         return http::not_found();
     };
-    state.update_attrs(attrs);
+    if state.injected_bugs.staged_dell_attributes() {
+        // Real iDRACs only apply staged attributes once their job has run
+        return job_location_response(idrac_state.add_job_with_attrs(Some(attrs)));
+    }
+    idrac_state.update_attrs(attrs);
     json!({}).into_ok_response()
 }
 
+fn jobs_collection() -> redfish::Collection<'static> {
+    redfish::Collection {
+        odata_id: Cow::Borrowed("/redfish/v1/Managers/iDRAC.Embedded.1/Oem/Dell/Jobs"),
+        odata_type: Cow::Borrowed("#DellJobCollection.DellJobCollection"),
+        name: Cow::Borrowed("JobQueue"),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum JobState {
     Scheduled,
@@ -117,8 +130,14 @@ async fn get_dell_job(State(state): State<BmcState>, Path(job_id): Path<String>)
         return http::not_found();
     };
     let Some(job) = state.get_job(&job_id) else {
-        return json!(format!("could not find iDRAC job: {job_id}"))
-            .into_response(StatusCode::NOT_FOUND);
+        return http::redfish_error(
+            StatusCode::NOT_FOUND,
+            "Base.1.12.ResourceMissingAtURI",
+            &format!(
+                "The resource at the URI '/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/{job_id}' was not found."
+            ),
+            "Place a valid resource at the URI or correct the URI and resubmit the request.",
+        );
     };
 
     let job_state = match job.job_state {
@@ -150,11 +169,44 @@ async fn get_dell_job(State(state): State<BmcState>, Path(job_id): Path<String>)
     .into_ok_response()
 }
 
+async fn get_dell_jobs(State(state): State<BmcState>) -> Response {
+    let redfish::oem::State::DellIdrac(state) = state.oem_state else {
+        return http::not_found();
+    };
+    let collection = jobs_collection();
+    let members = state
+        .jobs()
+        .iter()
+        .map(|job| {
+            let odata_id = format!("{}/{}", collection.odata_id, job.job_id);
+            json!({ "@odata.id": odata_id })
+        })
+        .collect::<Vec<_>>();
+    collection.with_members(&members).into_ok_response()
+}
+
 pub fn create_job_with_location(state: BmcState) -> Response {
     let redfish::oem::State::DellIdrac(state) = state.oem_state else {
         return http::not_found();
     };
-    match state.add_job() {
+    job_location_response(state.add_job())
+}
+
+/// Creates a job that applies BIOS `settings` to `bios` once it completes, like
+/// a real iDRAC does.
+pub fn create_bios_settings_job_with_location(
+    state: BmcState,
+    bios: Arc<Mutex<serde_json::Value>>,
+    settings: serde_json::Value,
+) -> Response {
+    let redfish::oem::State::DellIdrac(state) = state.oem_state else {
+        return http::not_found();
+    };
+    job_location_response(state.add_job_with_bios_settings(bios, settings))
+}
+
+fn job_location_response(job: Result<String, Box<dyn std::error::Error>>) -> Response {
+    match job {
         Ok(job_id) => json!({}).into_ok_response_with_location(
             HeaderValue::try_from(format!(
                 "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/{job_id}"
@@ -173,7 +225,18 @@ async fn post_delete_job_queue() -> Response {
     json!({}).into_ok_response()
 }
 
-async fn post_import_sys_configuration(State(state): State<BmcState>) -> Response {
+async fn post_import_sys_configuration(State(state): State<BmcState>, body: Bytes) -> Response {
+    let share_parameters = serde_json::from_slice::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|v| v.get("ShareParameters").cloned());
+    if !share_parameters.is_some_and(|v| v.is_object()) {
+        return http::redfish_error(
+            StatusCode::BAD_REQUEST,
+            "Base.1.12.PropertyMissing",
+            "The property ShareParameters is a required property and must be included in the request.",
+            "Ensure that the property is in the request body and has a valid value and resubmit the request if the operation failed.",
+        );
+    }
     create_job_with_location(state)
 }
 
@@ -186,6 +249,19 @@ pub struct Job {
     pub job_type: String,
     pub start_time: chrono::DateTime<chrono::Utc>,
     pub end_time: Option<chrono::DateTime<chrono::Utc>>,
+    /// Attributes staged by a PATCH, applied when the job completes.
+    pub pending_attrs: Option<serde_json::Value>,
+    /// BIOS settings staged by a PATCH, applied when the job completes.
+    pub pending_bios_settings: Option<PendingBiosSettings>,
+    /// Job that has to complete before this one can run.
+    pub depends_on: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingBiosSettings {
+    /// BIOS attributes of the system the settings are for.
+    pub bios: Arc<Mutex<serde_json::Value>>,
+    pub settings: serde_json::Value,
 }
 
 impl Job {
@@ -193,6 +269,10 @@ impl Job {
         matches!(self.job_type.as_str(), DELL_JOB_TYPE)
     }
 
+    pub fn is_completed(&self) -> bool {
+        matches!(self.job_state, JobState::Completed)
+    }
+
     pub fn percent_complete(&self) -> i32 {
         match &self.job_state {
             JobState::Completed => 100,
@@ -221,9 +301,58 @@ impl IdracState {
         self.jobs.lock().unwrap().get(job_id).cloned()
     }
 
+    /// All jobs in the queue, oldest first.
+    pub fn jobs(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = self.jobs.lock().unwrap().values().cloned().collect();
+        jobs.sort_by(|a, b| (a.start_time, &a.job_id).cmp(&(b.start_time, &b.job_id)));
+        jobs
+    }
+
     pub fn add_job(&self) -> Result<String, Box<dyn std::error::Error>> {
+        self.add_job_with_attrs(None)
+    }
+
+    /// Adds a job that applies `pending_attrs` to the iDRAC attributes once
+    /// it completes.
+    pub fn add_job_with_attrs(
+        &self,
+        pending_attrs: Option<serde_json::Value>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        self.insert_job(pending_attrs, None, None)
+    }
+
+    /// Adds a job that applies BIOS `settings` to `bios` once it completes.
+    pub fn add_job_with_bios_settings(
+        &self,
+        bios: Arc<Mutex<serde_json::Value>>,
+        settings: serde_json::Value,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        self.insert_job(None, Some(PendingBiosSettings { bios, settings }), None)
+    }
+
+    /// Adds a job that stays scheduled until the `prerequisite` job has
+    /// completed, like a config job waiting for a reboot job.
+    pub fn add_dependent_job(
+        &self,
+        prerequisite: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        self.insert_job(None, None, Some(prerequisite.to_string()))
+    }
+
+    fn insert_job(
+        &self,
+        pending_attrs: Option<serde_json::Value>,
+        pending_bios_settings: Option<PendingBiosSettings>,
+        depends_on: Option<String>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
         let mut jobs = self.jobs.lock().unwrap();
 
+        if let Some(prerequisite) = &depends_on
+            && !jobs.contains_key(prerequisite)
+        {
+            return Err(format!("Prerequisite job {prerequisite} does not exist").into());
+        }
+
         let job_id = rand::rng()
             .sample_iter::<u64, _>(StandardUniform)
             .map(|r| format!("JID_{r}"))
@@ -236,27 +365,79 @@ impl IdracState {
             job_type: DELL_JOB_TYPE.to_string(),
             start_time: chrono::offset::Utc::now(),
             end_time: None,
+            pending_attrs,
+            pending_bios_settings,
+            depends_on,
         };
 
         jobs.insert(job_id.clone(), job);
         Ok(job_id)
     }
 
+    /// Completes every scheduled job, prerequisites before the jobs that
+    /// depend on them.
     pub fn complete_all_bios_jobs(&self) {
         let mut jobs = self.jobs.lock().unwrap();
 
-        let bios_jobs: Vec<Job> = jobs
-            .values()
-            .filter(|job| job.is_dell_job())
-            .cloned()
-            .collect();
-        for mut job in bios_jobs {
-            job.job_state = JobState::Completed;
-            job.end_time = Some(chrono::offset::Utc::now());
-            jobs.insert(job.job_id.clone(), job);
+        loop {
+            let ready: Vec<String> = jobs
+                .values()
+                .filter(|job| job.is_dell_job() && !job.is_completed())
+                .filter(|job| prerequisite_completed(&jobs, job))
+                .map(|job| job.job_id.clone())
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            for job_id in ready {
+                self.finish_job(jobs.get_mut(&job_id).unwrap());
+            }
         }
     }
 
+    /// Completes a single job. Fails while its prerequisite is still scheduled.
+    pub fn complete_job(&self, job_id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut jobs = self.jobs.lock().unwrap();
+
+        let Some(job) = jobs.get(job_id) else {
+            return Err(format!("Job {job_id} does not exist").into());
+        };
+        if !prerequisite_completed(&jobs, job) {
+            return Err(format!(
+                "Job {job_id} is waiting for job {}",
+                job.depends_on.as_deref().unwrap_or_default()
+            )
+            .into());
+        }
+        self.finish_job(jobs.get_mut(job_id).unwrap());
+        Ok(())
+    }
+
+    fn finish_job(&self, job: &mut Job) {
+        job.job_state = JobState::Completed;
+        job.end_time = Some(chrono::offset::Utc::now());
+        if let Some(attrs) = job.pending_attrs.take() {
+            self.update_attrs(attrs);
+        }
+        if let Some(pending) = job.pending_bios_settings.take() {
+            json_patch(&mut pending.bios.lock().unwrap(), pending.settings);
+        }
+    }
+
+    /// BIOS settings staged for `bios` by jobs that have not completed yet.
+    pub fn pending_bios_settings(&self, bios: &Arc<Mutex<serde_json::Value>>) -> serde_json::Value {
+        let mut pending = json!({"Attributes": {}});
+        for job in self.jobs() {
+            if let Some(staged) = job
+                .pending_bios_settings
+                .filter(|staged| Arc::ptr_eq(&staged.bios, bios))
+            {
+                json_patch(&mut pending, staged.settings);
+            }
+        }
+        pending
+    }
+
     pub fn update_attrs(&self, v: serde_json::Value) {
         let mut dell_attrs = self.dell_attrs.lock().unwrap();
         json_patch(&mut dell_attrs, v);
@@ -268,3 +449,257 @@ impl IdracState {
         base
     }
 }
+
+fn prerequisite_completed(jobs: &HashMap<String, Job>, job: &Job) -> bool {
+    job.depends_on
+        .as_ref()
+        .is_none_or(|prerequisite| jobs.get(prerequisite).is_some_and(Job::is_completed))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+
+    use super::*;
+    use crate::test_support::{
+        call_router, dell_poweredge_r750_router, dell_poweredge_r750_router_with_state,
+        response_json,
+    };
+
+    async fn call(method: Method, uri: &str, body: &str) -> (StatusCode, serde_json::Value) {
+        call_json(&dell_poweredge_r750_router(), method, uri, body).await
+    }
+
+    async fn call_json(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        body: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        response_json(call_router(router, method, uri, &[], body).await).await
+    }
+
+    fn assert_error_shape(body: &serde_json::Value, message_id: &str) {
+        let error = &body["error"];
+        assert!(error["code"].is_string(), "{body}");
+        assert!(error["message"].is_string(), "{body}");
+        let extended_info = error["@Message.ExtendedInfo"].as_array().unwrap();
+        assert_eq!(extended_info.len(), 1);
+        assert_eq!(extended_info[0]["MessageId"], message_id);
+        assert!(extended_info[0]["Message"].is_string());
+        assert!(extended_info[0]["Resolution"].is_string());
+        assert!(extended_info[0]["Severity"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_job_not_found_error() {
+        let (status, body) = call(
+            Method::GET,
+            "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs/JID_missing",
+            "",
+        )
+        .await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_error_shape(&body, "Base.1.12.ResourceMissingAtURI");
+    }
+
+    #[tokio::test]
+    async fn test_import_config_invalid_error() {
+        let uri = "/redfish/v1/Managers/iDRAC.Embedded.1/Actions/Oem/EID_674_Manager.ImportSystemConfiguration";
+
+        let (status, body) = call(Method::POST, uri, r#"{"ImportBuffer": ""}"#).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_error_shape(&body, "Base.1.12.PropertyMissing");
+
+        let (status, _) = call(
+            Method::POST,
+            uri,
+            r#"{"ShareParameters": {"Target": "BIOS"}, "ImportBuffer": ""}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_list_jobs() {
+        let router = dell_poweredge_r750_router();
+        let jobs_uri = "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs";
+
+        let (status, body) = call_json(&router, Method::GET, jobs_uri, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["Members@odata.count"], 0);
+
+        let mut job_ids = Vec::new();
+        for _ in 0..2 {
+            let response = call_router(&router, Method::POST, jobs_uri, &[], "{}").await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let location = response.headers()["location"].to_str().unwrap();
+            job_ids.push(location.rsplit('/').next().unwrap().to_string());
+        }
+
+        for uri in [
+            jobs_uri,
+            "/redfish/v1/Managers/iDRAC.Embedded.1/Oem/Dell/Jobs",
+        ] {
+            let (status, body) = call_json(&router, Method::GET, uri, "").await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["@odata.type"], "#DellJobCollection.DellJobCollection");
+            assert_eq!(body["Members@odata.count"], 2);
+            let members = body["Members"].as_array().unwrap();
+            for job_id in &job_ids {
+                let job_uri =
+                    format!("/redfish/v1/Managers/iDRAC.Embedded.1/Oem/Dell/Jobs/{job_id}");
+                assert!(
+                    members.iter().any(|m| m["@odata.id"] == job_uri.as_str()),
+                    "{job_id} missing from {body}"
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_staged_attributes_applied_when_job_completes() {
+        let (router, state) = dell_poweredge_r750_router_with_state();
+        let attrs_uri = "/redfish/v1/Managers/iDRAC.Embedded.1/Attributes";
+
+        let (status, _) = call_json(
+            &router,
+            Method::POST,
+            "/InjectedBugs",
+            r#"{"staged_dell_attributes": true}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = call_json(
+            &router,
+            Method::PATCH,
+            attrs_uri,
+            r#"{"Attributes": {"SSH.1.Enable": "Disabled"}}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (_, body) = call_json(&router, Method::GET, attrs_uri, "").await;
+        assert_eq!(body["Attributes"]["SSH.1.Enable"], "Enabled");
+        let (_, jobs) = call_json(
+            &router,
+            Method::GET,
+            "/redfish/v1/Managers/iDRAC.Embedded.1/Jobs",
+            "",
+        )
+        .await;
+        assert_eq!(jobs["Members@odata.count"], 1);
+
+        state.complete_all_bios_jobs();
+
+        let (_, body) = call_json(&router, Method::GET, attrs_uri, "").await;
+        assert_eq!(body["Attributes"]["SSH.1.Enable"], "Disabled");
+    }
+    #[tokio::test]
+    async fn test_staged_bios_settings_applied_when_job_completes() {
+        let (router, state) = dell_poweredge_r750_router_with_state();
+        let bios_uri = "/redfish/v1/Systems/System.Embedded.1/Bios";
+
+        let (status, _) = call_json(
+            &router,
+            Method::POST,
+            "/InjectedBugs",
+            r#"{"staged_dell_attributes": true}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, _) = call_json(
+            &router,
+            Method::PATCH,
+            "/redfish/v1/Systems/System.Embedded.1/Bios/Settings",
+            r#"{"Attributes": {"SriovGlobalEnable": "Disabled"}}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        let (status, body) = call_json(&router, Method::GET, bios_uri, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["Attributes"]["SriovGlobalEnable"], "Enabled");
+
+        state.complete_all_bios_jobs();
+
+        let (_, body) = call_json(&router, Method::GET, bios_uri, "").await;
+        assert_eq!(body["Attributes"]["SriovGlobalEnable"], "Disabled");
+        assert_eq!(body["Attributes"]["TpmSecurity"], "On");
+    }
+
+    #[tokio::test]
+    async fn test_pending_bios_settings_differ_until_job_completes() {
+        let (router, state) = dell_poweredge_r750_router_with_state();
+        let bios_uri = "/redfish/v1/Systems/System.Embedded.1/Bios";
+        let settings_uri = "/redfish/v1/Systems/System.Embedded.1/Bios/Settings";
+
+        call_json(
+            &router,
+            Method::POST,
+            "/InjectedBugs",
+            r#"{"staged_dell_attributes": true}"#,
+        )
+        .await;
+
+        let (status, body) = call_json(&router, Method::GET, settings_uri, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["@odata.id"], settings_uri);
+        assert_eq!(body["Attributes"], json!({}));
+
+        for patch in [
+            r#"{"Attributes": {"SriovGlobalEnable": "Disabled"}}"#,
+            r#"{"Attributes": {"HttpDev1EnDis": "Disabled"}}"#,
+        ] {
+            let (status, _) = call_json(&router, Method::PATCH, settings_uri, patch).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let (_, pending) = call_json(&router, Method::GET, settings_uri, "").await;
+        assert_eq!(
+            pending["Attributes"],
+            json!({"SriovGlobalEnable": "Disabled", "HttpDev1EnDis": "Disabled"})
+        );
+        let (_, current) = call_json(&router, Method::GET, bios_uri, "").await;
+        assert_eq!(current["Attributes"]["SriovGlobalEnable"], "Enabled");
+        assert_eq!(current["Attributes"]["HttpDev1EnDis"], "Enabled");
+
+        state.complete_all_bios_jobs();
+
+        let (_, pending) = call_json(&router, Method::GET, settings_uri, "").await;
+        assert_eq!(pending["Attributes"], json!({}));
+        let (_, current) = call_json(&router, Method::GET, bios_uri, "").await;
+        assert_eq!(current["Attributes"]["SriovGlobalEnable"], "Disabled");
+        assert_eq!(current["Attributes"]["HttpDev1EnDis"], "Disabled");
+    }
+
+    #[test]
+    fn test_dependent_job_waits_for_prerequisite() {
+        let state = IdracState::default();
+        assert!(state.add_dependent_job("JID_missing").is_err());
+
+        let reboot_job = state.add_job().unwrap();
+        let config_job = state.add_dependent_job(&reboot_job).unwrap();
+
+        assert!(state.complete_job(&config_job).is_err());
+        assert!(!state.get_job(&config_job).unwrap().is_completed());
+
+        state.complete_job(&reboot_job).unwrap();
+        assert!(state.get_job(&reboot_job).unwrap().is_completed());
+        assert!(!state.get_job(&config_job).unwrap().is_completed());
+
+        state.complete_job(&config_job).unwrap();
+        assert!(state.get_job(&config_job).unwrap().is_completed());
+
+        // Completing everything at once still respects the ordering
+        let reboot_job = state.add_job().unwrap();
+        let config_job = state.add_dependent_job(&reboot_job).unwrap();
+        state.complete_all_bios_jobs();
+        let reboot_job = state.get_job(&reboot_job).unwrap();
+        let config_job = state.get_job(&config_job).unwrap();
+        assert!(reboot_job.is_completed() && config_job.is_completed());
+        assert!(reboot_job.end_time <= config_job.end_time);
+    }
+}
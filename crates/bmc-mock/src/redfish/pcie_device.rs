@@ -54,7 +54,9 @@ pub fn builder(resource: &redfish::Resource) -> PcieDeviceBuilder {
 }
 
 pub fn builder_from_nic(resource: &redfish::Resource, nic: &hw::nic::Nic) -> PcieDeviceBuilder {
-    let b = builder(resource).serial_number(&nic.serial_number);
+    let b = builder(resource)
+        .serial_number(&nic.serial_number)
+        .status(nic.status.unwrap_or(redfish::resource::Status::Ok));
     let b = if nic.is_mat_dpu { b.mat_dpu() } else { b };
     b.maybe_with(PcieDeviceBuilder::description, &nic.description)
         .maybe_with(PcieDeviceBuilder::manufacturer, &nic.manufacturer)
@@ -65,15 +65,23 @@ impl JsonPatch for Resource<'_> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Status {
     Ok,
+    Warning,
+    Critical,
 }
 
 impl Status {
     pub fn into_json(self) -> serde_json::Value {
+        let health = match self {
+            Status::Ok => "OK",
+            Status::Warning => "Warning",
+            Status::Critical => "Critical",
+        };
         json!({
             "State": "Enabled",
-            "Health": "OK",
+            "Health": health,
         })
     }
 }
@@ -55,6 +55,8 @@ async fn get_service_root(State(state): State<BmcState>) -> Response {
         .vendor(state.bmc_vendor.service_root_value())
         .maybe_with(ServiceRootBuilder::product, &state.bmc_product)
         .account_service(&redfish::account_service::resource())
+        .session_service(&redfish::session_service::resource())
+        .sessions(&redfish::session_service::collection())
         .chassis_collection(&redfish::chassis::collection())
         .system_collection(&redfish::computer_system::collection())
         .manager_collection(&redfish::manager::collection())
@@ -96,6 +98,14 @@ impl ServiceRootBuilder {
         self.apply_patch(v.nav_property("AccountService"))
     }
 
+    pub fn session_service(self, v: &redfish::Resource<'_>) -> Self {
+        self.apply_patch(v.nav_property("SessionService"))
+    }
+
+    pub fn sessions(self, v: &redfish::Collection<'_>) -> Self {
+        self.apply_patch(json!({ "Links": v.nav_property("Sessions") }))
+    }
+
     pub fn chassis_collection(self, v: &redfish::Collection<'_>) -> Self {
         self.apply_patch(v.nav_property("Chassis"))
     }
@@ -0,0 +1,290 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use axum::Router;
+use axum::extract::{Json, Path, State};
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use serde_json::json;
+
+use crate::bmc_state::BmcState;
+use crate::json::JsonExt;
+use crate::{http, redfish};
+
+/// Header carrying the session token, both when a session is created and on
+/// subsequent requests made with it.
+pub const AUTH_TOKEN_HEADER: &str = "X-Auth-Token";
+
+pub fn resource() -> redfish::Resource<'static> {
+    redfish::Resource {
+        odata_id: Cow::Borrowed("/redfish/v1/SessionService"),
+        odata_type: Cow::Borrowed("#SessionService.v1_1_8.SessionService"),
+        id: Cow::Borrowed("SessionService"),
+        name: Cow::Borrowed("Session Service"),
+    }
+}
+
+pub fn collection() -> redfish::Collection<'static> {
+    redfish::Collection {
+        odata_id: Cow::Borrowed("/redfish/v1/SessionService/Sessions"),
+        odata_type: Cow::Borrowed("#SessionCollection.SessionCollection"),
+        name: Cow::Borrowed("Session Collection"),
+    }
+}
+
+pub fn session_resource(session_id: &str) -> redfish::Resource<'static> {
+    redfish::Resource {
+        odata_id: Cow::Owned(format!("{}/{session_id}", collection().odata_id)),
+        odata_type: Cow::Borrowed("#Session.v1_3_0.Session"),
+        id: Cow::Owned(session_id.to_string()),
+        name: Cow::Borrowed("User Session"),
+    }
+}
+
+pub fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
+    r.route(&resource().odata_id, get(get_root))
+        .route(
+            &collection().odata_id,
+            get(get_sessions).post(create_session),
+        )
+        .route(
+            &session_resource("{session_id}").odata_id,
+            get(get_session).delete(delete_session),
+        )
+}
+
+struct Session {
+    user_name: String,
+    token: String,
+}
+
+#[derive(Default)]
+pub struct SessionState {
+    sessions: Mutex<HashMap<String, Session>>,
+}
+
+impl SessionState {
+    /// Returns true if `token` belongs to a session that hasn't been deleted.
+    pub fn is_valid_token(&self, token: &str) -> bool {
+        self.sessions
+            .lock()
+            .unwrap()
+            .values()
+            .any(|session| session.token == token)
+    }
+
+    fn create(&self, user_name: String) -> (String, String) {
+        let mut sessions = self.sessions.lock().unwrap();
+        let session_id = (1..)
+            .map(|n: u64| n.to_string())
+            .find(|id| !sessions.contains_key(id))
+            .unwrap();
+        let token: String = rand::rng()
+            .sample_iter(Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect();
+        sessions.insert(
+            session_id.clone(),
+            Session {
+                user_name,
+                token: token.clone(),
+            },
+        );
+        (session_id, token)
+    }
+}
+
+/// Returns true if `path` can be reached without a session, as on a real BMC
+/// where the service root and session creation are unauthenticated.
+pub fn is_unauthenticated_path(method: &axum::http::Method, path: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    path == redfish::service_root::resource().odata_id
+        || (method == axum::http::Method::POST && path == collection().odata_id)
+}
+
+async fn get_root() -> Response {
+    json!({
+        "ServiceEnabled": true,
+        "SessionTimeout": 1800,
+    })
+    .patch(resource())
+    .patch(collection().nav_property("Sessions"))
+    .into_ok_response()
+}
+
+async fn get_sessions(State(state): State<BmcState>) -> Response {
+    let mut session_ids = state
+        .session_state
+        .sessions
+        .lock()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    session_ids.sort();
+    let members = session_ids
+        .iter()
+        .map(|id| session_resource(id).entity_ref())
+        .collect::<Vec<_>>();
+    collection().with_members(&members).into_ok_response()
+}
+
+async fn create_session(
+    State(state): State<BmcState>,
+    Json(request): Json<serde_json::Value>,
+) -> Response {
+    let Some(user_name) = request.get("UserName").and_then(serde_json::Value::as_str) else {
+        return json!("UserName is expected field in Session creation")
+            .into_response(StatusCode::BAD_REQUEST);
+    };
+    if request
+        .get("Password")
+        .and_then(serde_json::Value::as_str)
+        .is_none()
+    {
+        return json!("Password is expected field in Session creation")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+
+    let (session_id, token) = state.session_state.create(user_name.to_string());
+    let location = session_resource(&session_id).odata_id.to_string();
+    let mut response = json!({ "UserName": user_name })
+        .patch(session_resource(&session_id))
+        .into_response(StatusCode::CREATED);
+    let headers = response.headers_mut();
+    headers.insert(
+        AUTH_TOKEN_HEADER,
+        HeaderValue::from_str(&token).expect("token is alphanumeric"),
+    );
+    headers.insert(
+        "Location",
+        HeaderValue::from_str(&location).expect("session path is valid"),
+    );
+    response
+}
+
+async fn get_session(State(state): State<BmcState>, Path(session_id): Path<String>) -> Response {
+    let sessions = state.session_state.sessions.lock().unwrap();
+    let Some(session) = sessions.get(&session_id) else {
+        return http::not_found();
+    };
+    json!({ "UserName": session.user_name })
+        .patch(session_resource(&session_id))
+        .into_ok_response()
+}
+
+async fn delete_session(State(state): State<BmcState>, Path(session_id): Path<String>) -> Response {
+    match state
+        .session_state
+        .sessions
+        .lock()
+        .unwrap()
+        .remove(&session_id)
+    {
+        Some(_) => StatusCode::NO_CONTENT.into_response(),
+        None => http::not_found(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+
+    use super::*;
+    use crate::test_support::{call_router, dell_poweredge_r750_router};
+
+    async fn call(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: serde_json::Value,
+    ) -> Response {
+        let headers: Vec<_> = token
+            .map(|token| (AUTH_TOKEN_HEADER, token))
+            .into_iter()
+            .collect();
+        call_router(router, method, uri, &headers, body).await
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let router = dell_poweredge_r750_router();
+        let systems_uri = redfish::computer_system::collection().odata_id.to_string();
+
+        let response = call(
+            &router,
+            Method::POST,
+            "/InjectedBugs",
+            None,
+            json!({"require_session_auth": true}),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = call(&router, Method::GET, &systems_uri, None, json!({})).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = call(
+            &router,
+            Method::POST,
+            &collection().odata_id,
+            None,
+            json!({"UserName": "root", "Password": "password"}),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let token = response.headers()[AUTH_TOKEN_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let location = response.headers()["Location"].to_str().unwrap().to_string();
+
+        let response = call(&router, Method::GET, &systems_uri, Some(&token), json!({})).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = call(&router, Method::GET, &systems_uri, Some("bogus"), json!({})).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = call(&router, Method::DELETE, &location, Some(&token), json!({})).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = call(&router, Method::GET, &systems_uri, Some(&token), json!({})).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_create_session_requires_credentials() {
+        let router = dell_poweredge_r750_router();
+        let response = call(
+            &router,
+            Method::POST,
+            &collection().odata_id,
+            None,
+            json!({"UserName": "root"}),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
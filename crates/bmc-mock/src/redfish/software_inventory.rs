@@ -51,6 +51,7 @@ pub fn builder(resource: &redfish::Resource) -> SoftwareInventoryBuilder {
     }
 }
 
+#[derive(Clone)]
 pub struct SoftwareInventory {
     pub id: Cow<'static, str>,
     value: serde_json::Value,
@@ -60,6 +61,16 @@ impl SoftwareInventory {
     pub fn to_json(&self) -> serde_json::Value {
         self.value.clone()
     }
+
+    pub fn version(&self) -> Option<&str> {
+        self.value
+            .get("Version")
+            .and_then(serde_json::Value::as_str)
+    }
+
+    pub fn set_version(&mut self, version: &str) {
+        self.value["Version"] = version.into();
+    }
 }
 
 pub struct SoftwareInventoryBuilder {
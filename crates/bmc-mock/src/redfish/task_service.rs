@@ -16,6 +16,7 @@
  */
 
 use axum::Router;
+use axum::extract::State;
 use axum::response::Response;
 use axum::routing::get;
 use serde_json::json;
@@ -27,7 +28,9 @@ pub fn add_routes(r: Router<BmcState>) -> Router<BmcState> {
     r.route("/redfish/v1/TaskService/Tasks/{task_id}", get(get_task))
 }
 
-async fn get_task() -> Response {
+async fn get_task(State(state): State<BmcState>) -> Response {
+    // Tasks complete as soon as they are polled.
+    state.update_service_state.complete_pending_updates();
     json!({
         "@odata.id": "/redfish/v1/TaskService/Tasks/0",
         "@odata.type": "#Task.v1_4_3.Task",
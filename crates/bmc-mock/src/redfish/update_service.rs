@@ -16,11 +16,14 @@
  */
 
 use std::borrow::Cow;
+use std::sync::Mutex;
 
 use axum::Router;
-use axum::extract::{Path, State};
+use axum::extract::{Json, Path, State};
+use axum::http::StatusCode;
 use axum::response::Response;
 use axum::routing::{get, post};
+use serde_json::json;
 
 use crate::bmc_state::BmcState;
 use crate::json::{JsonExt, JsonPatch};
@@ -65,21 +68,66 @@ pub struct UpdateServiceConfig {
 }
 
 pub struct UpdateServiceState {
-    firmware_inventory: Vec<redfish::software_inventory::SoftwareInventory>,
+    firmware_inventory: Mutex<Vec<redfish::software_inventory::SoftwareInventory>>,
+    // Firmware inventory ids targeted by SimpleUpdate requests whose task
+    // hasn't completed yet.
+    pending_updates: Mutex<Vec<String>>,
 }
 
 impl UpdateServiceState {
     pub fn from_config(config: UpdateServiceConfig) -> Self {
         Self {
-            firmware_inventory: config.firmware_inventory,
+            firmware_inventory: Mutex::new(config.firmware_inventory),
+            pending_updates: Mutex::default(),
         }
     }
 
     pub fn find_firmware_inventory(
         &self,
         id: &str,
-    ) -> Option<&redfish::software_inventory::SoftwareInventory> {
-        self.firmware_inventory.iter().find(|v| v.id == id)
+    ) -> Option<redfish::software_inventory::SoftwareInventory> {
+        self.firmware_inventory
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|v| v.id == id)
+            .cloned()
+    }
+
+    /// Applies all pending SimpleUpdate requests, bumping the version of
+    /// each targeted firmware inventory entry.
+    pub fn complete_pending_updates(&self) {
+        let pending = std::mem::take(&mut *self.pending_updates.lock().unwrap());
+        let mut inventory = self.firmware_inventory.lock().unwrap();
+        for id in pending {
+            if let Some(fw) = inventory.iter_mut().find(|v| v.id == id) {
+                let version = bump_version(fw.version().unwrap_or_default());
+                fw.set_version(&version);
+            }
+        }
+    }
+}
+
+/// Increments the last number in a version string, e.g. `1.2.3` becomes
+/// `1.2.4` and `BF-24.10-17` becomes `BF-24.10-18`. Zero padding is kept, so
+/// `WW_02` becomes `WW_03`. A version without any number gets `.1` appended.
+fn bump_version(version: &str) -> String {
+    let end = match version.rfind(|c: char| c.is_ascii_digit()) {
+        Some(i) => i + 1,
+        None => return format!("{version}.1"),
+    };
+    let start = version[..end]
+        .rfind(|c: char| !c.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    match version[start..end].parse::<u64>() {
+        Ok(n) => format!(
+            "{}{:0width$}{}",
+            &version[..start],
+            n + 1,
+            &version[end..],
+            width = end - start
+        ),
+        Err(_) => format!("{version}.1"),
     }
 }
 
@@ -90,7 +138,33 @@ async fn get_update_service() -> Response {
         .into_ok_response()
 }
 
-async fn update_firmware_simple_update() -> Response {
+async fn update_firmware_simple_update(
+    State(state): State<BmcState>,
+    Json(request): Json<serde_json::Value>,
+) -> Response {
+    if request
+        .get("ImageURI")
+        .and_then(serde_json::Value::as_str)
+        .is_none()
+    {
+        return json!("ImageURI is expected field in SimpleUpdate action")
+            .into_response(StatusCode::BAD_REQUEST);
+    }
+    // Targets are firmware inventory URIs; keep only the inventory ids.
+    let targets = request
+        .get("Targets")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(serde_json::Value::as_str)
+        .filter_map(|target| target.rsplit('/').next())
+        .map(str::to_string);
+    state
+        .update_service_state
+        .pending_updates
+        .lock()
+        .unwrap()
+        .extend(targets);
     redfish::task_service::update_firmware_simple_update_task()
 }
 
@@ -98,6 +172,8 @@ async fn get_firmware_inventory_collection(State(state): State<BmcState>) -> Res
     let members = state
         .update_service_state
         .firmware_inventory
+        .lock()
+        .unwrap()
         .iter()
         .map(|sw| redfish::software_inventory::firmware_inventory_resource(&sw.id).entity_ref())
         .collect::<Vec<_>>();
@@ -138,3 +214,85 @@ impl UpdateServiceBuilder {
         self.apply_patch(v.nav_property("FirmwareInventory"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Method;
+
+    use super::*;
+    use crate::test_support::{call_router, response_json, wiwynn_gb200_router};
+
+    async fn call(
+        router: &Router,
+        method: Method,
+        uri: &str,
+        body: serde_json::Value,
+    ) -> (StatusCode, serde_json::Value) {
+        response_json(call_router(router, method, uri, &[], body).await).await
+    }
+
+    #[test]
+    fn test_bump_version() {
+        assert_eq!(bump_version("32.47.1026"), "32.47.1027");
+        assert_eq!(bump_version("25.06-2_NV_WW_02"), "25.06-2_NV_WW_03");
+        assert_eq!(bump_version("02.04.12-dde0f655"), "02.04.12-dde0f656");
+        assert_eq!(bump_version("0.9"), "0.10");
+        assert_eq!(bump_version("    "), "    .1");
+    }
+
+    #[test]
+    fn test_bump_version_keeps_zero_padding() {
+        assert_eq!(bump_version("1.0.007"), "1.0.008");
+        assert_eq!(bump_version("24.09"), "24.10");
+        assert_eq!(bump_version("WW_099"), "WW_100");
+        assert_eq!(bump_version("WW_99"), "WW_100");
+        assert_eq!(bump_version("00"), "01");
+    }
+
+    #[tokio::test]
+    async fn test_simple_update_bumps_firmware_inventory_version() {
+        let router = wiwynn_gb200_router();
+        let nic_uri = redfish::software_inventory::firmware_inventory_resource("NIC_0")
+            .odata_id
+            .to_string();
+
+        let (status, inventory) = call(
+            &router,
+            Method::GET,
+            &redfish::software_inventory::firmware_inventory_collection().odata_id,
+            json!({}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(
+            inventory["Members"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|m| m["@odata.id"] == nic_uri.as_str())
+        );
+
+        let (_, nic) = call(&router, Method::GET, &nic_uri, json!({})).await;
+        assert_eq!(nic["Version"], "32.47.1026");
+
+        let (status, task) = call(
+            &router,
+            Method::POST,
+            &simple_update_target(),
+            json!({"ImageURI": "http://example.com/nic.bin", "Targets": [nic_uri]}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+
+        // The version is only updated once the update task completes.
+        let (_, nic) = call(&router, Method::GET, &nic_uri, json!({})).await;
+        assert_eq!(nic["Version"], "32.47.1026");
+
+        let task_uri = task["@odata.id"].as_str().unwrap();
+        let (_, task) = call(&router, Method::GET, task_uri, json!({})).await;
+        assert_eq!(task["TaskState"], "Completed");
+
+        let (_, nic) = call(&router, Method::GET, &nic_uri, json!({})).await;
+        assert_eq!(nic["Version"], "32.47.1027");
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Serves a mocked BMC on an ephemeral local port so tests in other crates
+//! can talk to it over real HTTP, the same way carbide talks to a BMC.
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use url::Url;
+
+use super::NoopPowerControl;
+use crate::{BmcState, HostHardwareType, HostMachineInfo, MachineInfo, machine_router_with_state};
+
+/// A running bmc-mock instance. The server is stopped when this is dropped.
+pub struct BmcMockHarness {
+    /// Base URL of the mock, e.g. `http://127.0.0.1:41234`.
+    pub base_url: Url,
+    pub address: SocketAddr,
+    /// State behind the served router. Changes made here are visible to the
+    /// next request.
+    pub state: BmcState,
+    join_handle: JoinHandle<()>,
+}
+
+impl BmcMockHarness {
+    /// Starts a mock BMC for the given machine on `127.0.0.1:0`.
+    pub async fn start(machine_info: MachineInfo) -> std::io::Result<Self> {
+        let (router, state) = machine_router_with_state(
+            machine_info,
+            Arc::new(NoopPowerControl),
+            "test-host-id".to_string(),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let join_handle = tokio::spawn(async move {
+            if let Err(err) = axum::serve(listener, router).await {
+                tracing::error!("bmc-mock harness at {address} failed: {err}");
+            }
+        });
+        let base_url = Url::parse(&format!("http://{address}")).expect("valid URL");
+        Ok(Self {
+            base_url,
+            address,
+            state,
+            join_handle,
+        })
+    }
+
+    /// Starts a mock Dell PowerEdge R750 host BMC.
+    pub async fn dell_poweredge_r750() -> std::io::Result<Self> {
+        Self::start(MachineInfo::Host(HostMachineInfo::new(
+            HostHardwareType::DellPowerEdgeR750,
+            vec![],
+        )))
+        .await
+    }
+
+    /// Returns the absolute URL for `path` on this mock, e.g. `/redfish/v1`.
+    pub fn url(&self, path: &str) -> Url {
+        self.base_url.join(path).expect("valid path")
+    }
+}
+
+impl Drop for BmcMockHarness {
+    fn drop(&mut self) {
+        self.join_handle.abort();
+    }
+}
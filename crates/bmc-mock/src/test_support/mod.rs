@@ -21,11 +21,13 @@ use nv_redfish::bmc_http::{BmcCredentials, CacheSettings, HttpBmc};
 use url::Url;
 
 use crate::{
-    DpuFirmwareVersions, DpuMachineInfo, HostHardwareType, HostMachineInfo, MachineInfo,
+    BmcState, DpuFirmwareVersions, DpuMachineInfo, HostHardwareType, HostMachineInfo, MachineInfo,
     MockPowerState, PowerControl, SetSystemPowerError, SystemPowerControl, machine_router,
+    machine_router_with_state,
 };
 
 pub mod axum_http_client;
+pub mod harness;
 
 use axum_http_client::AxumRouterHttpClient;
 
@@ -82,16 +84,24 @@ pub fn wiwynn_gb200_bmc() -> Arc<TestBmc> {
     ))
 }
 
-pub fn dell_poweredge_r750_bmc() -> Arc<TestBmc> {
+pub fn dell_poweredge_r750_router() -> axum::Router {
+    dell_poweredge_r750_router_with_state().0
+}
+
+pub fn dell_poweredge_r750_router_with_state() -> (axum::Router, BmcState) {
     let machine_info = MachineInfo::Host(HostMachineInfo::new(
         HostHardwareType::DellPowerEdgeR750,
         vec![],
     ));
-    let router = machine_router(
+    machine_router_with_state(
         machine_info,
         Arc::new(NoopPowerControl),
         "test-host-id".to_string(),
-    );
+    )
+}
+
+pub fn dell_poweredge_r750_bmc() -> Arc<TestBmc> {
+    let router = dell_poweredge_r750_router();
     let client = AxumRouterHttpClient::new(router);
     let endpoint = Url::parse("https://bmc-mock.local").expect("valid URL");
     let credentials = BmcCredentials::new("root".to_string(), "password".to_string());
@@ -103,6 +113,50 @@ pub fn dell_poweredge_r750_bmc() -> Arc<TestBmc> {
     ))
 }
 
+/// Send a JSON request with `headers` to `router`, for unit tests of the mock's handlers
+#[cfg(test)]
+pub(crate) async fn call_router(
+    router: &axum::Router,
+    method: axum::http::Method,
+    uri: &str,
+    headers: &[(&str, &str)],
+    body: impl ToString,
+) -> axum::response::Response {
+    use tower::ServiceExt;
+
+    let mut builder = axum::http::Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Content-Type", "application/json");
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    router
+        .clone()
+        .oneshot(
+            builder
+                .body(axum::body::Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+/// Status and JSON body of `response`, with an empty body read as `null`
+#[cfg(test)]
+pub(crate) async fn response_json(
+    response: axum::response::Response,
+) -> (axum::http::StatusCode, serde_json::Value) {
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    if body.is_empty() {
+        return (status, serde_json::Value::Null);
+    }
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
 #[cfg(test)]
 mod test {
 
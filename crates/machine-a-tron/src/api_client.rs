@@ -362,6 +362,7 @@ impl ApiClient {
             config: Some(instance_config),
             metadata: None,
             allow_unhealthy_machine: false,
+            idempotency_key: None,
         };
 
         self.0
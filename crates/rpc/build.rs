@@ -628,6 +628,18 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .type_attribute("forge.RackFirmware", "#[derive(serde::Serialize)]")
         .type_attribute("forge.RackFirmwareList", "#[derive(serde::Serialize)]")
+        .type_attribute(
+            "forge.RackFirmwareApplyResponse",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.DeviceUpdateResult",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
+        .type_attribute(
+            "forge.NodeJobInfo",
+            "#[derive(serde::Deserialize, serde::Serialize)]",
+        )
         .type_attribute(
             "forge.MachineHardwareInfoGpu",
             "#[derive(serde::Deserialize, serde::Serialize)]",
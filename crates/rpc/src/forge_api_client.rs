@@ -186,4 +186,60 @@ impl tonic_client_wrapper::ConnectionProvider<ForgeClientT> for ForgeTlsConnecti
     fn connection_url(&self) -> &str {
         self.current_endpoint_url()
     }
+
+    fn request_timeout(&self) -> Option<std::time::Duration> {
+        self.client_config.request_timeout
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tonic_client_wrapper::ConnectionProvider;
+
+    use super::*;
+
+    /// A connection provider which never manages to connect in time.
+    #[derive(Debug)]
+    struct SlowConnectionProvider {
+        request_timeout: Option<Duration>,
+    }
+
+    #[async_trait::async_trait]
+    impl ConnectionProvider<ForgeClientT> for SlowConnectionProvider {
+        async fn provide_connection(&self) -> Result<ForgeClientT, Status> {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Err(Status::unavailable("never connected"))
+        }
+
+        async fn connection_is_stale(&self, _last_connected: SystemTime) -> bool {
+            false
+        }
+
+        fn connection_url(&self) -> &str {
+            "https://slow.example.com"
+        }
+
+        fn request_timeout(&self) -> Option<Duration> {
+            self.request_timeout
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_rpc_is_aborted_at_request_timeout() {
+        let client = ForgeApiClient::build(SlowConnectionProvider {
+            request_timeout: Some(Duration::from_millis(50)),
+        });
+
+        let started = std::time::Instant::now();
+        let err = client
+            .version(crate::protos::forge::VersionRequest::default())
+            .await
+            .expect_err("slow RPC should time out");
+
+        assert_eq!(err.code(), tonic::Code::DeadlineExceeded);
+        assert!(err.message().contains("timed out"), "{}", err.message());
+        assert!(started.elapsed() < Duration::from_secs(60));
+    }
 }
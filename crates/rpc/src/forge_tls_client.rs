@@ -170,6 +170,7 @@ pub struct ForgeClientConfig {
     pub socks_proxy: Option<String>,
     pub connect_retries_max: Option<u32>,
     pub connect_retries_interval: Option<Duration>,
+    pub request_timeout: Option<Duration>,
 }
 
 impl ForgeClientConfig {
@@ -206,6 +207,7 @@ impl ForgeClientConfig {
             // MR though, I think.
             connect_retries_max: Some(3),
             connect_retries_interval: Some(Duration::from_secs(20)),
+            request_timeout: None,
         }
     }
 
@@ -333,6 +335,12 @@ impl ForgeClientConfig {
     pub fn socks_proxy(&mut self, socks_proxy: Option<String>) {
         self.socks_proxy = socks_proxy;
     }
+
+    /// Aborts any single RPC made by a `ForgeApiClient` using this config
+    /// once it has taken longer than `request_timeout`.
+    pub fn request_timeout(&mut self, request_timeout: Option<Duration>) {
+        self.request_timeout = request_timeout;
+    }
 }
 
 // RetryConfig is intended to be a generic
@@ -0,0 +1,351 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Validation of rack firmware configuration JSON.
+//!
+//! Shared by carbide-api, which rejects invalid configurations on create, and the
+//! admin CLI, which validates configuration files offline, so that both agree on
+//! what a valid configuration is.
+
+use serde_json::Value;
+
+use crate::validation::ValidationFinding;
+
+/// Location type of the files that are downloaded for a configuration
+const FIRMWARE_LOCATION_TYPE: &str = "Firmware";
+
+/// Component of a compute tray BoardSKU that carries the Power Shelf firmware. Each of its
+/// subcomponents names the file it is flashed from in `Filename`.
+pub const POWER_SHELF_FIRMWARE_COMPONENT: &str = "Power Shelf FW";
+
+/// Validate a rack firmware configuration, returning everything found wrong with it.
+/// The configuration is valid if none of the findings is an error.
+pub fn validate_config(config: &Value) -> Vec<ValidationFinding> {
+    let mut findings = Vec::new();
+
+    if !config.is_object() {
+        findings.push(ValidationFinding::error(
+            "InvalidConfig",
+            "configuration must be a JSON object",
+            None,
+        ));
+        return findings;
+    }
+
+    if !is_non_empty_string(config.get("Id")) {
+        findings.push(error("InvalidField", "Id", "must be a non-empty string"));
+    }
+
+    let Some(board_skus) = config.get("BoardSKUs").and_then(Value::as_array) else {
+        findings.push(error("InvalidField", "BoardSKUs", "must be an array"));
+        return findings;
+    };
+
+    let mut firmware_locations = 0;
+    for (i, board_sku) in board_skus.iter().enumerate() {
+        let sku_path = format!("BoardSKUs[{i}]");
+        if !is_non_empty_string(board_sku.get("SKUID")) {
+            findings.push(error(
+                "InvalidField",
+                format!("{sku_path}.SKUID"),
+                "must be a non-empty string",
+            ));
+        }
+
+        let Some(firmware) = board_sku.get("Components").and_then(|c| c.get("Firmware")) else {
+            continue;
+        };
+        let Some(firmware) = firmware.as_array() else {
+            findings.push(error(
+                "InvalidField",
+                format!("{sku_path}.Components.Firmware"),
+                "must be an array",
+            ));
+            continue;
+        };
+
+        for (j, component) in firmware.iter().enumerate() {
+            let component_path = format!("{sku_path}.Components.Firmware[{j}]");
+            if !is_non_empty_string(component.get("Component")) {
+                findings.push(error(
+                    "InvalidField",
+                    format!("{component_path}.Component"),
+                    "must be a non-empty string",
+                ));
+            }
+
+            let Some(locations) = component.get("Locations") else {
+                continue;
+            };
+            let Some(locations) = locations.as_array() else {
+                findings.push(error(
+                    "InvalidField",
+                    format!("{component_path}.Locations"),
+                    "must be an array",
+                ));
+                continue;
+            };
+
+            for (k, location) in locations.iter().enumerate() {
+                let location_path = format!("{component_path}.Locations[{k}]");
+                validate_location(location, &location_path, &mut findings);
+                if location.get("Type").and_then(Value::as_str) == Some(FIRMWARE_LOCATION_TYPE) {
+                    firmware_locations += 1;
+                }
+            }
+
+            if component.get("Component").and_then(Value::as_str)
+                == Some(POWER_SHELF_FIRMWARE_COMPONENT)
+            {
+                validate_power_shelf_subcomponents(
+                    component,
+                    locations,
+                    &component_path,
+                    &mut findings,
+                );
+            }
+        }
+    }
+
+    if firmware_locations == 0 {
+        findings.push(warning(
+            "NoFirmwareLocations",
+            "BoardSKUs",
+            format!(
+                "no locations of Type \"{FIRMWARE_LOCATION_TYPE}\", nothing will be downloaded"
+            ),
+        ));
+    }
+
+    findings
+}
+
+fn validate_location(location: &Value, path: &str, findings: &mut Vec<ValidationFinding>) {
+    if !is_non_empty_string(location.get("Location")) {
+        findings.push(error(
+            "InvalidField",
+            format!("{path}.Location"),
+            "must be a non-empty string",
+        ));
+    }
+
+    if location.get("Type").and_then(Value::as_str).is_none() {
+        findings.push(warning(
+            "MissingLocationType",
+            format!("{path}.Type"),
+            "missing, the location will not be downloaded",
+        ));
+    }
+
+    if let Some(sha256) = location.get("SHA256") {
+        let valid = sha256
+            .as_str()
+            .is_some_and(|s| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()));
+        if !valid {
+            findings.push(error(
+                "InvalidSha256",
+                format!("{path}.SHA256"),
+                "must be 64 hex digits",
+            ));
+        }
+    }
+
+    if let Some(size) = location.get("Size")
+        && size.as_u64().is_none()
+    {
+        findings.push(error(
+            "InvalidSize",
+            format!("{path}.Size"),
+            "must be a non-negative integer",
+        ));
+    }
+}
+
+/// Each Power Shelf subcomponent is flashed from the firmware file its `Filename` names, which
+/// has to be one of the files of the component's locations
+fn validate_power_shelf_subcomponents(
+    component: &Value,
+    locations: &[Value],
+    path: &str,
+    findings: &mut Vec<ValidationFinding>,
+) {
+    let Some(subcomponents) = component.get("SubComponents").and_then(Value::as_array) else {
+        return;
+    };
+    let filenames: Vec<&str> = locations
+        .iter()
+        .filter(|location| {
+            location.get("Type").and_then(Value::as_str) == Some(FIRMWARE_LOCATION_TYPE)
+        })
+        .filter_map(|location| location.get("Location").and_then(Value::as_str))
+        .filter_map(|url| url.rsplit('/').next())
+        .collect();
+
+    for (i, subcomponent) in subcomponents.iter().enumerate() {
+        let filename_path = format!("{path}.SubComponents[{i}].Filename");
+        match subcomponent.get("Filename").and_then(Value::as_str) {
+            None => findings.push(error(
+                "MissingSubcomponentFile",
+                filename_path,
+                "must name the firmware file the subcomponent is flashed from",
+            )),
+            Some(filename) if !filenames.contains(&filename) => findings.push(error(
+                "UnknownSubcomponentFile",
+                filename_path,
+                format!("{filename} is not one of the component's firmware files"),
+            )),
+            Some(_) => {}
+        }
+    }
+}
+
+/// An error about the value at `path` of a configuration
+fn error(code: &str, path: impl Into<String>, message: impl Into<String>) -> ValidationFinding {
+    ValidationFinding::error(code, message, Some(path.into()))
+}
+
+/// A warning about the value at `path` of a configuration
+fn warning(code: &str, path: impl Into<String>, message: impl Into<String>) -> ValidationFinding {
+    ValidationFinding::warning(code, message, Some(path.into()))
+}
+
+fn is_non_empty_string(value: Option<&Value>) -> bool {
+    value
+        .and_then(Value::as_str)
+        .is_some_and(|s| !s.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_valid_config() {
+        let config = json!({
+            "Id": "fw-1",
+            "BoardSKUs": [
+                {
+                    "SKUID": "sku-001",
+                    "Components": {
+                        "Firmware": [
+                            {
+                                "Component": "BMC",
+                                "Locations": [
+                                    {
+                                        "Location": "artifactory.example.com/bmc.fwpkg",
+                                        "Type": "Firmware",
+                                        "SHA256": "a".repeat(64),
+                                        "Size": 1024
+                                    }
+                                ]
+                            }
+                        ]
+                    }
+                }
+            ]
+        });
+        assert_eq!(validate_config(&config), []);
+    }
+
+    #[test]
+    fn test_validate_invalid_config() {
+        let config = json!({
+            "BoardSKUs": [
+                {
+                    "Components": {
+                        "Firmware": [
+                            {
+                                "Component": "BMC",
+                                "Locations": [
+                                    {"Location": "", "Type": "Firmware", "SHA256": "abc"},
+                                    {"Location": "artifactory.example.com/bmc.crt"}
+                                ]
+                            }
+                        ]
+                    }
+                }
+            ]
+        });
+        let findings: Vec<String> = validate_config(&config)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            findings,
+            [
+                "[error] InvalidField: Id: must be a non-empty string",
+                "[error] InvalidField: BoardSKUs[0].SKUID: must be a non-empty string",
+                "[error] InvalidField: BoardSKUs[0].Components.Firmware[0].Locations[0].Location: must be a non-empty string",
+                "[error] InvalidSha256: BoardSKUs[0].Components.Firmware[0].Locations[0].SHA256: must be 64 hex digits",
+                "[warning] MissingLocationType: BoardSKUs[0].Components.Firmware[0].Locations[1].Type: missing, the location will not be downloaded",
+            ]
+        );
+
+        let findings = validate_config(&json!({"Id": "fw-1", "BoardSKUs": []}));
+        assert_eq!(findings.len(), 1);
+        assert!(!findings[0].is_error());
+
+        assert!(
+            validate_config(&json!([]))
+                .iter()
+                .any(ValidationFinding::is_error)
+        );
+    }
+
+    #[test]
+    fn test_validate_power_shelf_subcomponent_files() {
+        let config = json!({
+            "Id": "fw-1",
+            "BoardSKUs": [
+                {
+                    "SKUID": "sku-001",
+                    "Components": {
+                        "Firmware": [
+                            {
+                                "Component": POWER_SHELF_FIRMWARE_COMPONENT,
+                                "Locations": [
+                                    {
+                                        "Location": "artifactory.example.com/ps/powershelf_1.0.fwpkg",
+                                        "Type": "Firmware"
+                                    }
+                                ],
+                                "SubComponents": [
+                                    {"Component": "PSU", "Version": "1.2", "Filename": "powershelf_1.0.fwpkg"},
+                                    {"Component": "PMC", "Version": "3.4"},
+                                    {"Component": "FAN", "Version": "0.9", "Filename": "fan.bin"}
+                                ]
+                            }
+                        ]
+                    }
+                }
+            ]
+        });
+        let findings: Vec<String> = validate_config(&config)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            findings,
+            [
+                "[error] MissingSubcomponentFile: BoardSKUs[0].Components.Firmware[0].SubComponents[1].Filename: must name the firmware file the subcomponent is flashed from",
+                "[error] UnknownSubcomponentFile: BoardSKUs[0].Components.Firmware[0].SubComponents[2].Filename: fan.bin is not one of the component's firmware files",
+            ]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * SPDX-FileCopyrightText: Copyright (c) 2026 NVIDIA CORPORATION & AFFILIATES. All rights reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Findings of validations, shared by carbide-api and its clients so that all
+//! validations, e.g. of machines or of rack firmware configurations, report in
+//! the same shape.
+
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// How severe a [ValidationFinding] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ValidationSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl Display for ValidationSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        };
+        f.write_str(s)
+    }
+}
+
+/// A single result of a validation
+///
+/// Validations report all their findings in this shape, so that they can be
+/// rendered uniformly regardless of what was validated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationFinding {
+    pub severity: ValidationSeverity,
+    /// Stable identifier of the check which produced the finding,
+    /// e.g. `FailedValidationTest`
+    pub code: String,
+    /// Human readable description of the finding
+    pub message: String,
+    /// The object the finding is about, e.g. the name of a test
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+}
+
+impl ValidationFinding {
+    pub fn error(
+        code: impl Into<String>,
+        message: impl Into<String>,
+        subject: Option<String>,
+    ) -> Self {
+        Self {
+            severity: ValidationSeverity::Error,
+            code: code.into(),
+            message: message.into(),
+            subject,
+        }
+    }
+
+    pub fn warning(
+        code: impl Into<String>,
+        message: impl Into<String>,
+        subject: Option<String>,
+    ) -> Self {
+        Self {
+            severity: ValidationSeverity::Warning,
+            code: code.into(),
+            message: message.into(),
+            subject,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == ValidationSeverity::Error
+    }
+}
+
+impl Display for ValidationFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}: ", self.severity, self.code)?;
+        if let Some(subject) = &self.subject {
+            write!(f, "{subject}: ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
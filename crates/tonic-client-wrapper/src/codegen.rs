@@ -215,6 +215,24 @@ impl CodeGenerator {
                     self.inner.connection_provider.connection_url()
                 }
 
+                async fn with_request_timeout<T, F>(&self, call: F) -> std::result::Result<T, tonic::Status>
+                where
+                    F: std::future::Future<Output = std::result::Result<T, tonic::Status>>,
+                {
+                    match self.inner.connection_provider.request_timeout() {
+                        Some(timeout) => ::tokio::time::timeout(timeout, call)
+                            .await
+                            .unwrap_or_else(|_| {
+                                Err(tonic::Status::deadline_exceeded(format!(
+                                    "Request to {} timed out after {:?}",
+                                    self.url(),
+                                    timeout
+                                )))
+                            }),
+                        None => call.await,
+                    }
+                }
+
                 #wrapper_methods
             }
         };
@@ -410,7 +428,10 @@ impl CodeGenerator {
                     where
                         S: tonic::IntoStreamingRequest<Message = #input_type>,
                     {
-                        self.connection().await?.#method_name(request).await
+                        self.with_request_timeout(async {
+                            self.connection().await?.#method_name(request).await
+                        })
+                        .await
                     }
                 })
             }
@@ -421,12 +442,15 @@ impl CodeGenerator {
                     where
                         S: tonic::IntoStreamingRequest<Message = #input_type>,
                     {
-                        Ok(self
-                            .connection()
-                            .await?
-                            .#method_name(request)
-                            .await?
-                            .into_inner())
+                        self.with_request_timeout(async {
+                            Ok(self
+                                .connection()
+                                .await?
+                                .#method_name(request)
+                                .await?
+                                .into_inner())
+                        })
+                        .await
                     }
                 })
             }
@@ -435,12 +459,15 @@ impl CodeGenerator {
                 let token_stream = if input_type_str == "()" {
                     quote! {
                         pub async fn #method_name(&self) -> Result<tonic::codec::Streaming<#output_type>, tonic::Status> {
-                            Ok(self
-                                .connection()
-                                .await?
-                                .#method_name(tonic::Request::new(()))
-                                .await?
-                                .into_inner())
+                            self.with_request_timeout(async {
+                                Ok(self
+                                    .connection()
+                                    .await?
+                                    .#method_name(tonic::Request::new(()))
+                                    .await?
+                                    .into_inner())
+                            })
+                            .await
                         }
                     }
                 } else {
@@ -453,23 +480,29 @@ impl CodeGenerator {
                     if has_zero_fields {
                         quote! {
                             pub async fn #method_name(&self) -> Result<tonic::codec::Streaming<#output_type>, tonic::Status> {
-                                Ok(self
-                                    .connection()
-                                    .await?
-                                    .#method_name(tonic::Request::new(#input_type {}))
-                                    .await?
-                                    .into_inner())
+                                self.with_request_timeout(async {
+                                    Ok(self
+                                        .connection()
+                                        .await?
+                                        .#method_name(tonic::Request::new(#input_type {}))
+                                        .await?
+                                        .into_inner())
+                                })
+                                .await
                             }
                         }
                     } else {
                         quote! {
                             pub async fn #method_name<T: Into<#input_type>>(&self, request: T) -> Result<tonic::codec::Streaming<#output_type>, tonic::Status> {
-                                Ok(self
-                                    .connection()
-                                    .await?
-                                    .#method_name(tonic::Request::new(request.into()))
-                                    .await?
-                                    .into_inner())
+                                self.with_request_timeout(async {
+                                    Ok(self
+                                        .connection()
+                                        .await?
+                                        .#method_name(tonic::Request::new(request.into()))
+                                        .await?
+                                        .into_inner())
+                                })
+                                .await
                             }
                         }
                     }
@@ -481,12 +514,15 @@ impl CodeGenerator {
                 let token_stream = if input_type_str == "()" {
                     quote! {
                         pub async fn #method_name(&self) -> Result<#output_type, tonic::Status> {
-                            Ok(self
-                                .connection()
-                                .await?
-                                .#method_name(tonic::Request::new(()))
-                                .await?
-                                .into_inner())
+                            self.with_request_timeout(async {
+                                Ok(self
+                                    .connection()
+                                    .await?
+                                    .#method_name(tonic::Request::new(()))
+                                    .await?
+                                    .into_inner())
+                            })
+                            .await
                         }
                     }
                 } else {
@@ -499,23 +535,29 @@ impl CodeGenerator {
                     if has_zero_fields {
                         quote! {
                             pub async fn #method_name(&self) -> Result<#output_type, tonic::Status> {
-                                Ok(self
-                                    .connection()
-                                    .await?
-                                    .#method_name(tonic::Request::new(#input_type {}))
-                                    .await?
-                                    .into_inner())
+                                self.with_request_timeout(async {
+                                    Ok(self
+                                        .connection()
+                                        .await?
+                                        .#method_name(tonic::Request::new(#input_type {}))
+                                        .await?
+                                        .into_inner())
+                                })
+                                .await
                             }
                         }
                     } else {
                         quote! {
                             pub async fn #method_name<T: Into<#input_type>>(&self, request: T) -> Result<#output_type, tonic::Status> {
-                                Ok(self
-                                    .connection()
-                                    .await?
-                                    .#method_name(tonic::Request::new(request.into()))
-                                    .await?
-                                    .into_inner())
+                                self.with_request_timeout(async {
+                                    Ok(self
+                                        .connection()
+                                        .await?
+                                        .#method_name(tonic::Request::new(request.into()))
+                                        .await?
+                                        .into_inner())
+                                })
+                                .await
                             }
                         }
                     }
@@ -652,12 +694,15 @@ mod tests {
                 wrapper.to_string(),
                 quote! {
                     pub async fn void_rpc(&self) -> Result<crate::test::SomeResponse, tonic::Status> {
-                        Ok(self
-                            .connection()
-                            .await?
-                            .void_rpc(tonic::Request::new(crate::test::VoidRequest {}))
-                            .await?
-                            .into_inner())
+                        self.with_request_timeout(async {
+                            Ok(self
+                                .connection()
+                                .await?
+                                .void_rpc(tonic::Request::new(crate::test::VoidRequest {}))
+                                .await?
+                                .into_inner())
+                        })
+                        .await
                     }
                 }
                     .to_string()
@@ -671,12 +716,15 @@ mod tests {
                 wrapper.to_string(),
                 quote! {
                     pub async fn single_message_rpc<T: Into<crate::test::SingleMessageRequest>>(&self, request: T) -> Result<crate::test::SomeResponse, tonic::Status> {
-                        Ok(self
-                            .connection()
-                            .await?
-                            .single_message_rpc(tonic::Request::new(request.into()))
-                            .await?
-                            .into_inner())
+                        self.with_request_timeout(async {
+                            Ok(self
+                                .connection()
+                                .await?
+                                .single_message_rpc(tonic::Request::new(request.into()))
+                                .await?
+                                .into_inner())
+                        })
+                        .await
                     }
                 }
                 .to_string()
@@ -690,12 +738,15 @@ mod tests {
                 wrapper.to_string(),
                 quote! {
                     pub async fn single_primitive_rpc<T: Into<crate::test::SinglePrimitiveRequest>>(&self, request: T) -> Result<crate::test::SomeResponse, tonic::Status> {
-                        Ok(self
-                            .connection()
-                            .await?
-                            .single_primitive_rpc(tonic::Request::new(request.into()))
-                            .await?
-                            .into_inner())
+                        self.with_request_timeout(async {
+                            Ok(self
+                                .connection()
+                                .await?
+                                .single_primitive_rpc(tonic::Request::new(request.into()))
+                                .await?
+                                .into_inner())
+                        })
+                        .await
                     }
                 }
                     .to_string()
@@ -709,12 +760,15 @@ mod tests {
                 wrapper.to_string(),
                 quote! {
                     pub async fn single_one_of_message_rpc<T: Into<crate::test::SingleOneOfMessageRequest>>(&self, request: T) -> Result<crate::test::SomeResponse, tonic::Status> {
-                        Ok(self
-                            .connection()
-                            .await?
-                            .single_one_of_message_rpc(tonic::Request::new(request.into()))
-                            .await?
-                            .into_inner())
+                        self.with_request_timeout(async {
+                            Ok(self
+                                .connection()
+                                .await?
+                                .single_one_of_message_rpc(tonic::Request::new(request.into()))
+                                .await?
+                                .into_inner())
+                        })
+                        .await
                     }
                 }
                     .to_string()
@@ -728,12 +782,15 @@ mod tests {
                 wrapper.to_string(),
                 quote! {
                     pub async fn single_one_of_primitive_rpc<T: Into<crate::test::SingleOneOfPrimitiveRequest>>(&self, request: T) -> Result<crate::test::SomeResponse, tonic::Status> {
-                        Ok(self
-                            .connection()
-                            .await?
-                            .single_one_of_primitive_rpc(tonic::Request::new(request.into()))
-                            .await?
-                            .into_inner())
+                        self.with_request_timeout(async {
+                            Ok(self
+                                .connection()
+                                .await?
+                                .single_one_of_primitive_rpc(tonic::Request::new(request.into()))
+                                .await?
+                                .into_inner())
+                        })
+                        .await
                     }
                 }
                     .to_string()
@@ -747,12 +804,15 @@ mod tests {
                 wrapper.to_string(),
                 quote! {
                     pub async fn multi_rpc<T: Into<crate::test::MultiRequest>>(&self, request: T) -> Result<crate::test::SomeResponse, tonic::Status> {
-                        Ok(self
-                            .connection()
-                            .await?
-                            .multi_rpc(tonic::Request::new(request.into()))
-                            .await?
-                            .into_inner())
+                        self.with_request_timeout(async {
+                            Ok(self
+                                .connection()
+                                .await?
+                                .multi_rpc(tonic::Request::new(request.into()))
+                                .await?
+                                .into_inner())
+                        })
+                        .await
                     }
                 }
                     .to_string()
@@ -766,12 +826,15 @@ mod tests {
                 wrapper.to_string(),
                 quote! {
                     pub async fn extern_rpc<T: Into<crate::test::ExternRequest>>(&self, request: T) -> Result<crate::test::SomeResponse, tonic::Status> {
-                        Ok(self
-                            .connection()
-                            .await?
-                            .extern_rpc(tonic::Request::new(request.into()))
-                            .await?
-                            .into_inner())
+                        self.with_request_timeout(async {
+                            Ok(self
+                                .connection()
+                                .await?
+                                .extern_rpc(tonic::Request::new(request.into()))
+                                .await?
+                                .into_inner())
+                        })
+                        .await
                     }
                 }
                     .to_string()
@@ -785,12 +848,15 @@ mod tests {
                 wrapper.to_string(),
                 quote! {
                     pub async fn single_streaming_message_rpc<T: Into<crate::test::SingleMessageRequest>>(&self, request: T) -> Result<tonic::codec::Streaming<crate::test::SomeResponse>, tonic::Status> {
-                       Ok(self
-                            .connection()
-                            .await?
-                            .single_streaming_message_rpc(tonic::Request::new(request.into()))
-                            .await?
-                            .into_inner())
+                       self.with_request_timeout(async {
+                           Ok(self
+                               .connection()
+                               .await?
+                               .single_streaming_message_rpc(tonic::Request::new(request.into()))
+                               .await?
+                               .into_inner())
+                       })
+                       .await
                     }
                 }
                 .to_string()
@@ -41,4 +41,10 @@ pub trait ConnectionProvider<T: Clone>: Send + Sync + std::fmt::Debug + 'static
 
     /// Return the server URL for the connection, for debug/logging purposes.
     fn connection_url(&self) -> &str;
+
+    /// Maximum time a single RPC (including establishing the connection) may take before it is
+    /// aborted with `DeadlineExceeded`. `None` (the default) means RPCs never time out.
+    fn request_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
 }